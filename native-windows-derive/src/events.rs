@@ -14,13 +14,13 @@ struct CallbackFunction {
 
 impl Parse for CallbackFunction {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        use syn::group::parse_parens;
-
         let path = input.parse()?;
         let mut args = None;
 
-        if let Ok(parens) = parse_parens(input) {
-            args = Some(parens.content.parse_terminated(syn::Ident::parse)?);
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            args = Some(content.parse_terminated(syn::Ident::parse)?);
         }
 
         Ok(CallbackFunction {
@@ -30,11 +30,35 @@ impl Parse for CallbackFunction {
     }
 }
 
+/// An event identifier. Ex: `OnButtonClick` or `Custom(ID)`
+struct CallbackEventId {
+    ident: syn::Ident,
+    value: Option<syn::Expr>
+}
+
+impl Parse for CallbackEventId {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let mut value = None;
+
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            value = Some(content.parse()?);
+        }
+
+        Ok(CallbackEventId {
+            ident,
+            value
+        })
+    }
+}
+
 /// A single pair of (PATH, CALLBACK_EVENT_ID): [CALLBACK_FUNCTIONS,]
 #[allow(unused)]
 struct CallbackDef {
     field_name: Option<syn::Expr>,
-    callback_id: syn::Ident,
+    callback_id: CallbackEventId,
     callbacks: Punctuated<CallbackFunction, Token![,]>
 }
 
@@ -43,7 +67,7 @@ impl Parse for CallbackDef {
         let content;
         
         /// Try to parse the optional `(PATH, CALLBACK_EVENT_ID)` syntax
-        fn parse_callback_name(input: &mut ParseStream) -> Result<(Option<syn::Expr>, syn::Ident), syn::Error> {
+        fn parse_callback_name(input: &mut ParseStream) -> Result<(Option<syn::Expr>, CallbackEventId), syn::Error> {
             let event_content;
             let _paren_token = parenthesized!(event_content in input);
 
@@ -355,8 +379,13 @@ fn map_callback_args(member: &syn::Ident, args: &Option<Punctuated<syn::Ident, T
 }
 
 
-fn map_event_enum(ident: &syn::Ident) -> syn::Pat {
-    let evt = ident.to_string();
+fn map_event_enum(id: &CallbackEventId) -> syn::Pat {
+    let evt = id.ident.to_string();
+    if let Some(value) = id.value.as_ref() {
+        let ident = &id.ident;
+        return syn::parse2(quote! { Event::#ident(#value) }).expect("Failed to parse event value");
+    }
+
     let pat = match &evt as &str {
         "MousePressLeftUp" | "MousePressLeftDown" | "MousePressRightUp" | "MousePressRightDown" => {
             format!("Event::OnMousePress(MousePressEvent::{})", evt)
@@ -103,6 +103,14 @@ Fields without attributes, even `nwg` types, are left untouched.
 
 Finally, the derive macro also creates a default event handler that will live through the ui struct lifetime. 
 
+The trait `NamedControls` is also implemented on both structs. It returns the controls using their field name. 
+For example, to record the events of the ui with an `EventRecorder`:
+
+```rust
+let recorder = nwg::EventRecorder::new();
+recorder.register_all(&my_ui);
+```
+
 
 # Attributes usage
 
@@ -167,6 +175,18 @@ That's very limiting. For example, if the same callback is used by two different
 
 It's also possible to not use any parameters, ex: `TestApp::callback1()`. 
 
+Custom events raised with `nwg::raise_event` are bound using `Custom(ID)`, where ID is a literal or a constant.
+
+```
+const DOCUMENT_SAVED: u32 = 1;
+
+struct TestApp {
+    #[nwg_control]
+    #[nwg_events( Custom(DOCUMENT_SAVED): [TestApp::saved(SELF, EVT_DATA)] )]
+    window: nwg::Window
+}
+```
+
 Different event types:
 
 ```
@@ -238,6 +258,7 @@ pub fn derive_ui(input: pm::TokenStream) -> pm::TokenStream {
     let partials = ui.partials();
     let layouts = ui.layouts();
     let events = ui.events();
+    let named_controls = ui.named_controls();
 
     let derive_ui = quote! {
         mod #module_name {
@@ -269,6 +290,18 @@ pub fn derive_ui(input: pm::TokenStream) -> pm::TokenStream {
                 }
             }
 
+            impl NamedControls for #struct_name {
+                fn named_controls(&self) -> Vec<(&'static str, ControlHandle)> {
+                    #named_controls
+                }
+            }
+
+            impl NamedControls for #ui_struct_name {
+                fn named_controls(&self) -> Vec<(&'static str, ControlHandle)> {
+                    self.inner.named_controls()
+                }
+            }
+
             impl Drop for #ui_struct_name {
                 /// To make sure that everything is freed without issues, the default handler must be unbound.
                 fn drop(&mut self) {
@@ -304,6 +304,23 @@ impl<'a> ToTokens for NwgUiControls<'a> {
 
 }
 
+pub struct NwgUiNamedControls<'a>(&'a NwgUi<'a>);
+
+impl<'a> ToTokens for NwgUiNamedControls<'a> {
+
+    fn to_tokens(&self, tokens: &mut pm2::TokenStream) {
+        let ui = &self.0;
+        let members: Vec<&syn::Ident> = ui.controls.iter().map(|item| item.id).collect();
+
+        let named_tk = quote! {
+            vec![ #( (stringify!(#members), self.#members.handle) ),* ]
+        };
+
+        named_tk.to_tokens(tokens);
+    }
+
+}
+
 pub struct NwgUiResources<'a>(&'a NwgUi<'a>);
 
 impl<'a> ToTokens for NwgUiResources<'a> {
@@ -660,6 +677,10 @@ impl<'a> NwgUi<'a> {
         NwgUiControls(self)
     }
 
+    pub fn named_controls(&self) -> NwgUiNamedControls {
+        NwgUiNamedControls(self)
+    }
+
     pub fn resources(&self) -> NwgUiResources {
         NwgUiResources(self)
     }
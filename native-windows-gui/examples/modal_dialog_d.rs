@@ -0,0 +1,102 @@
+/*!
+    An example that shows how to run a NWG ui as a modal dialog. Upon clicking on "Open Dialog" button, the main window
+    is disabled until the user closes the dialog. The choice of the user is returned by `nwg::Dialog::run`.
+
+    Unlike `dialog_multithreading_d`, everything runs on the same thread.
+
+    Requires the following features: `cargo run --example modal_dialog_d --features "modal-dialog"`
+*/
+
+extern crate native_windows_gui as nwg;
+extern crate native_windows_derive as nwd;
+
+use nwd::NwgUi;
+use nwg::NativeUi;
+use std::cell::RefCell;
+
+
+/// The dialog UI
+#[derive(Default, NwgUi)]
+pub struct ChoiceDialog {
+    data: RefCell<Option<String>>,
+
+    #[nwg_control(size: (300, 115), position: (650, 300), title: "A dialog", flags: "WINDOW")]
+    window: nwg::Window,
+
+    #[nwg_control(text: "YES", position: (10, 10), size: (130, 95))]
+    #[nwg_events( OnButtonClick: [ChoiceDialog::choose(SELF, CTRL)] )]
+    choice_yes: nwg::Button,
+
+    #[nwg_control(text: "NO", position: (160, 10), size: (130, 95), focus: true)]
+    #[nwg_events( OnButtonClick: [ChoiceDialog::choose(SELF, CTRL)] )]
+    choice_no: nwg::Button,
+}
+
+impl ChoiceDialog {
+
+    fn choose(&self, btn: &nwg::Button) {
+        let mut data = self.data.borrow_mut();
+        if btn == &self.choice_no {
+            *data = Some("No!".to_string());
+        } else if btn == &self.choice_yes {
+            *data = Some("Yes!".to_string());
+        }
+
+        self.window.close();
+    }
+
+}
+
+impl nwg::DialogUi for ChoiceDialog {
+    type Output = Option<String>;
+
+    fn dialog_handle(&self) -> &nwg::ControlHandle {
+        &self.window.handle
+    }
+
+    fn dialog_output(&self) -> Option<String> {
+        self.data.borrow_mut().take()
+    }
+}
+
+
+/// The Main UI
+#[derive(Default, NwgUi)]
+pub struct ModalApp {
+    #[nwg_control(size: (300, 115), position: (300, 300), title: "Modal dialog example", flags: "WINDOW|VISIBLE")]
+    #[nwg_events( OnWindowClose: [ModalApp::exit] )]
+    window: nwg::Window,
+
+    #[nwg_control(size: (280, 25), position: (10, 10), readonly: true)]
+    name_edit: nwg::TextInput,
+
+    #[nwg_control(text: "Open Dialog", size: (280, 60), position: (10, 40), focus: true)]
+    #[nwg_events( OnButtonClick: [ModalApp::open_dialog] )]
+    button: nwg::Button,
+}
+
+impl ModalApp {
+
+    fn exit(&self) {
+        nwg::stop_thread_dispatch();
+    }
+
+    fn open_dialog(&self) {
+        match nwg::Dialog::run(Some(&self.window), ChoiceDialog::default()) {
+            Ok(Some(choice)) => self.name_edit.set_text(&choice),
+            Ok(None) => self.name_edit.set_text("Dialog cancelled"),
+            Err(e) => { nwg::error_message("Error", &format!("{}", e)); }
+        }
+
+        self.button.set_focus();
+    }
+
+}
+
+fn main() {
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    let _app = ModalApp::build_ui(Default::default()).expect("Failed to build UI");
+    nwg::dispatch_thread_events();
+}
@@ -370,9 +370,10 @@ pub fn main() {
     app.canvas.create_context();
     app.canvas.render();
 
-    // Here we use the `with_callback` version of dispatch_thread_events
-    // Internally the callback will be executed almost as fast as `loop { callback() }`
-    nwg::dispatch_thread_events_with_callback(move || {
+    // Here we use the `with_pacing` version of dispatch_thread_events
+    // The canvas is rendered once the events queue is empty and the thread sleeps until new events are received.
+    // Calling `nwg::request_frame()` in the callback would render the canvas continuously.
+    nwg::dispatch_thread_events_with_pacing(nwg::DispatchPacing::OnIdle, move || {
         app.canvas.render();
     });
 }
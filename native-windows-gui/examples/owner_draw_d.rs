@@ -0,0 +1,109 @@
+/*!
+    An application that show how to draw the items of a ListBox and a ComboBox with an `ItemRenderer`.
+    The list box displays users with an avatar and a role, and the combobox displays font families with a preview.
+
+    Requires the following features: `cargo run --example owner_draw_d --features "listbox combobox image-list"`
+*/
+
+
+extern crate native_windows_gui as nwg;
+extern crate native_windows_derive as nwd;
+
+use nwd::NwgUi;
+use nwg::NativeUi;
+use std::fmt;
+
+
+#[derive(Default)]
+pub struct User {
+    name: &'static str,
+    role: &'static str,
+    admin: bool,
+}
+
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+fn render_user(user: &User) -> nwg::OwnerDrawItem {
+    nwg::OwnerDrawItem {
+        image: Some(if user.admin { 0 } else { 1 }),
+        secondary_text: Some(user.role.to_string()),
+        text_color: if user.admin { Some([170, 0, 0]) } else { None },
+        ..nwg::OwnerDrawItem::new(user.name)
+    }
+}
+
+
+#[derive(Default, NwgUi)]
+pub struct OwnerDrawApp {
+    #[nwg_control(size: (300, 400), position: (300, 300), title: "Owner drawn items")]
+    #[nwg_events( OnWindowClose: [OwnerDrawApp::exit], OnInit: [OwnerDrawApp::load_data] )]
+    window: nwg::Window,
+
+    #[nwg_resource(initial: 2, size: (32, 32))]
+    avatars: nwg::ImageList,
+
+    #[nwg_resource(family: "Arial", size: 20)]
+    arial: nwg::Font,
+
+    #[nwg_resource(family: "Times New Roman", size: 20)]
+    times: nwg::Font,
+
+    #[nwg_resource(family: "Courier New", size: 20)]
+    courier: nwg::Font,
+
+    #[nwg_layout(parent: window)]
+    layout: nwg::GridLayout,
+
+    #[nwg_control(collection: vec!["Arial", "Times New Roman", "Courier New"], selected_index: Some(0), renderer: |f: &&'static str| nwg::OwnerDrawItem::new(*f))]
+    #[nwg_layout_item(layout: layout, col: 0, row: 0)]
+    fonts: nwg::ComboBox<&'static str>,
+
+    #[nwg_control(renderer: render_user)]
+    #[nwg_layout_item(layout: layout, col: 0, row: 1, row_span: 5)]
+    users: nwg::ListBox<User>,
+}
+
+impl OwnerDrawApp {
+
+    fn load_data(&self) {
+        self.avatars.add_icon_from_filename("./test_rc/cog.ico").unwrap();
+        self.avatars.add_icon_from_filename("./test_rc/love.ico").unwrap();
+        self.users.set_image_list(Some(&self.avatars));
+
+        self.users.set_collection(vec![
+            User { name: "Jane Doe", role: "Administrator", admin: true },
+            User { name: "John Smith", role: "Editor", admin: false },
+            User { name: "Alex Martin", role: "Reader", admin: false },
+        ]);
+
+        // Each font family is displayed with its own font
+        let fonts = [self.arial.handle, self.times.handle, self.courier.handle];
+        self.fonts.set_renderer(move |family: &&'static str| {
+            let handle = match *family {
+                "Arial" => fonts[0],
+                "Times New Roman" => fonts[1],
+                _ => fonts[2]
+            };
+
+            nwg::OwnerDrawItem { font: Some(nwg::Font { handle }), ..nwg::OwnerDrawItem::new(*family) }
+        }).expect("The combobox has a renderer");
+    }
+
+    fn exit(&self) {
+        nwg::stop_thread_dispatch();
+    }
+
+}
+
+fn main() {
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    let _app = OwnerDrawApp::build_ui(Default::default()).expect("Failed to build UI");
+
+    nwg::dispatch_thread_events();
+}
@@ -65,11 +65,6 @@ pub struct SyncDraw {
     #[nwg_resource(family: "Arial", size: 17)]
     font: nwg::Font,
 
-    // Refresh timer (30 FPS)
-    #[nwg_control(parent: window, interval: 33, stopped: false)]
-    #[nwg_events(OnTimerTick: [SyncDraw::render])]
-    refresh_timer: nwg::Timer,
-
     // Static Buttons
     #[nwg_control(text: "Mode:", position: (10, 10), size: (50, 10), font: Some(&data.font) )]
     mode_lbl: nwg::Label,
@@ -268,7 +263,8 @@ fn init_app() -> Result<(), &'static str> {
     init_shared_memory(&app);
     load_shared_memory(&app);
 
-    nwg::dispatch_thread_events();
+    // Render the canvas at most 30 times per second. The thread sleeps while there are no new events.
+    nwg::dispatch_thread_events_with_pacing(nwg::DispatchPacing::FrameRate(30), || app.render());
 
     // Close the shared memory handle once we are done
     app.data.borrow_mut().close();
@@ -0,0 +1,99 @@
+/*!
+    An application that show how to fill a TreeView from a `TreeModel`. The tree view browses the file system
+    and the content of a directory is only read when the directory is expanded.
+
+    Requires the following features: `cargo run --example tree_model_d --features "tree-view"`
+*/
+
+
+extern crate native_windows_gui as nwg;
+extern crate native_windows_derive as nwd;
+
+use nwd::NwgUi;
+use nwg::NativeUi;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+
+/// Directories are read on demand
+struct FileSystem {
+    root: PathBuf
+}
+
+impl nwg::TreeModel for FileSystem {
+    type Node = PathBuf;
+
+    fn children(&self, node: Option<&PathBuf>) -> Vec<PathBuf> {
+        let path = node.unwrap_or(&self.root);
+        let mut children: Vec<PathBuf> = match std::fs::read_dir(path) {
+            Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(_) => Vec::new()
+        };
+
+        // Directories first
+        children.sort_by_key(|p| (!p.is_dir(), p.clone()));
+        children
+    }
+
+    fn label(&self, node: &PathBuf) -> String {
+        node.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| node.display().to_string())
+    }
+
+    fn has_children(&self, node: &PathBuf) -> bool {
+        node.is_dir()
+    }
+}
+
+
+#[derive(Default, NwgUi)]
+pub struct TreeModelApp {
+    #[nwg_control(size: (400, 500), position: (300, 300), title: "TreeView - File system")]
+    #[nwg_events( OnWindowClose: [TreeModelApp::exit], OnInit: [TreeModelApp::load_data] )]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window)]
+    layout: nwg::GridLayout,
+
+    #[nwg_control(focus: true)]
+    #[nwg_layout_item(layout: layout, col: 0, row: 0, row_span: 9)]
+    #[nwg_events( OnTreeItemSelectionChanged: [TreeModelApp::show_path] )]
+    tree_view: nwg::TreeView,
+
+    #[nwg_control(readonly: true)]
+    #[nwg_layout_item(layout: layout, col: 0, row: 9)]
+    path: nwg::TextInput,
+}
+
+impl TreeModelApp {
+
+    fn load_data(&self) {
+        let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        self.tree_view.set_model(Rc::new(FileSystem { root }));
+    }
+
+    fn show_path(&self) {
+        let path = self.tree_view.selected_item()
+            .and_then(|item| self.tree_view.item_node::<PathBuf>(&item));
+
+        match path {
+            Some(path) => self.path.set_text(&path.display().to_string()),
+            None => self.path.set_text("")
+        }
+    }
+
+    fn exit(&self) {
+        nwg::stop_thread_dispatch();
+    }
+
+}
+
+fn main() {
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    let _app = TreeModelApp::build_ui(Default::default()).expect("Failed to build UI");
+
+    nwg::dispatch_thread_events();
+}
@@ -0,0 +1,103 @@
+/*!
+    An application that show how to use a virtual ListView. The list view displays one million rows
+    without storing a single item in the control. Clicking on a column header sorts the rows.
+
+    Requires the following features: `cargo run --example virtual_list_view_d --features "list-view"`
+*/
+
+
+extern crate native_windows_gui as nwg;
+extern crate native_windows_derive as nwd;
+
+use nwd::NwgUi;
+use nwg::NativeUi;
+use std::rc::Rc;
+use std::cell::Cell;
+
+
+/// Rows are computed on demand
+struct Squares {
+    count: usize
+}
+
+impl nwg::ListViewDataSource for Squares {
+    fn row_count(&self) -> usize {
+        self.count
+    }
+
+    fn cell_text(&self, row: usize, column: usize) -> String {
+        match column {
+            0 => row.to_string(),
+            _ => (row as u64 * row as u64).to_string(),
+        }
+    }
+}
+
+
+#[derive(Default, NwgUi)]
+pub struct VirtualListApp {
+    #[nwg_control(size: (400, 500), position: (300, 300), title: "Virtual ListView")]
+    #[nwg_events( OnWindowClose: [VirtualListApp::exit], OnInit: [VirtualListApp::load_data])]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window)]
+    layout: nwg::GridLayout,
+
+    #[nwg_control(
+        list_style: nwg::ListViewStyle::Detailed, focus: true,
+        flags: "VISIBLE|TAB_STOP|VIRTUAL",
+        ex_flags: nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT,
+    )]
+    #[nwg_layout_item(layout: layout, col: 0, row: 0)]
+    #[nwg_events( OnListViewColumnClick: [VirtualListApp::sort(SELF, EVT_DATA)] )]
+    data_view: nwg::ListView,
+
+    sort: Cell<Option<(usize, nwg::ListViewSort)>>,
+}
+
+impl VirtualListApp {
+
+    fn load_data(&self) {
+        self.data_view.insert_column("Value");
+        self.data_view.insert_column("Square");
+        self.data_view.set_data_source(Rc::new(Squares { count: 1_000_000 })).expect("Failed to set the data source");
+    }
+
+    fn sort(&self, data: &nwg::EventData) {
+        use nwg::ListViewSort::{Ascending, Descending};
+
+        let (_, column) = data.on_list_view_item_index();
+        let sort = match self.sort.get() {
+            Some((c, Ascending)) if c == column => Descending,
+            _ => Ascending,
+        };
+
+        if let Some((previous_column, _)) = self.sort.get() {
+            self.data_view.set_sort_indicator(previous_column, None);
+        }
+
+        // Texts are compared as numbers
+        let value = |v: &str| v.parse::<u64>().unwrap_or(0);
+        match sort {
+            Ascending => self.data_view.sort_by(column, |a, b| value(a).cmp(&value(b))),
+            Descending => self.data_view.sort_by(column, |a, b| value(b).cmp(&value(a))),
+        }
+
+        self.data_view.set_sort_indicator(column, Some(sort));
+        self.sort.set(Some((column, sort)));
+    }
+
+    fn exit(&self) {
+        nwg::stop_thread_dispatch();
+    }
+
+}
+
+fn main() {
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    let _app = VirtualListApp::build_ui(Default::default()).expect("Failed to build UI");
+
+    nwg::dispatch_thread_events();
+}
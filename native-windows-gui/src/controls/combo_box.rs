@@ -3,11 +3,16 @@ use winapi::shared::minwindef::{LPARAM, WPARAM};
 use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
 use crate::win32::base_helper::{check_hwnd, to_utf16, from_utf16};
 use crate::win32::window_helper as wh;
-use crate::{Font, NwgError};
+use crate::win32::owner_draw::{OwnerDraw, bind_owner_draw, item_height};
+use crate::{Font, NwgError, ItemRenderer, RawEventHandler, bind_raw_event_handler_inner, unbind_raw_event_handler};
 use super::{ControlHandle, ControlBase};
 use std::cell::{Ref, RefMut, RefCell};
 use std::fmt::Display;
-use std::mem;
+use std::rc::Rc;
+use std::{mem, ptr};
+
+#[cfg(feature="image-list")]
+use crate::ImageList;
 
 const NOT_BOUND: &'static str = "Combobox is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: Combobox handle is not HWND!";
@@ -30,6 +35,121 @@ bitflags! {
     }
 }
 
+
+/**
+    Decides which items of an editable combobox are displayed while the user types. See `ComboBox::set_matcher`.
+    NWG implements `PrefixMatcher`, `SubstringMatcher` and `FuzzyMatcher`. Use `rank_matches` to test a matcher without a control.
+*/
+pub trait ComboBoxMatcher {
+    /// Returns the score of `item` for the text typed by the user, or `None` if the item must be hidden.
+    /// The items with the highest score are displayed first.
+    fn score(&self, text: &str, item: &str) -> Option<i32>;
+}
+
+/// Displays the items that begin with the text, in the order of the collection. The case is ignored.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PrefixMatcher;
+
+impl ComboBoxMatcher for PrefixMatcher {
+    fn score(&self, text: &str, item: &str) -> Option<i32> {
+        match item.to_lowercase().starts_with(&text.to_lowercase()) {
+            true => Some(0),
+            false => None
+        }
+    }
+}
+
+/// Displays the items that contain the text. The items where the text appears first are displayed first. The case is ignored.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SubstringMatcher;
+
+impl ComboBoxMatcher for SubstringMatcher {
+    fn score(&self, text: &str, item: &str) -> Option<i32> {
+        let item = item.to_lowercase();
+        let position = item.find(&text.to_lowercase())?;
+        Some(-(item[..position].chars().count() as i32))
+    }
+}
+
+/**
+    Displays the items that contain all the characters of the text in the same order, ex: "nwg" matches "Native Windows GUI".
+    Characters that follow each other or that begin a word increase the score, characters far from the previous one decrease it.
+    The case is ignored.
+*/
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FuzzyMatcher;
+
+impl ComboBoxMatcher for FuzzyMatcher {
+    fn score(&self, text: &str, item: &str) -> Option<i32> {
+        const CONSECUTIVE_BONUS: i32 = 5;
+        const WORD_START_BONUS: i32 = 3;
+        const MAX_GAP_PENALTY: usize = 3;
+
+        let item: Vec<char> = item.chars().collect();
+        let mut score = 0;
+        let mut next = 0;
+        let mut previous: Option<usize> = None;
+
+        for c in text.chars() {
+            let index = (next..item.len()).find(|&i| same_char(item[i], c))?;
+
+            let gap = match previous {
+                Some(p) => index - p - 1,
+                None => index
+            };
+
+            if previous.is_some() && gap == 0 {
+                score += CONSECUTIVE_BONUS;
+            }
+
+            if is_word_start(&item, index) {
+                score += WORD_START_BONUS;
+            }
+
+            score -= gap.min(MAX_GAP_PENALTY) as i32;
+            previous = Some(index);
+            next = index + 1;
+        }
+
+        Some(score)
+    }
+}
+
+fn same_char(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+fn is_word_start(item: &[char], index: usize) -> bool {
+    match index {
+        0 => true,
+        _ => {
+            let (previous, current) = (item[index-1], item[index]);
+            !previous.is_alphanumeric() || (previous.is_lowercase() && current.is_uppercase())
+        }
+    }
+}
+
+/**
+    Returns the indices of the items that match `text` with `matcher`, from the highest score to the lowest.
+    Items with the same score keep the order of `items`.
+
+    ```rust
+    use native_windows_gui as nwg;
+    let items = ["Banana", "Apple", "Pineapple"];
+    assert_eq!(nwg::rank_matches(&nwg::SubstringMatcher, "apple", &items), vec![1, 2]);
+    ```
+*/
+pub fn rank_matches<S: AsRef<str>>(matcher: &dyn ComboBoxMatcher, text: &str, items: &[S]) -> Vec<usize> {
+    let mut scores: Vec<(i32, usize)> = items.iter()
+        .enumerate()
+        .filter_map(|(index, item)| matcher.score(text, item.as_ref()).map(|score| (score, index)))
+        .collect();
+
+    scores.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scores.into_iter().map(|(_, index)| index).collect()
+}
+
+
 /**
 A combo box consists of a list and a selection field. The list presents the options that a user can select,
 and the selection field displays the current selection.
@@ -46,11 +166,15 @@ Requires the `combobox` feature.
   * `collection`:     The default collection of the combobox
   * `selected_index`: The default selected index. None means no values are selected.  
   * `focus`:          The control receive focus after being created
+  * `editable`:       If the user can type a text in the combobox
+  * `matcher`:        Filters the items with the text typed by the user. See `ComboBox::set_matcher`
+  * `renderer`:       Draws the items with an `ItemRenderer` instead of their display value. See `OwnerDrawItem`
 
 **Control events:**
   * `OnComboBoxClosed`: When the combobox dropdown is closed
   * `OnComboBoxDropdown`: When the combobox dropdown is opened
   * `OnComboxBoxSelection`: When a new value in a combobox is choosen
+  * `OnComboBoxTextChanged`: When the user changed the text of an editable combobox
   * `MousePress(_)`: Generic mouse press events on the checkbox
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
@@ -73,7 +197,24 @@ fn build_combobox(combo: &mut nwg::ComboBox<&'static str>, window: &nwg::Window)
 #[derive(Default)]
 pub struct ComboBox<D: Display+Default> {
     pub handle: ControlHandle,
-    collection: RefCell<Vec<D>>
+    collection: RefCell<Vec<D>>,
+    filter: Rc<ComboBoxFilter>,
+    renderer: RefCell<Option<Box<dyn ItemRenderer<D>>>>,
+    owner_draw: Rc<OwnerDraw>,
+    handler0: RefCell<Option<RawEventHandler>>,
+    handler1: RefCell<Option<RawEventHandler>>,
+}
+
+/// The state of the filter of an editable combobox
+#[derive(Default)]
+struct ComboBoxFilter {
+    matcher: RefCell<Option<Box<dyn ComboBoxMatcher>>>,
+
+    /// The display value of the items of the collection. Only filled if there is a matcher.
+    items: RefCell<Vec<String>>,
+
+    /// The indices in the collection of the displayed items. `None` if every item is displayed.
+    matches: RefCell<Option<Vec<usize>>>,
 }
 
 impl<D: Display+Default> ComboBox<D> {
@@ -88,10 +229,79 @@ impl<D: Display+Default> ComboBox<D> {
             font: None,
             collection: None,
             selected_index: None,
-            parent: None
+            parent: None,
+            editable: false,
+            matcher: None,
+            renderer: None,
+        }
+    }
+
+    /// Replaces the renderer of the items and draws the items again.
+    /// Returns an error if the combobox was not created with the `renderer` builder parameter, as the owner draw style cannot be added afterward.
+    pub fn set_renderer<R: ItemRenderer<D> + 'static>(&self, renderer: R) -> Result<(), NwgError> {
+        if self.renderer.borrow().is_none() {
+            return Err(NwgError::control_create("The renderer of a ComboBox can only be replaced if it was created with the `renderer` builder parameter"));
+        }
+
+        *self.renderer.borrow_mut() = Some(Box::new(renderer));
+        self.update_items();
+        Ok(())
+    }
+
+    /// Sets the image list used by the `image` of the items of an owner drawn combobox. See `OwnerDrawItem`
+    #[cfg(feature="image-list")]
+    pub fn set_image_list(&self, list: Option<&ImageList>) {
+        self.owner_draw.image_list.set(list.map(|l| l.handle).unwrap_or(ptr::null_mut()));
+        self.update_items();
+    }
+
+    /// Returns the image list of an owner drawn combobox. The image list is not owned by the returned value.
+    #[cfg(feature="image-list")]
+    pub fn image_list(&self) -> Option<ImageList> {
+        match self.owner_draw.image_list.get() {
+            h if h.is_null() => None,
+            handle => Some(ImageList { handle, owned: false })
         }
     }
 
+    /**
+        Filters the items of an editable combobox with the text typed by the user. The dropdown is opened with the matching items,
+        ordered by their score. All the items are displayed again when the dropdown is closed.
+
+        While the items are filtered, `selection` and `set_selection` still use the indices of the collection.
+    */
+    pub fn set_matcher<M: ComboBoxMatcher + 'static>(&self, matcher: M) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        *self.filter.matcher.borrow_mut() = Some(Box::new(matcher));
+        self.update_items();
+
+        if self.handler0.borrow().is_none() {
+            self.bind_filter_handler(handle);
+        }
+    }
+
+    /// Removes the matcher of the combobox. All the items are displayed again.
+    pub fn reset_matcher(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        self.filter.clear(handle);
+        self.filter.matcher.borrow_mut().take();
+        self.filter.items.borrow_mut().clear();
+    }
+
+    /// Returns the text of the combobox. For an editable combobox, this is the text typed by the user.
+    pub fn text(&self) -> String {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_text(handle) }
+    }
+
+    /// Sets the text of an editable combobox. Does not filter the items.
+    pub fn set_text<'a>(&self, v: &'a str) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_text(handle, v) }
+    }
+
     /// Remove the item at the selected index and returns it.
     /// Panic of the index is out of bounds
     pub fn remove(&self, index: usize) -> D {
@@ -99,10 +309,13 @@ impl<D: Display+Default> ComboBox<D> {
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
+        self.filter.clear(handle);
         wh::send_message(handle, CB_DELETESTRING, index as WPARAM, 0);
 
-        let mut col_ref = self.collection.borrow_mut();
-        col_ref.remove(index)
+        let item = self.collection.borrow_mut().remove(index);
+        self.update_items();
+
+        item
     }
 
     /// Sort the inner collection by the display value of it's items and update the view
@@ -112,6 +325,7 @@ impl<D: Display+Default> ComboBox<D> {
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
+        self.filter.matches.borrow_mut().take();
         self.clear_inner(handle);
 
         let mut col = self.collection.borrow_mut();
@@ -126,6 +340,9 @@ impl<D: Display+Default> ComboBox<D> {
             let display_os = to_utf16(&display);
             wh::send_message(handle, CB_ADDSTRING, 0, display_os.as_ptr() as LPARAM);
         }
+
+        drop(col);
+        self.update_items();
     }
 
     /// Show or hide the dropdown of the combox
@@ -145,7 +362,12 @@ impl<D: Display+Default> ComboBox<D> {
         let index = wh::send_message(handle, CB_GETCURSEL, 0, 0);
 
         if index == CB_ERR { None }
-        else { Some(index as usize) }
+        else {
+            match self.filter.matches.borrow().as_ref() {
+                Some(matches) => matches.get(index as usize).cloned(),
+                None => Some(index as usize)
+            }
+        }
     }
 
     /// Return the display value of the currenctly selected item
@@ -180,6 +402,8 @@ impl<D: Display+Default> ComboBox<D> {
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
+        self.filter.clear(handle);
+
         let index = index.unwrap_or(-1isize as usize);
         wh::send_message(handle, CB_SETCURSEL, index, 0);
     }
@@ -191,6 +415,8 @@ impl<D: Display+Default> ComboBox<D> {
         use winapi::um::winuser::{CB_SELECTSTRING, CB_ERR};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        self.filter.clear(handle);
         
         let os_string = to_utf16(value);
 
@@ -208,12 +434,15 @@ impl<D: Display+Default> ComboBox<D> {
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
+        self.filter.clear(handle);
+
         let display = format!("{}", item);
         let display_os = to_utf16(&display);
 
         wh::send_message(handle, CB_ADDSTRING, 0, display_os.as_ptr() as LPARAM);
 
         self.collection.borrow_mut().push(item);
+        self.update_items();
     }
 
     /// Insert an item in the collection and the control.
@@ -225,6 +454,8 @@ impl<D: Display+Default> ComboBox<D> {
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
+        self.filter.clear(handle);
+
         let display = format!("{}", item);
         let display_os = to_utf16(&display);
 
@@ -236,6 +467,9 @@ impl<D: Display+Default> ComboBox<D> {
         }
 
         wh::send_message(handle, CB_INSERTSTRING, index, display_os.as_ptr() as LPARAM);
+
+        drop(col);
+        self.update_items();
     }
 
     /// Update the visual of the control with the inner collection.
@@ -245,6 +479,7 @@ impl<D: Display+Default> ComboBox<D> {
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
+        self.filter.matches.borrow_mut().take();
         self.clear_inner(handle);
 
         for item in self.collection.borrow().iter() {
@@ -253,6 +488,8 @@ impl<D: Display+Default> ComboBox<D> {
             
             wh::send_message(handle, CB_ADDSTRING, 0, display_os.as_ptr() as LPARAM);
         }
+
+        self.update_items();
     }
 
     /// Set the item collection of the combobox. Return the old collection
@@ -261,6 +498,7 @@ impl<D: Display+Default> ComboBox<D> {
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
+        self.filter.matches.borrow_mut().take();
         self.clear_inner(handle);
 
         for item in col.iter() {
@@ -271,6 +509,9 @@ impl<D: Display+Default> ComboBox<D> {
 
         let mut col_ref = self.collection.borrow_mut();
         mem::swap::<Vec<D>>(&mut col_ref, &mut col);
+        drop(col_ref);
+
+        self.update_items();
 
         col
     }
@@ -302,6 +543,7 @@ impl<D: Display+Default> ComboBox<D> {
     pub fn set_font(&self, font: Option<&Font>) {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         unsafe { wh::set_window_font(handle, font.map(|f| f.handle), true); }
+        self.update_items();
     }
 
     
@@ -402,10 +644,146 @@ impl<D: Display+Default> ComboBox<D> {
         wh::send_message(handle, CB_RESETCONTENT, 0, 0);
     }
 
+    /// Copies the display value of the items for the filter handler and renders the owner drawn items.
+    /// The handlers cannot access the collection.
+    fn update_items(&self) {
+        use winapi::um::winuser::{CB_SETITEMHEIGHT, InvalidateRect};
+
+        if self.filter.matcher.borrow().is_some() {
+            let items = self.collection.borrow().iter().map(|i| format!("{}", i)).collect();
+            *self.filter.items.borrow_mut() = items;
+        }
+
+        if let Some(renderer) = self.renderer.borrow().as_ref() {
+            let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+            let items = self.collection.borrow().iter().map(|i| renderer.render(i)).collect();
+            *self.owner_draw.items.borrow_mut() = items;
+
+            let height = item_height(handle, &self.owner_draw);
+            wh::send_message(handle, CB_SETITEMHEIGHT, 0, height as LPARAM);
+            unsafe { InvalidateRect(handle, ptr::null(), 1); }
+        }
+    }
+
+    /// Filters the items when the text changes and displays all the items again when the dropdown is closed
+    fn bind_filter_handler(&self, handle: HWND) {
+        use winapi::um::winuser::{WM_COMMAND, CBN_EDITCHANGE, CBN_CLOSEUP};
+        use winapi::shared::minwindef::HIWORD;
+        use winapi::shared::basetsd::UINT_PTR;
+
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let filter = self.filter.clone();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as UINT_PTR, move |_hwnd, msg, w, l| {
+            if msg != WM_COMMAND || l as HWND != handle {
+                return None;
+            }
+
+            match HIWORD(w as u32) as u16 {
+                CBN_EDITCHANGE => filter.apply(handle),
+                CBN_CLOSEUP => filter.clear(handle),
+                _ => {}
+            }
+
+            None
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+}
+
+impl ComboBoxFilter {
+
+    /// Displays the items matching the text of the combobox in the dropdown
+    fn apply(&self, handle: HWND) {
+        use winapi::um::winuser::{CB_SHOWDROPDOWN, CB_GETDROPPEDSTATE, SetCursor, LoadCursorW, IDC_ARROW};
+
+        let text = unsafe { wh::get_window_text(handle) };
+        let matches = match self.matcher.borrow().as_ref() {
+            Some(matcher) => rank_matches(matcher.as_ref(), &text, &self.items.borrow()[..]),
+            None => { return; }
+        };
+
+        if text.is_empty() || matches.is_empty() {
+            self.clear(handle);
+            wh::send_message(handle, CB_SHOWDROPDOWN, 0, 0);
+            return;
+        }
+
+        {
+            let items = self.items.borrow();
+            replace_items(handle, matches.iter().map(|&i| items[i].as_str()), &text);
+        }
+
+        *self.matches.borrow_mut() = Some(matches);
+
+        if wh::send_message(handle, CB_GETDROPPEDSTATE, 0, 0) == 0 {
+            wh::send_message(handle, CB_SHOWDROPDOWN, 1, 0);
+
+            // Opening the dropdown replaces the text with the first item and hides the cursor
+            restore_text(handle, &text);
+            unsafe { SetCursor(LoadCursorW(ptr::null_mut(), IDC_ARROW)); }
+        }
+    }
+
+    /// Displays all the items again. The selected item is kept.
+    fn clear(&self, handle: HWND) {
+        use winapi::um::winuser::{CB_GETCURSEL, CB_SETCURSEL, CB_ERR};
+
+        let matches = match self.matches.borrow_mut().take() {
+            Some(m) => m,
+            None => { return; }
+        };
+
+        let selected = match wh::send_message(handle, CB_GETCURSEL, 0, 0) {
+            CB_ERR => None,
+            index => matches.get(index as usize).cloned()
+        };
+
+        let text = unsafe { wh::get_window_text(handle) };
+        replace_items(handle, self.items.borrow().iter().map(|i| i.as_str()), &text);
+
+        if let Some(index) = selected {
+            wh::send_message(handle, CB_SETCURSEL, index as WPARAM, 0);
+        }
+    }
+
+}
+
+/// Replaces the items displayed in the control. `CB_RESETCONTENT` also clears the text of an editable combobox, so `text` is set again.
+fn replace_items<'a, I: Iterator<Item=&'a str>>(handle: HWND, items: I, text: &str) {
+    use winapi::um::winuser::{CB_RESETCONTENT, CB_ADDSTRING};
+
+    wh::send_message(handle, CB_RESETCONTENT, 0, 0);
+
+    for item in items {
+        let display_os = to_utf16(item);
+        wh::send_message(handle, CB_ADDSTRING, 0, display_os.as_ptr() as LPARAM);
+    }
+
+    restore_text(handle, text);
+}
+
+/// Sets the text of an editable combobox and moves the caret at the end of the text
+fn restore_text(handle: HWND, text: &str) {
+    use winapi::um::winuser::CB_SETEDITSEL;
+    use winapi::shared::minwindef::MAKELONG;
+
+    unsafe { wh::set_window_text(handle, text); }
+
+    let end = text.encode_utf16().count() as u16;
+    wh::send_message(handle, CB_SETEDITSEL, 0, MAKELONG(end, end) as LPARAM);
 }
 
 impl<D: Display+Default> Drop for ComboBox<D> {
     fn drop(&mut self) {
+        for handler in [&self.handler0, &self.handler1].iter() {
+            if let Some(h) = handler.borrow_mut().take() {
+                drop(unbind_raw_event_handler(&h));
+            }
+        }
+
         self.handle.destroy();
     }
 }
@@ -419,7 +797,10 @@ pub struct ComboBoxBuilder<'a, D: Display+Default> {
     font: Option<&'a Font>,
     collection: Option<Vec<D>>,
     selected_index: Option<usize>,
-    parent: Option<ControlHandle>
+    parent: Option<ControlHandle>,
+    editable: bool,
+    matcher: Option<Box<dyn ComboBoxMatcher>>,
+    renderer: Option<Box<dyn ItemRenderer<D>>>,
 }
 
 impl<'a, D: Display+Default> ComboBoxBuilder<'a, D> {
@@ -469,8 +850,25 @@ impl<'a, D: Display+Default> ComboBoxBuilder<'a, D> {
         self
     }
 
+    pub fn editable(mut self, editable: bool) -> ComboBoxBuilder<'a, D> {
+        self.editable = editable;
+        self
+    }
+
+    pub fn matcher<M: ComboBoxMatcher + 'static>(mut self, matcher: M) -> ComboBoxBuilder<'a, D> {
+        self.matcher = Some(Box::new(matcher));
+        self
+    }
+
+    pub fn renderer<R: ItemRenderer<D> + 'static>(mut self, renderer: R) -> ComboBoxBuilder<'a, D> {
+        self.renderer = Some(Box::new(renderer));
+        self
+    }
+
 
     pub fn build(self, out: &mut ComboBox<D>) -> Result<(), NwgError> {
+        use winapi::um::winuser::{CBS_DROPDOWNLIST, CBS_DROPDOWN, CBS_AUTOHSCROLL, CBS_OWNERDRAWFIXED, CBS_HASSTRINGS};
+
         let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
 
         let parent = match self.parent {
@@ -478,15 +876,40 @@ impl<'a, D: Display+Default> ComboBoxBuilder<'a, D> {
             None => Err(NwgError::no_parent("ComboBox"))
         }?;
 
+        // An editable combobox uses a dropdown instead of a dropdown list
+        let mut forced_flags = match self.editable {
+            true => (out.forced_flags() & !CBS_DROPDOWNLIST) | CBS_DROPDOWN | CBS_AUTOHSCROLL,
+            false => out.forced_flags()
+        };
+
+        // The items of an owner drawn combobox are painted by NWG using the renderer
+        if self.renderer.is_some() {
+            forced_flags |= CBS_OWNERDRAWFIXED | CBS_HASSTRINGS;
+        }
+
         out.handle = ControlBase::build_hwnd()
             .class_name(out.class_name())
-            .forced_flags(out.forced_flags())
+            .forced_flags(forced_flags)
             .flags(flags)
             .size(self.size)
             .position(self.position)
             .parent(Some(parent))
             .build()?;
 
+        if let Some(renderer) = self.renderer {
+            let handle = out.handle.hwnd().unwrap();
+            let filter = out.filter.clone();
+
+            // The displayed items may be filtered by the text of an editable combobox
+            let item_index = move |index: usize| match filter.matches.borrow().as_ref() {
+                Some(matches) => matches.get(index).cloned(),
+                None => Some(index)
+            };
+
+            *out.renderer.borrow_mut() = Some(renderer);
+            *out.handler1.borrow_mut() = Some(bind_owner_draw(handle, out.owner_draw.clone(), item_index));
+        }
+
         if self.font.is_some() {
             out.set_font(self.font);
         } else {
@@ -501,6 +924,12 @@ impl<'a, D: Display+Default> ComboBoxBuilder<'a, D> {
             out.set_selection(self.selected_index);
         }
 
+        if let Some(matcher) = self.matcher {
+            *out.filter.matcher.borrow_mut() = Some(matcher);
+            out.update_items();
+            out.bind_filter_handler(out.handle.hwnd().unwrap());
+        }
+
         out.set_enabled(self.enabled);
 
         if self.focus {
@@ -1,8 +1,11 @@
 use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
+use winapi::um::commctrl::{DTS_TIMEFORMAT, DTS_UPDOWN};
+use winapi::um::minwinbase::SYSTEMTIME;
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::{to_utf16, check_hwnd};
 use crate::{Font, NwgError};
-use super::{ControlBase, ControlHandle};
+use super::{ControlBase, ControlHandle, DatePickerValue};
+use super::date_picker_value::{to_systemtime, from_systemtime};
 
 const NOT_BOUND: &'static str = "DatePicker is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: DatePicker handle is not HWND!";
@@ -17,26 +20,18 @@ bitflags! {
         * VISIBLE:  The date picker is immediatly visible after creation
         * DISABLED: The date picker cannot be interacted with by the user. It also has a grayed out look.
         * TAB_STOP: The control can be selected using tab navigation
+        * TIME:     The control picks a time of the day instead of a date. Implies UP_DOWN.
+        * UP_DOWN:  Use up-down arrows to edit the value instead of a calendar dropdown
     */
     pub struct DatePickerFlags: u32 {
         const VISIBLE = WS_VISIBLE;
         const DISABLED = WS_DISABLED;
         const TAB_STOP = WS_TABSTOP;
+        const TIME = DTS_TIMEFORMAT;
+        const UP_DOWN = DTS_UPDOWN;
     }
 }
 
-/**
-    A date struct that can be passed to a date time picker control.
-    Fields are self explanatory.
-*/
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub struct DatePickerValue {
-    pub year: u16,
-    pub month: u16,
-    pub day: u16
-}
-
-
 
 /**
 A date and time picker (DTP) control provides a simple and intuitive interface through which to exchange date and time information with a user.
@@ -49,13 +44,15 @@ Requires the `datetime-picker` feature.
   * `size`:     The dtp size.
   * `position`: The dtp position.
   * `enabled`:  If the dtp can be used by the user. It also has a grayed out look if disabled.
-  * `flags`:    A combination of the DatePickerFlags values.
+  * `flags`:    A combination of the DatePickerFlags values. Use `DatePickerFlags::TIME` to pick a time instead of a date.
   * `font`:     The font used for the dtp text
   * `date`:     The default date as a `DatePickerValue` value
   * `format`:   The format of the date. See the `set_format` method.
   * `range`:    The accepted range of dates. The value is inclusive.
   * `focus`:    The control receive focus after being created
 
+Invalid dates or dates outside of the range make `build` return a `NwgError::DateTimeError`.
+
 **Control events:**
   * `OnDatePickerClosed`: When the datepicker dropdown is closed
   * `OnDatePickerDropdown`: When the datepicker dropdown is opened
@@ -67,9 +64,9 @@ Requires the `datetime-picker` feature.
 ```rust
 use native_windows_gui as nwg;
 fn build_dtp(date: &mut nwg::DatePicker, window: &nwg::Window) {
-    let v = nwg::DatePickerValue { year: 2000, month: 10, day: 5 };
-    let v1 = nwg::DatePickerValue { year: 2000, month: 10, day: 5 };
-    let v2 = nwg::DatePickerValue { year: 2012, month: 10, day: 5 };
+    let v = nwg::DatePickerValue::new(2000, 10, 5);
+    let v1 = nwg::DatePickerValue::new(2000, 10, 5);
+    let v2 = nwg::DatePickerValue::new(2012, 10, 5);
     
     nwg::DatePicker::builder()
         .size((200, 300))
@@ -80,6 +77,19 @@ fn build_dtp(date: &mut nwg::DatePicker, window: &nwg::Window) {
         .parent(window)
         .build(date);
 }
+
+fn build_time_picker(time: &mut nwg::DatePicker, window: &nwg::Window) {
+    use nwg::DatePickerFlags as F;
+
+    let now = nwg::DatePickerValue::from_system_time_local(std::time::SystemTime::now()).unwrap();
+
+    nwg::DatePicker::builder()
+        .flags(F::VISIBLE | F::TAB_STOP | F::TIME)
+        .date(Some(now))
+        .format(Some("HH':'mm':'ss"))
+        .parent(window)
+        .build(time);
+}
 ```
 */
 #[derive(Default, PartialEq, Eq)]
@@ -176,13 +186,12 @@ impl DatePicker {
     }
 
     /**
-        Return the time set in the control in a `PickerDate` structure.  
+        Return the date and time set in the control in a `DatePickerValue` structure.  
         Return None if `optional` was set and the checkbox is not checked.  
         Note: use `get_text` to get the text value of the control.
     */
     pub fn value(&self) -> Option<DatePickerValue> {
         use winapi::um::commctrl::{GDT_VALID, DTM_GETSYSTEMTIME};
-        use winapi::um::minwinbase::SYSTEMTIME;
         use std::mem;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
@@ -191,47 +200,51 @@ impl DatePicker {
 
         let r = unsafe{ wh::send_message(handle, DTM_GETSYSTEMTIME, 0, mem::transmute(&mut syst)) };
         match r {
-            GDT_VALID => Some(DatePickerValue {
-                year: syst.wYear,
-                month: syst.wMonth,
-                day: syst.wDay
-            }),
+            GDT_VALID => Some(from_systemtime(&syst)),
             _ => None
         }
     }
 
     /**
-        Set the time set in the control in a `PickerDate` structure.  
+        Set the date and time of the control from a `DatePickerValue` structure.  
         If `None` is passed, this clears the checkbox.
+
+        Returns a `NwgError::DateTimeError` if the value is not a valid date or if it is outside of the control range.
     */
-    pub fn set_value(&self, date: Option<DatePickerValue>) {
+    pub fn set_value(&self, date: Option<DatePickerValue>) -> Result<(), NwgError> {
         use winapi::um::commctrl::{DTM_SETSYSTEMTIME, GDT_VALID, GDT_NONE};
         use winapi::shared::minwindef::{WPARAM, LPARAM};
-        use winapi::um::minwinbase::SYSTEMTIME;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
-        match date {
-            Some(date) => {
-                let syst: SYSTEMTIME = SYSTEMTIME{ 
-                    wYear: date.year, 
-                    wMonth: date.month, 
-                    wDay: date.day, 
-                    wDayOfWeek:0, wHour:0, wMinute:0, wSecond:0, wMilliseconds: 0 
-                };
-
-                wh::send_message(handle, DTM_SETSYSTEMTIME, GDT_VALID as WPARAM, &syst as *const SYSTEMTIME as LPARAM);
-            },
-            None => { 
-                wh::send_message(handle, DTM_SETSYSTEMTIME, GDT_NONE as WPARAM, 0); 
+        let date = match date {
+            Some(date) => date,
+            None => {
+                wh::send_message(handle, DTM_SETSYSTEMTIME, GDT_NONE as WPARAM, 0);
+                return Ok(());
             }
         };
+
+        date.validate()?;
+
+        let (min, max) = unsafe { get_range(handle) };
+        if min.map(|min| date < min).unwrap_or(false) || max.map(|max| date > max).unwrap_or(false) {
+            return Err(NwgError::date_time(format!("{} is outside of the DatePicker range", date.to_iso8601())));
+        }
+
+        let syst = to_systemtime(&date);
+        match wh::send_message(handle, DTM_SETSYSTEMTIME, GDT_VALID as WPARAM, &syst as *const SYSTEMTIME as LPARAM) {
+            0 => Err(NwgError::date_time(format!("The DatePicker rejected {}", date.to_iso8601()))),
+            _ => Ok(())
+        }
     }
 
-    /// Gets the current minimum and maximum allowable system times for a date and time picker control.
+    /**
+        Gets the current minimum and maximum allowable system times for a date and time picker control.
+        A limit that was never set is returned zeroed.
+    */
     pub fn range(&self) -> [DatePickerValue; 2] {
         use winapi::um::commctrl::DTM_GETRANGE;
-        use winapi::um::minwinbase::SYSTEMTIME;
         use winapi::shared::minwindef::{LPARAM};
         use std::mem;
 
@@ -241,28 +254,33 @@ impl DatePicker {
 
         wh::send_message(handle, DTM_GETRANGE, 0, &mut tr as *mut [SYSTEMTIME; 2] as LPARAM); 
     
-        [
-            DatePickerValue { year: tr[0].wYear, month: tr[0].wMonth, day: tr[0].wDay },
-            DatePickerValue { year: tr[1].wYear, month: tr[1].wMonth, day: tr[1].wDay },
-        ]
-        
+        [from_systemtime(&tr[0]), from_systemtime(&tr[1])]
     }
     
-    /// Sets the minimum and maximum allowable system times for a date and time picker control. 
-    pub fn set_range(&self, r: &[DatePickerValue; 2]) {
+    /**
+        Sets the minimum and maximum allowable system times for a date and time picker control. 
+
+        Returns a `NwgError::DateTimeError` if one of the values is not a valid date or if the minimum is after the maximum.
+    */
+    pub fn set_range(&self, r: &[DatePickerValue; 2]) -> Result<(), NwgError> {
         use winapi::um::commctrl::DTM_SETRANGE;
         use winapi::um::commctrl::{GDTR_MIN, GDTR_MAX};
-        use winapi::um::minwinbase::SYSTEMTIME;
         use winapi::shared::minwindef::{LPARAM};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
-        let values = [
-            SYSTEMTIME { wYear: r[0].year , wMonth: r[0].month, wDayOfWeek: 0, wDay: r[0].day, wHour: 0, wMinute: 0, wSecond: 0, wMilliseconds: 0 },
-            SYSTEMTIME { wYear: r[1].year , wMonth: r[1].month, wDayOfWeek: 0, wDay: r[1].day, wHour: 0, wMinute: 0, wSecond: 0, wMilliseconds: 0 }
-        ];
+        r[0].validate()?;
+        r[1].validate()?;
+        if r[0] > r[1] {
+            return Err(NwgError::date_time(format!("The range minimum ({}) is after the maximum ({})", r[0].to_iso8601(), r[1].to_iso8601())));
+        }
+
+        let values = [to_systemtime(&r[0]), to_systemtime(&r[1])];
 
-        wh::send_message(handle, DTM_SETRANGE, GDTR_MIN | GDTR_MAX, &values as *const [SYSTEMTIME; 2] as LPARAM);
+        match wh::send_message(handle, DTM_SETRANGE, GDTR_MIN | GDTR_MAX, &values as *const [SYSTEMTIME; 2] as LPARAM) {
+            0 => Err(NwgError::date_time("The DatePicker rejected the range")),
+            _ => Ok(())
+        }
     }
 
     /// Return the font of the control
@@ -452,12 +470,12 @@ impl<'a> DatePickerBuilder<'a> {
             out.set_font(Font::global_default().as_ref());
         }
 
-        if self.date.is_some() {
-            out.set_value(self.date)
+        if self.range.is_some() {
+            out.set_range(&self.range.unwrap())?;
         }
 
-        if self.range.is_some() {
-            out.set_range(&self.range.unwrap());
+        if self.date.is_some() {
+            out.set_value(self.date)?;
         }
 
         if self.format.is_some() {
@@ -488,3 +506,18 @@ unsafe fn get_dtp_info(handle: HWND) -> DATETIMEPICKERINFO {
 
     dtp_info
 }
+
+/// Returns the minimum and the maximum of the control, if they are set
+unsafe fn get_range(handle: HWND) -> (Option<DatePickerValue>, Option<DatePickerValue>) {
+    use winapi::um::commctrl::{DTM_GETRANGE, GDTR_MIN, GDTR_MAX};
+    use winapi::shared::minwindef::{LPARAM, WPARAM};
+    use std::mem;
+
+    let mut tr: [SYSTEMTIME; 2] = mem::zeroed();
+    let limits = wh::send_message(handle, DTM_GETRANGE, 0, &mut tr as *mut [SYSTEMTIME; 2] as LPARAM) as WPARAM;
+
+    let min = match limits & GDTR_MIN == GDTR_MIN { true => Some(from_systemtime(&tr[0])), false => None };
+    let max = match limits & GDTR_MAX == GDTR_MAX { true => Some(from_systemtime(&tr[1])), false => None };
+
+    (min, max)
+}
@@ -0,0 +1,345 @@
+/*!
+    The value type of the `DatePicker` and `MonthCalendar` controls and its conversions.
+*/
+use winapi::um::minwinbase::SYSTEMTIME;
+use std::time::{SystemTime, Duration, UNIX_EPOCH};
+use std::{mem, ptr};
+use crate::NwgError;
+
+/// Smallest year that can be represented by a winapi SYSTEMTIME
+const MIN_YEAR: u16 = 1601;
+
+/// Largest year that can be represented by a winapi SYSTEMTIME
+const MAX_YEAR: u16 = 30827;
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+
+/**
+    A date and time struct that can be passed to a date time picker or a month calendar control.
+    Fields are self explanatory. `month` and `day` start at 1, the time fields start at 0.
+
+    The controls display the value as is, so it should be in the local time of the user.
+    Use `from_system_time_local` and `to_system_time_local` to convert from and to a `SystemTime`.
+
+    The fields are ordered from the year to the second, so the derived comparisons are chronological.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let date = nwg::DatePickerValue::new(2020, 2, 29).with_time(13, 37, 0);
+    assert!(date.validate().is_ok());
+    assert_eq!(date.to_iso8601(), "2020-02-29T13:37:00");
+    assert_eq!(nwg::DatePickerValue::from_iso8601("2020-02-29T13:37:00").unwrap(), date);
+    ```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct DatePickerValue {
+    pub year: u16,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+}
+
+impl DatePickerValue {
+
+    /// Creates a value at midnight of the selected day. The value is not validated.
+    pub fn new(year: u16, month: u16, day: u16) -> DatePickerValue {
+        DatePickerValue { year, month, day, hour: 0, minute: 0, second: 0 }
+    }
+
+    /// Returns a copy of the value with the time of the day replaced. The value is not validated.
+    pub fn with_time(self, hour: u16, minute: u16, second: u16) -> DatePickerValue {
+        DatePickerValue { hour, minute, second, ..self }
+    }
+
+    /**
+        Checks that the value is an existing date and time.
+        Years are accepted from 1601 to 30827, the range of the winapi SYSTEMTIME.
+    */
+    pub fn validate(&self) -> Result<(), NwgError> {
+        if self.year < MIN_YEAR || self.year > MAX_YEAR {
+            return Err(NwgError::date_time(format!("Year {} is outside of the supported range ({}-{})", self.year, MIN_YEAR, MAX_YEAR)));
+        }
+
+        if self.month < 1 || self.month > 12 {
+            return Err(NwgError::date_time(format!("Month {} does not exist", self.month)));
+        }
+
+        if self.day < 1 || self.day > days_in_month(self.year, self.month) {
+            return Err(NwgError::date_time(format!("Day {} does not exist in {:04}-{:02}", self.day, self.year, self.month)));
+        }
+
+        if self.hour > 23 || self.minute > 59 || self.second > 59 {
+            return Err(NwgError::date_time(format!("{:02}:{:02}:{:02} is not a valid time", self.hour, self.minute, self.second)));
+        }
+
+        Ok(())
+    }
+
+    /**
+        Converts a system time to a value in UTC. Subseconds are truncated.
+        Returns an error if the time is outside of the years supported by `validate`.
+
+        The value is not in the local time of the user. To display a system time in a control, use `from_system_time_local`.
+    */
+    pub fn from_system_time(time: SystemTime) -> Result<DatePickerValue, NwgError> {
+        let too_far = || NwgError::date_time("System time is outside of the supported range");
+
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i128,
+            Err(e) => {
+                // Round toward the past so that the time of the day stays positive
+                let d = e.duration();
+                -(d.as_secs() as i128) - if d.subsec_nanos() > 0 { 1 } else { 0 }
+            }
+        };
+
+        let days = seconds.div_euclid(SECONDS_PER_DAY as i128);
+        let time = seconds.rem_euclid(SECONDS_PER_DAY as i128) as u32;
+        if days.abs() > (i64::MAX / SECONDS_PER_DAY) as i128 {
+            return Err(too_far());
+        }
+
+        let (year, month, day) = civil_from_days(days as i64);
+        if year < MIN_YEAR as i64 || year > MAX_YEAR as i64 {
+            return Err(too_far());
+        }
+
+        Ok(DatePickerValue {
+            year: year as u16,
+            month,
+            day,
+            hour: (time / 3600) as u16,
+            minute: (time % 3600 / 60) as u16,
+            second: (time % 60) as u16,
+        })
+    }
+
+    /// Converts the value, read as UTC, to a system time. Returns an error if the value is not valid.
+    /// To convert a value selected in a control, use `to_system_time_local`.
+    pub fn to_system_time(&self) -> Result<SystemTime, NwgError> {
+        self.validate()?;
+
+        let days = days_from_civil(self.year as i64, self.month, self.day);
+        let seconds = days * SECONDS_PER_DAY + (self.hour as i64) * 3600 + (self.minute as i64) * 60 + (self.second as i64);
+
+        let time = if seconds >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(seconds as u64))
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs((-seconds) as u64))
+        };
+
+        time.ok_or_else(|| NwgError::date_time(format!("{} cannot be represented by the system time", self.to_iso8601())))
+    }
+
+    /**
+        Converts a system time to a value in the current time zone of the user, including daylight saving time.
+        Subseconds are truncated. Returns an error if the time is outside of the years supported by `validate`.
+    */
+    pub fn from_system_time_local(time: SystemTime) -> Result<DatePickerValue, NwgError> {
+        use winapi::um::timezoneapi::SystemTimeToTzSpecificLocalTime;
+
+        let utc = to_systemtime(&DatePickerValue::from_system_time(time)?);
+        let mut local: SYSTEMTIME = unsafe { mem::zeroed() };
+        if unsafe { SystemTimeToTzSpecificLocalTime(ptr::null(), &utc, &mut local) } == 0 {
+            return Err(NwgError::date_time("Failed to convert the system time to the local time"));
+        }
+
+        let value = from_systemtime(&local);
+        value.validate()?;
+        Ok(value)
+    }
+
+    /// Converts the value, read in the current time zone of the user, to a system time. Returns an error if the value is not valid.
+    pub fn to_system_time_local(&self) -> Result<SystemTime, NwgError> {
+        use winapi::um::timezoneapi::TzSpecificLocalTimeToSystemTime;
+
+        self.validate()?;
+
+        let local = to_systemtime(self);
+        let mut utc: SYSTEMTIME = unsafe { mem::zeroed() };
+        if unsafe { TzSpecificLocalTimeToSystemTime(ptr::null(), &local, &mut utc) } == 0 {
+            return Err(NwgError::date_time(format!("Failed to convert {} from the local time", self.to_iso8601())));
+        }
+
+        from_systemtime(&utc).to_system_time()
+    }
+
+    /**
+        Reads an ISO-8601 date (`2020-02-29`) or date and time (`2020-02-29T13:37`, `2020-02-29T13:37:00`) in the extended format.
+        A space can be used instead of the `T`. Fractions of seconds are truncated.
+
+        A time ending with `Z` is in UTC. A time ending with an offset (ex: `+02:00`) is converted to UTC.
+        A time without a zone is returned as is.
+    */
+    pub fn from_iso8601(text: &str) -> Result<DatePickerValue, NwgError> {
+        let error = || NwgError::date_time(format!("{:?} is not a ISO-8601 date", text));
+
+        let trimmed = text.trim();
+        let (date, time) = match trimmed.find(&['T', 't', ' '][..]) {
+            Some(i) => (&trimmed[..i], Some(&trimmed[(i+1)..])),
+            None => (trimmed, None)
+        };
+
+        let mut parts = date.split('-');
+        let (year, month, day) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(y), Some(m), Some(d), None) => (digits(y, 4), digits(m, 2), digits(d, 2)),
+            _ => { return Err(error()); }
+        };
+
+        let mut value = match (year, month, day) {
+            (Some(y), Some(m), Some(d)) => DatePickerValue::new(y, m, d),
+            _ => { return Err(error()); }
+        };
+
+        let mut offset = 0i64;
+        if let Some(time) = time {
+            let (time, zone) = match time.find(&['Z', 'z', '+', '-'][..]) {
+                Some(i) => (&time[..i], Some(&time[i..])),
+                None => (time, None)
+            };
+
+            offset = match zone {
+                None | Some("Z") | Some("z") => 0,
+                Some(zone) => parse_offset(zone).ok_or_else(error)?,
+            };
+
+            // Fractions of seconds
+            let time = match time.find(&['.', ','][..]) {
+                Some(i) if time[(i+1)..].bytes().all(|b| b.is_ascii_digit()) => &time[..i],
+                Some(_) => { return Err(error()); },
+                None => time
+            };
+
+            let mut parts = time.split(':');
+            let (hour, minute, second) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(h), Some(m), None, None) => (digits(h, 2), digits(m, 2), Some(0)),
+                (Some(h), Some(m), Some(s), None) => (digits(h, 2), digits(m, 2), digits(s, 2)),
+                _ => { return Err(error()); }
+            };
+
+            value = match (hour, minute, second) {
+                (Some(h), Some(m), Some(s)) => value.with_time(h, m, s),
+                _ => { return Err(error()); }
+            };
+        }
+
+        value.validate()?;
+
+        if offset == 0 {
+            return Ok(value);
+        }
+
+        let local = value.to_system_time()?;
+        let utc = if offset > 0 {
+            local.checked_sub(Duration::from_secs(offset as u64))
+        } else {
+            local.checked_add(Duration::from_secs((-offset) as u64))
+        };
+
+        utc.ok_or_else(error).and_then(DatePickerValue::from_system_time)
+    }
+
+    /// Writes the value in the ISO-8601 extended format, without zone (ex: `2020-02-29T13:37:00`)
+    pub fn to_iso8601(&self) -> String {
+        format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+
+}
+
+pub(crate) fn to_systemtime(value: &DatePickerValue) -> SYSTEMTIME {
+    SYSTEMTIME {
+        wYear: value.year,
+        wMonth: value.month,
+        wDayOfWeek: 0,
+        wDay: value.day,
+        wHour: value.hour,
+        wMinute: value.minute,
+        wSecond: value.second,
+        wMilliseconds: 0
+    }
+}
+
+pub(crate) fn from_systemtime(value: &SYSTEMTIME) -> DatePickerValue {
+    DatePickerValue {
+        year: value.wYear,
+        month: value.wMonth,
+        day: value.wDay,
+        hour: value.wHour,
+        minute: value.wMinute,
+        second: value.wSecond,
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) fn days_in_month(year: u16, month: u16) -> u16 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+/// Number of days since 1970-01-01 in the proleptic gregorian calendar
+fn days_from_civil(year: i64, month: u16, day: u16) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + (day as i64) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u16, u16) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month as u16, day as u16)
+}
+
+/// Parses a number of exactly `len` ascii digits
+fn digits(text: &str, len: usize) -> Option<u16> {
+    if text.len() == len && text.bytes().all(|b| b.is_ascii_digit()) {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Parses a zone offset (`+hh:mm`, `+hhmm` or `+hh`) in seconds
+fn parse_offset(zone: &str) -> Option<i64> {
+    let sign = match zone.as_bytes().first() {
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        _ => { return None; }
+    };
+
+    let zone = zone[1..].replace(':', "");
+    let (hours, minutes) = match zone.len() {
+        2 => (digits(&zone, 2)?, 0),
+        4 => (digits(&zone[..2], 2)?, digits(&zone[2..], 2)?),
+        _ => { return None; }
+    };
+
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    Some(sign * ((hours as i64) * 3600 + (minutes as i64) * 60))
+}
@@ -166,6 +166,12 @@ use super::{DatePicker};
 #[cfg(feature = "datetime-picker")]
 handles!(DatePicker);
 
+#[cfg(feature = "month-calendar")]
+use super::MonthCalendar;
+
+#[cfg(feature = "month-calendar")]
+handles!(MonthCalendar);
+
 #[cfg(feature = "progress-bar")]
 use super::ProgressBar;
 
@@ -0,0 +1,421 @@
+/*!
+    Syntax highlighting of a `RichTextBox`. See `RichTextBox::set_highlighter`.
+
+    The text is highlighted line by line. `HighlightCache` remembers the style of each line, so that after an edit
+    only the edited lines are highlighted again, and only the characters whose style changed are formatted by the control.
+*/
+use super::index_to_utf16_offset;
+use std::ops::Range;
+use std::mem;
+
+
+/// The style of a span of highlighted text. The `None` colors are the default colors of the control.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HighlightStyle {
+    pub text_color: Option<[u8; 3]>,
+    pub background_color: Option<[u8; 3]>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl HighlightStyle {
+
+    /// A style that only changes the color of the text
+    pub fn color(text_color: [u8; 3]) -> HighlightStyle {
+        HighlightStyle { text_color: Some(text_color), ..Default::default() }
+    }
+
+}
+
+/// A span of text highlighted by a `Highlighter`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HighlightSpan {
+    /// The index of the line of the span
+    pub line: usize,
+
+    /// The byte range of the span in the line
+    pub range: Range<usize>,
+
+    pub style: HighlightStyle,
+}
+
+
+/**
+    Computes the highlighted spans of the text of a `RichTextBox`. NWG implements `KeywordHighlighter`.
+
+    ```rust
+    use native_windows_gui as nwg;
+    use std::ops::Range;
+
+    /// Highlights the lines starting with "#"
+    struct Headers;
+
+    impl nwg::Highlighter for Headers {
+        fn highlight(&self, lines: &[&str], range: Range<usize>) -> Vec<nwg::HighlightSpan> {
+            range.filter(|&i| lines[i].starts_with('#'))
+                .map(|i| nwg::HighlightSpan { line: i, range: 0..lines[i].len(), style: nwg::HighlightStyle::color([0, 0, 200]) })
+                .collect()
+        }
+    }
+    ```
+*/
+pub trait Highlighter {
+    /**
+        Returns the spans of the lines in `range`. `lines` contains all the lines of the text, without the line breaks.
+        The spans outside of `range` and the spans that do not start and end on a char boundary are ignored.
+        If spans overlap, the last one is displayed.
+    */
+    fn highlight(&self, lines: &[&str], range: Range<usize>) -> Vec<HighlightSpan>;
+
+    /**
+        Returns the lines to highlight again after the lines in `changed` were edited. `changed` is empty if lines were only removed.
+        By default, only the edited lines are highlighted again. Highlighters with constructs over multiple lines (ex: block comments)
+        can extend the range to the lines that the edit affects.
+    */
+    fn invalidated(&self, lines: &[&str], changed: Range<usize>) -> Range<usize> {
+        let _ = lines;
+        changed
+    }
+}
+
+
+/// A range of text to format, in UTF-16 code units. `style` is `None` for the text that is not highlighted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleRun {
+    pub range: Range<u32>,
+    pub style: Option<HighlightStyle>,
+}
+
+/**
+    The highlighted lines of a text. `update` compares a new text with the previous one and returns the ranges to format.
+
+    This is what `RichTextBox` uses internally. It does not depend on the control, so it can be used to highlight other controls.
+*/
+#[derive(Clone, Debug, Default)]
+pub struct HighlightCache {
+    lines: Vec<String>,
+
+    /// The style of each UTF-16 code unit of each line
+    styles: Vec<Vec<Option<HighlightStyle>>>,
+}
+
+impl HighlightCache {
+
+    pub fn new() -> HighlightCache {
+        Default::default()
+    }
+
+    /// Forgets the highlighted text. The next update formats all the text.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.styles.clear();
+    }
+
+    /**
+        Highlights the lines of `text` that changed since the last update and returns the ranges that must be formatted.
+
+        The edited lines are entirely formatted, because the control may have given any format to the new characters.
+        The other lines highlighted again (see `Highlighter::invalidated`) are only formatted where their style changed.
+        `\r\n`, `\r` and `\n` are line breaks.
+    */
+    pub fn update(&mut self, text: &str, highlighter: &dyn Highlighter) -> Vec<StyleRun> {
+        let (lines, starts) = split_lines(text);
+        let old_count = self.lines.len();
+
+        let prefix = self.lines.iter().zip(lines.iter())
+            .take_while(|(old, new)| old == *new)
+            .count();
+
+        let suffix = self.lines[prefix..].iter().rev().zip(lines[prefix..].iter().rev())
+            .take_while(|(old, new)| old == *new)
+            .count();
+
+        let changed = prefix..(lines.len() - suffix);
+        if changed.is_empty() && old_count - suffix == prefix {
+            return Vec::new();
+        }
+
+        // The styles of the unchanged lines are kept. The styles of the edited lines are replaced below.
+        let mut styles = mem::take(&mut self.styles);
+        let suffix_styles = styles.split_off(old_count - suffix);
+        styles.truncate(prefix);
+        styles.extend(changed.clone().map(|_| Vec::new()));
+        styles.extend(suffix_styles);
+
+        let invalidated = highlighter.invalidated(&lines, changed.clone());
+        let range = invalidated.start.min(changed.start)..invalidated.end.max(changed.end).min(lines.len());
+
+        let mut painted: Vec<Vec<Option<HighlightStyle>>> = lines[range.clone()].iter()
+            .map(|line| vec![None; index_to_utf16_offset(line, line.len()) as usize])
+            .collect();
+
+        for span in highlighter.highlight(&lines, range.clone()) {
+            if !range.contains(&span.line) {
+                continue;
+            }
+
+            let line = lines[span.line];
+            let r = span.range;
+            if r.start >= r.end || r.end > line.len() || !line.is_char_boundary(r.start) || !line.is_char_boundary(r.end) {
+                continue;
+            }
+
+            let start = index_to_utf16_offset(line, r.start) as usize;
+            let end = index_to_utf16_offset(line, r.end) as usize;
+            for style in painted[span.line - range.start][start..end].iter_mut() {
+                *style = Some(span.style);
+            }
+        }
+
+        let mut runs = Vec::new();
+        for (index, new_styles) in range.clone().zip(painted) {
+            let old_styles = match changed.contains(&index) {
+                true => None,
+                false => Some(&styles[index][..])
+            };
+
+            diff_styles(old_styles, &new_styles, starts[index], &mut runs);
+            styles[index] = new_styles;
+        }
+
+        self.lines = lines.iter().map(|line| line.to_string()).collect();
+        self.styles = styles;
+
+        runs
+    }
+
+}
+
+/// Splits `text` in lines. Returns the lines and the UTF-16 offset of their first character.
+fn split_lines(text: &str) -> (Vec<&str>, Vec<u32>) {
+    let mut lines = Vec::new();
+    let mut starts = Vec::new();
+    let mut line_start = (0, 0);
+    let mut offset = 0;
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        offset += c.len_utf16() as u32;
+        if c != '\r' && c != '\n' {
+            continue;
+        }
+
+        let mut end = index + 1;
+        if c == '\r' {
+            if let Some(&(_, '\n')) = chars.peek() {
+                chars.next();
+                end += 1;
+                offset += 1;
+            }
+        }
+
+        lines.push(&text[line_start.0..index]);
+        starts.push(line_start.1);
+        line_start = (end, offset);
+    }
+
+    lines.push(&text[line_start.0..]);
+    starts.push(line_start.1);
+
+    (lines, starts)
+}
+
+/**
+    Appends to `runs` the ranges where `new` is different from `old`, starting at the offset `start`.
+    If `old` is `None`, the whole line is different.
+*/
+fn diff_styles(old: Option<&[Option<HighlightStyle>]>, new: &[Option<HighlightStyle>], start: u32, runs: &mut Vec<StyleRun>) {
+    let mut run: Option<StyleRun> = None;
+
+    for (index, style) in new.iter().enumerate() {
+        let changed = match old {
+            Some(old) => old.get(index) != Some(style),
+            None => true
+        };
+
+        if !changed {
+            continue;
+        }
+
+        let offset = start + index as u32;
+        match run.as_mut() {
+            Some(r) if r.range.end == offset && r.style == *style => { r.range.end += 1; },
+            _ => {
+                let next = StyleRun { range: offset..(offset + 1), style: *style };
+                if let Some(r) = run.replace(next) {
+                    runs.push(r);
+                }
+            }
+        }
+    }
+
+    if let Some(r) = run {
+        runs.push(r);
+    }
+}
+
+
+/**
+    A simple highlighter for programming languages: keywords, strings, line comments and, with the `regex-highlighter` feature, regular expressions.
+
+    Strings and comments are found first. The keywords and the regular expressions are only searched in the rest of the line,
+    and the regular expressions are displayed over the keywords.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let highlighter = nwg::KeywordHighlighter::new()
+        .keywords(&["fn", "let", "if", "else", "return"], nwg::HighlightStyle { bold: true, ..nwg::HighlightStyle::color([0, 0, 255]) })
+        .strings(nwg::HighlightStyle::color([163, 21, 21]))
+        .line_comment("//", nwg::HighlightStyle { italic: true, ..nwg::HighlightStyle::color([0, 128, 0]) });
+    ```
+*/
+#[derive(Clone, Debug, Default)]
+pub struct KeywordHighlighter {
+    keywords: Vec<(String, HighlightStyle)>,
+    strings: Option<HighlightStyle>,
+    line_comment: Option<(String, HighlightStyle)>,
+
+    #[cfg(feature = "regex-highlighter")]
+    patterns: Vec<(regex::Regex, HighlightStyle)>,
+}
+
+impl KeywordHighlighter {
+
+    pub fn new() -> KeywordHighlighter {
+        Default::default()
+    }
+
+    /// Highlights the words in `keywords` with `style`. The keywords are case sensitive and only match whole words.
+    pub fn keywords(mut self, keywords: &[&str], style: HighlightStyle) -> KeywordHighlighter {
+        self.keywords.extend(keywords.iter().map(|k| (k.to_string(), style)));
+        self
+    }
+
+    /// Highlights the text between double quotes with `style`. The quotes escaped with `\` do not end the string.
+    pub fn strings(mut self, style: HighlightStyle) -> KeywordHighlighter {
+        self.strings = Some(style);
+        self
+    }
+
+    /// Highlights the text from `prefix` (ex: "//") to the end of the line with `style`
+    pub fn line_comment(mut self, prefix: &str, style: HighlightStyle) -> KeywordHighlighter {
+        self.line_comment = match prefix.is_empty() {
+            true => None,
+            false => Some((prefix.to_string(), style))
+        };
+
+        self
+    }
+
+    /// Highlights the text matching `pattern` with `style`. Requires the `regex-highlighter` feature.
+    #[cfg(feature = "regex-highlighter")]
+    pub fn pattern(mut self, pattern: &str, style: HighlightStyle) -> Result<KeywordHighlighter, regex::Error> {
+        self.patterns.push((regex::Regex::new(pattern)?, style));
+        Ok(self)
+    }
+
+    fn highlight_line(&self, index: usize, line: &str, spans: &mut Vec<HighlightSpan>) {
+        let span = |range: Range<usize>, style: HighlightStyle| HighlightSpan { line: index, range, style };
+
+        // The code between the strings and the comments
+        let mut code = Vec::new();
+        let mut code_start = 0;
+        let mut position = 0;
+
+        while position < line.len() {
+            let rest = &line[position..];
+            if let Some((prefix, style)) = self.line_comment.as_ref() {
+                if rest.starts_with(prefix.as_str()) {
+                    code.push(code_start..position);
+                    spans.push(span(position..line.len(), *style));
+                    code_start = line.len();
+                    break;
+                }
+            }
+
+            if let (Some(style), true) = (self.strings, rest.starts_with('"')) {
+                let end = string_end(rest).map(|end| position + end).unwrap_or(line.len());
+                code.push(code_start..position);
+                spans.push(span(position..end, style));
+                code_start = end;
+                position = end;
+                continue;
+            }
+
+            position += rest.chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+        }
+
+        code.push(code_start..line.len());
+
+        for range in code.iter().filter(|r| !r.is_empty()) {
+            for word in words(line, range.clone()) {
+                if let Some((_, style)) = self.keywords.iter().find(|(k, _)| *k == line[word.clone()]) {
+                    spans.push(span(word, *style));
+                }
+            }
+        }
+
+        #[cfg(feature = "regex-highlighter")]
+        for (regex, style) in self.patterns.iter() {
+            for range in code.iter().filter(|r| !r.is_empty()) {
+                for m in regex.find_iter(&line[range.clone()]) {
+                    spans.push(span((range.start + m.start())..(range.start + m.end()), *style));
+                }
+            }
+        }
+    }
+
+}
+
+impl Highlighter for KeywordHighlighter {
+
+    fn highlight(&self, lines: &[&str], range: Range<usize>) -> Vec<HighlightSpan> {
+        let mut spans = Vec::new();
+        for index in range {
+            self.highlight_line(index, lines[index], &mut spans);
+        }
+
+        spans
+    }
+
+}
+
+/// Returns the byte index after the closing quote of the string at the start of `text`
+fn string_end(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => { escaped = true; },
+            '"' if !escaped => { return Some(index + 1); },
+            _ => { escaped = false; }
+        }
+    }
+
+    None
+}
+
+/// Returns the byte ranges of the words (letters, digits and `_`) of `line` in `range`
+fn words(line: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (index, c) in line[range.clone()].char_indices() {
+        let index = range.start + index;
+        match (c.is_alphanumeric() || c == '_', start) {
+            (true, None) => { start = Some(index); },
+            (false, Some(s)) => {
+                words.push(s..index);
+                start = None;
+            },
+            _ => {}
+        }
+    }
+
+    if let Some(s) = start {
+        words.push(s..range.end);
+    }
+
+    words
+}
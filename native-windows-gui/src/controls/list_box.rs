@@ -3,13 +3,18 @@ use winapi::shared::minwindef::{WPARAM, LPARAM};
 use winapi::um::winuser::{LBS_MULTIPLESEL, LBS_NOSEL, WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::{to_utf16, from_utf16, check_hwnd};
-use crate::{Font, NwgError};
+use crate::win32::owner_draw::{OwnerDraw, bind_owner_draw, item_height};
+use crate::{Font, NwgError, ItemRenderer, RawEventHandler, unbind_raw_event_handler};
 use super::{ControlBase, ControlHandle};
 use std::cell::{Ref, RefMut, RefCell};
 use std::fmt::Display;
 use std::ops::Range;
+use std::rc::Rc;
 use std::mem;
 
+#[cfg(feature="image-list")]
+use crate::ImageList;
+
 const NOT_BOUND: &'static str = "ListBox is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: ListBox handle is not HWND!";
 
@@ -51,6 +56,7 @@ Requires the `list-box` feature.
   * `collection`:      The default collections of the listbox
   * `selected_index`:  The default selected index in the listbox collection
   * `multi_selection`: The collections of indices to set as selected in a multi selection listbox 
+  * `renderer`:        Draws the items with an `ItemRenderer` instead of their display value. See `OwnerDrawItem`
 
 **Control events:**
   * `OnListBoxSelect`: When the current listbox selection is changed
@@ -76,7 +82,10 @@ fn build_listbox(listb: &mut nwg::ListBox<&'static str>, window: &nwg::Window, f
 #[derive(Default)]
 pub struct ListBox<D: Display+Default> {
     pub handle: ControlHandle,
-    collection: RefCell<Vec<D>>
+    collection: RefCell<Vec<D>>,
+    renderer: RefCell<Option<Box<dyn ItemRenderer<D>>>>,
+    owner_draw: Rc<OwnerDraw>,
+    handler0: RefCell<Option<RawEventHandler>>,
 }
 
 impl<D: Display+Default> ListBox<D> {
@@ -92,7 +101,36 @@ impl<D: Display+Default> ListBox<D> {
             collection: None,
             selected_index: None,
             multi_selection: Vec::new(),
-            parent: None
+            parent: None,
+            renderer: None,
+        }
+    }
+
+    /// Replaces the renderer of the items and draws the items again.
+    /// Returns an error if the list box was not created with the `renderer` builder parameter, as the owner draw style cannot be added afterward.
+    pub fn set_renderer<R: ItemRenderer<D> + 'static>(&self, renderer: R) -> Result<(), NwgError> {
+        if self.renderer.borrow().is_none() {
+            return Err(NwgError::control_create("The renderer of a ListBox can only be replaced if it was created with the `renderer` builder parameter"));
+        }
+
+        *self.renderer.borrow_mut() = Some(Box::new(renderer));
+        self.update_owner_draw();
+        Ok(())
+    }
+
+    /// Sets the image list used by the `image` of the items of an owner drawn list box. See `OwnerDrawItem`
+    #[cfg(feature="image-list")]
+    pub fn set_image_list(&self, list: Option<&ImageList>) {
+        self.owner_draw.image_list.set(list.map(|l| l.handle).unwrap_or(std::ptr::null_mut()));
+        self.update_owner_draw();
+    }
+
+    /// Returns the image list of an owner drawn list box. The image list is not owned by the returned value.
+    #[cfg(feature="image-list")]
+    pub fn image_list(&self) -> Option<ImageList> {
+        match self.owner_draw.image_list.get() {
+            h if h.is_null() => None,
+            handle => Some(ImageList { handle, owned: false })
         }
     }
 
@@ -109,6 +147,7 @@ impl<D: Display+Default> ListBox<D> {
         }
 
         self.collection.borrow_mut().push(item);
+        self.update_owner_draw();
     }
 
     /// Insert an item in the collection and the control. 
@@ -132,6 +171,9 @@ impl<D: Display+Default> ListBox<D> {
         unsafe {
             wh::send_message(handle, LB_INSERTSTRING, index, mem::transmute(display_os.as_ptr()));
         }
+
+        drop(col);
+        self.update_owner_draw();
     }
 
 
@@ -143,8 +185,10 @@ impl<D: Display+Default> ListBox<D> {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_DELETESTRING, index as WPARAM, 0);
 
-        let mut col_ref = self.collection.borrow_mut();
-        col_ref.remove(index)
+        let item = self.collection.borrow_mut().remove(index);
+        self.update_owner_draw();
+
+        item
     }
 
     /// Return the index of the currencty selected item for single value list box.
@@ -329,6 +373,8 @@ impl<D: Display+Default> ListBox<D> {
                 wh::send_message(handle, LB_ADDSTRING, 0, mem::transmute(display_os.as_ptr()));
             }
         }
+
+        self.update_owner_draw();
     }
 
     /// Set the item collection of the list box. Return the old collection
@@ -350,6 +396,9 @@ impl<D: Display+Default> ListBox<D> {
 
         let mut col_ref = self.collection.borrow_mut();
         mem::swap::<Vec<D>>(&mut col_ref, &mut col);
+        drop(col_ref);
+
+        self.update_owner_draw();
 
         col
     }
@@ -387,6 +436,7 @@ impl<D: Display+Default> ListBox<D> {
     pub fn set_font(&self, font: Option<&Font>) {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         unsafe { wh::set_window_font(handle, font.map(|f| f.handle), true); }
+        self.update_owner_draw();
     }
 
     /// Return true if the control currently has the keyboard focus
@@ -487,10 +537,34 @@ impl<D: Display+Default> ListBox<D> {
         wh::send_message(handle, LB_RESETCONTENT, 0, 0);
     }
 
+    /// Renders the items of the collection again and updates the height of the items. Does nothing if the list box is not owner drawn.
+    fn update_owner_draw(&self) {
+        use winapi::um::winuser::{LB_SETITEMHEIGHT, InvalidateRect};
+
+        let renderer = self.renderer.borrow();
+        let renderer = match renderer.as_ref() {
+            Some(r) => r,
+            None => { return; }
+        };
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let items = self.collection.borrow().iter().map(|i| renderer.render(i)).collect();
+        *self.owner_draw.items.borrow_mut() = items;
+
+        let height = item_height(handle, &self.owner_draw);
+        wh::send_message(handle, LB_SETITEMHEIGHT, 0, height as LPARAM);
+        unsafe { InvalidateRect(handle, std::ptr::null(), 1); }
+    }
+
 }
 
 impl<D: Display+Default> Drop for ListBox<D> {
     fn drop(&mut self) {
+        let handler = self.handler0.borrow_mut().take();
+        if let Some(h) = handler {
+            drop(unbind_raw_event_handler(&h));
+        }
+
         self.handle.destroy();
     }
 }
@@ -505,7 +579,8 @@ pub struct ListBoxBuilder<'a, D: Display+Default> {
     collection: Option<Vec<D>>,
     selected_index: Option<usize>,
     multi_selection: Vec<usize>,
-    parent: Option<ControlHandle>
+    parent: Option<ControlHandle>,
+    renderer: Option<Box<dyn ItemRenderer<D>>>,
 }
 
 impl<'a, D: Display+Default> ListBoxBuilder<'a, D> {
@@ -560,7 +635,14 @@ impl<'a, D: Display+Default> ListBoxBuilder<'a, D> {
         self
     }
 
+    pub fn renderer<R: ItemRenderer<D> + 'static>(mut self, renderer: R) -> ListBoxBuilder<'a, D> {
+        self.renderer = Some(Box::new(renderer));
+        self
+    }
+
     pub fn build(self, out: &mut ListBox<D>) -> Result<(), NwgError> {
+        use winapi::um::winuser::LBS_OWNERDRAWFIXED;
+
         let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
 
         let parent = match self.parent {
@@ -568,15 +650,27 @@ impl<'a, D: Display+Default> ListBoxBuilder<'a, D> {
             None => Err(NwgError::no_parent("ListBox"))
         }?;
 
+        // The items of an owner drawn list box are painted by NWG using the renderer
+        let forced_flags = match self.renderer.is_some() {
+            true => out.forced_flags() | LBS_OWNERDRAWFIXED,
+            false => out.forced_flags()
+        };
+
         out.handle = ControlBase::build_hwnd()
             .class_name(out.class_name())
-            .forced_flags(out.forced_flags())
+            .forced_flags(forced_flags)
             .flags(flags)
             .size(self.size)
             .position(self.position)
             .parent(Some(parent))
             .build()?;
 
+        if let Some(renderer) = self.renderer {
+            let handle = out.handle.hwnd().unwrap();
+            *out.renderer.borrow_mut() = Some(renderer);
+            *out.handler0.borrow_mut() = Some(bind_owner_draw(handle, out.owner_draw.clone(), Some));
+        }
+
         if self.font.is_some() {
             out.set_font(self.font);
         } else {
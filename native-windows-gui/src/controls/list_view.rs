@@ -2,13 +2,16 @@ use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
 use winapi::um::commctrl::{
     LVS_ICON, LVS_SMALLICON, LVS_LIST, LVS_REPORT, LVS_NOCOLUMNHEADER, LVCOLUMNW, LVCFMT_LEFT, LVCFMT_RIGHT, LVCFMT_CENTER, LVCFMT_JUSTIFYMASK,
     LVCFMT_IMAGE, LVCFMT_BITMAP_ON_RIGHT, LVCFMT_COL_HAS_IMAGES, LVITEMW, LVIF_TEXT, LVCF_WIDTH, LVCF_TEXT, LVS_EX_GRIDLINES, LVS_EX_BORDERSELECT,
-    LVS_EX_AUTOSIZECOLUMNS, LVM_SETEXTENDEDLISTVIEWSTYLE, LVS_EX_FULLROWSELECT, LVS_SINGLESEL, LVCF_FMT, LVIF_IMAGE
+    LVS_EX_AUTOSIZECOLUMNS, LVM_SETEXTENDEDLISTVIEWSTYLE, LVS_EX_FULLROWSELECT, LVS_EX_CHECKBOXES, LVS_SINGLESEL, LVCF_FMT, LVIF_IMAGE, LVS_OWNERDATA, LVS_EDITLABELS
 };
 use super::{ControlBase, ControlHandle};
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::{to_utf16, from_utf16, check_hwnd};
-use crate::{NwgError, RawEventHandler, unbind_raw_event_handler};
-use std::{mem, cell::RefCell};
+use crate::{NwgError, RawEventHandler, ItemDrag, unbind_raw_event_handler};
+use crate::win32::item_drag::{ItemDragHandler, bind_item_drag};
+use winapi::shared::{windef::HWND, minwindef::LPARAM, basetsd::UINT_PTR};
+use winapi::ctypes::c_int;
+use std::{mem, cell::RefCell, rc::Rc, cmp::Ordering};
 
 #[cfg(feature="image-list")]
 use crate::ImageList;
@@ -25,8 +28,10 @@ bitflags! {
         * VISIBLE:  The list view is immediatly visible after creation
         * DISABLED: The list view cannot be interacted with by the user. It also has a grayed out look. The user can drag the items to any location in the list-view window.
         * TAB_STOP: The control can be selected using tab navigation
-        * NO_HEADER: Remove the headers in Detailed view (always ON, see "Windows is Shit" section in ListView docs as of why)
+        * NO_HEADER: Remove the headers in Detailed view (ON by default, see "Windows is Shit" section in ListView docs as of why)
         * SINGLE_SELECTION: Only one item can be selected
+        * VIRTUAL: The list view does not store its items. The items are fetched from a `ListViewDataSource`. See `ListView::set_data_source`
+        * EDIT_LABELS: The user can edit the items of the first column by clicking on a selected item. See `ListView::edit_item`
     */
     pub struct ListViewFlags: u32 {
         const VISIBLE = WS_VISIBLE;
@@ -34,8 +39,10 @@ bitflags! {
         const TAB_STOP = WS_TABSTOP;
 
         const SINGLE_SELECTION = LVS_SINGLESEL;
+        const VIRTUAL = LVS_OWNERDATA;
+        const EDIT_LABELS = LVS_EDITLABELS;
 
-        // Remove the headers in Detailed view (always ON, see "Windows is Shit" section in ListView docs as of why)
+        // Remove the headers in Detailed view (ON by default, see "Windows is Shit" section in ListView docs as of why)
         const NO_HEADER = LVS_NOCOLUMNHEADER;
     }
 }
@@ -49,6 +56,7 @@ bitflags! {
         * BORDER_SELECT: Only highlight the border instead of the full item. COMMCTRL version 4.71 or later
         * AUTO_COLUMN_SIZE: Automatically resize to column
         * FULL_ROW_SELECT: When an item is selected, the item and all its subitems are highlighted. Only in detailed view 
+        * CHECKBOXES: Display a checkbox next to the items. See `ListView::set_item_checked`
     */
     pub struct ListViewExFlags: u32 {
         const NONE = 0;
@@ -56,6 +64,7 @@ bitflags! {
         const BORDER_SELECT = LVS_EX_BORDERSELECT;
         const AUTO_COLUMN_SIZE = LVS_EX_AUTOSIZECOLUMNS;
         const FULL_ROW_SELECT = LVS_EX_FULLROWSELECT;
+        const CHECKBOXES = LVS_EX_CHECKBOXES;
     }
 }
 
@@ -116,6 +125,34 @@ impl ListViewStyle {
 }
 
 
+/// The colors of a list view cell returned by the callback of `ListView::set_item_colors`.
+/// `None` keeps the default color of the list view.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListViewCellColors {
+    pub text: Option<[u8; 3]>,
+    pub background: Option<[u8; 3]>,
+}
+
+
+/// The control used by `ListView::edit_item` to edit an item
+#[derive(Clone, Debug)]
+pub enum ListViewCellEditor {
+    /// A text input
+    Text,
+
+    /// A dropdown list with the choices
+    Choices(Vec<String>),
+}
+
+
+/// The sort indicator displayed in the header of a list view column
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ListViewSort {
+    Ascending,
+    Descending,
+}
+
+
 #[cfg(feature="image-list")]
 #[derive(Copy, Clone, Debug)]
 pub enum ListViewImageListType {
@@ -172,6 +209,22 @@ pub struct ListViewColumn {
     pub text: String,
 }
 
+/// A group of items to insert in a list view. See `ListView::insert_group`
+#[derive(Default, Clone, Debug)]
+pub struct InsertListViewGroup {
+    /// An application defined id used to identify the group. Must be unique in the list view.
+    pub id: i32,
+
+    /// The text displayed in the group header
+    pub header: String,
+
+    /// If the group can be collapsed by the user
+    pub collapsible: bool,
+
+    /// If the group is collapsed when inserted
+    pub collapsed: bool,
+}
+
 
 /// Represents a list view item parameters
 #[derive(Default, Clone, Debug)]
@@ -206,6 +259,44 @@ pub struct ListViewItem {
     pub image: i32,
 }
 
+/**
+    The items of a virtual list view. The list view only asks for the cells that are currently visible.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    struct LogLines {
+        lines: Vec<(u64, String)>
+    }
+
+    impl nwg::ListViewDataSource for LogLines {
+        fn row_count(&self) -> usize { self.lines.len() }
+
+        fn cell_text(&self, row: usize, column: usize) -> String {
+            match column {
+                0 => self.lines[row].0.to_string(),
+                _ => self.lines[row].1.clone(),
+            }
+        }
+    }
+    ```
+*/
+pub trait ListViewDataSource {
+    /// Returns the number of rows in the list view
+    fn row_count(&self) -> usize;
+
+    /// Returns the text of the cell at `row` and `column`
+    fn cell_text(&self, row: usize, column: usize) -> String;
+
+    /// Returns the index of the image of the cell in the list view image list. Images are only displayed at column 0.
+    fn cell_image(&self, _row: usize, _column: usize) -> Option<i32> { None }
+
+    /// Called before the list view asks for the cells of the rows `from` to `to` (inclusive).
+    /// Can be used to prefetch the data if fetching the rows one by one is slow.
+    fn cache_hint(&self, _from: usize, _to: usize) {}
+}
+
+
 /**
 A list-view control is a window that displays a collection of items.
 List-view controls provide several ways to arrange and display items and are much more flexible than simple ListBox.
@@ -222,6 +313,7 @@ Builder parameters:
     * `item_count`: Number of item to preallocate
     * `list_style`: The default style of the listview
     * `focus`:      The control receive focus after being created
+    * `data_source`: Creates a virtual list view that fetch its items from the data source. See `ListView::set_data_source`
 
 **Control events:**
   * `MousePress(_)`: Generic mouse press events on the tree view
@@ -229,15 +321,36 @@ Builder parameters:
   * `OnMouseWheel`: Generic mouse wheel event
   * `OnKeyPress`:    Generic key press event
   * `OnKeyRelease`:  Generic key release event
+  * `OnListViewColumnClick`: When the user clicks on a column header
+  * `OnListViewItemChecked`: When the user checks or unchecks an item (see `ListViewExFlags::CHECKBOXES`)
+  * `OnItemDrop`: When a row is dropped after a drag started in this list view. See `ListView::enable_item_drag`
+  * `OnListViewBeginEdit`: When the user starts editing an item
+  * `OnListViewEndEdit`: When the user is done editing an item
 
 Windows is Shit:
-- The win32 header controls leaks megabytes of memory per seconds because it is shit. As such, NO_HEADER is always ON.
+- The win32 header controls leaks megabytes of memory per seconds because it is shit. As such, NO_HEADER is ON by default.
+  The header is required to display the columns names and to receive `OnListViewColumnClick`. To display it, set the flags without NO_HEADER.
 
 */
 #[derive(Default)]
 pub struct ListView {
     pub handle: ControlHandle,
+    data_source: RefCell<Option<Rc<dyn ListViewDataSource>>>,
+    virtual_order: Rc<RefCell<Vec<usize>>>,
+    cell_edit: Rc<RefCell<Option<CellEdit>>>,
+    item_colors: Rc<RefCell<Option<Box<dyn Fn(usize, usize) -> ListViewCellColors>>>>,
+    drag: RefCell<Option<ItemDragHandler>>,
     handler0: RefCell<Option<RawEventHandler>>,
+    handler1: RefCell<Option<RawEventHandler>>,
+    handler2: RefCell<Option<RawEventHandler>>,
+}
+
+/// An item being edited with `ListView::edit_item`
+struct CellEdit {
+    editor: HWND,
+    handler: RawEventHandler,
+    row: usize,
+    column: usize,
 }
 
 impl ListView {
@@ -251,8 +364,102 @@ impl ListView {
             ex_flags: None,
             style: ListViewStyle::Simple,
             parent: None,
-            item_count: 0
+            item_count: 0,
+            data_source: None,
+        }
+    }
+
+    /**
+        Sets the data source of a virtual list view and sets the item count to `ListViewDataSource::row_count`.
+        The list view must have been created with the `VIRTUAL` flag.
+
+        In a virtual list view, the methods that modify the items (ex: `insert_item`, `update_item`, `remove_item`) do nothing.
+        Instead, update the data source and call `reload_data`.
+
+        Errors:
+          - If the list view was not created with the `VIRTUAL` flag
+          - If the event handler that fetches the items could not be bound
+    */
+    pub fn set_data_source(&self, source: Rc<dyn ListViewDataSource>) -> Result<(), NwgError> {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::commctrl::{NMLVDISPINFOW, NMLVCACHEHINT, LVN_GETDISPINFOW, LVN_ODCACHEHINT};
+        use winapi::um::winuser::{WM_NOTIFY, NMHDR};
+        use winapi::shared::{basetsd::UINT_PTR, minwindef::LRESULT};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if wh::get_style(handle) & LVS_OWNERDATA == 0 {
+            return Err(NwgError::control_create("ListView data source can only be set on a list view created with the VIRTUAL flag"));
+        }
+
+        self.unbind_parent_handler();
+
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let handler_source = source.clone();
+        let handler_order = self.virtual_order.clone();
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as UINT_PTR, move |_hwnd, msg, _w, l| {
+            if msg != WM_NOTIFY {
+                return None;
+            }
+
+            unsafe {
+                let nmhdr = &*(l as *const NMHDR);
+                if nmhdr.hwndFrom != handle {
+                    return None;
+                }
+
+                match nmhdr.code {
+                    LVN_GETDISPINFOW => {
+                        let info = &mut *(l as *mut NMLVDISPINFOW);
+                        fill_virtual_item(&*handler_source, &handler_order.borrow(), &mut info.item);
+                        Some(0 as LRESULT)
+                    },
+                    LVN_ODCACHEHINT => {
+                        // Hints are meaningless once the rows are sorted
+                        let hint = &*(l as *const NMLVCACHEHINT);
+                        if handler_order.borrow().is_empty() {
+                            handler_source.cache_hint(hint.iFrom as usize, hint.iTo as usize);
+                        }
+                        Some(0 as LRESULT)
+                    },
+                    _ => None
+                }
+            }
+        });
+
+        *self.handler0.borrow_mut() = Some(handler?);
+        *self.data_source.borrow_mut() = Some(source);
+
+        self.reload_data();
+
+        Ok(())
+    }
+
+    /// Returns the data source of a virtual list view
+    pub fn data_source(&self) -> Option<Rc<dyn ListViewDataSource>> {
+        self.data_source.borrow().clone()
+    }
+
+    /// Reads the row count of the data source again and redraws the list view.
+    /// Must be called after the data source is modified. Does nothing if the list view has no data source.
+    ///
+    /// If the row count changed, the order set by `sort_by` is reset.
+    pub fn reload_data(&self) {
+        use winapi::um::commctrl::{LVM_SETITEMCOUNT, LVSICF_NOSCROLL};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let count = match self.data_source.borrow().as_ref() {
+            Some(source) => source.row_count(),
+            None => { return; }
+        };
+
+        let mut order = self.virtual_order.borrow_mut();
+        if order.len() != count {
+            order.clear();
         }
+        drop(order);
+
+        wh::send_message(handle, LVM_SETITEMCOUNT, count as _, LVSICF_NOSCROLL);
+        self.invalidate();
     }
 
     /// Sets the image list of the listview
@@ -316,6 +523,306 @@ impl ListView {
         ]
     }
 
+    /**
+        Sets a callback that returns the colors of the cell at (row, column). Return the same colors for every column to color a whole row.
+        The callback is called each time a cell is painted, so it must be fast. Call `invalidate` to repaint the list view
+        after the colors changed.
+
+        Selected items are always painted with the system highlight color.
+        In a sorted virtual list view, use `source_row` to map `row` to the row of the data source.
+    */
+    pub fn set_item_colors<F>(&self, colors: F)
+        where F: Fn(usize, usize) -> ListViewCellColors + 'static
+    {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::commctrl::{NMLVCUSTOMDRAW, NM_CUSTOMDRAW, CDDS_PREPAINT, CDDS_ITEMPREPAINT, CDDS_SUBITEM,
+            CDRF_NOTIFYITEMDRAW, CDRF_NOTIFYSUBITEMDRAW, CDRF_NEWFONT, CDRF_DODEFAULT};
+        use winapi::um::winuser::{WM_NOTIFY, NMHDR};
+        use winapi::shared::basetsd::UINT_PTR;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        *self.item_colors.borrow_mut() = Some(Box::new(colors));
+
+        if self.handler2.borrow().is_none() {
+            // The address of the colors is used as id because the list view handle is already used by the data source handler
+            let handler_id = Rc::as_ptr(&self.item_colors) as *const u8 as UINT_PTR;
+            let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+            let item_colors = self.item_colors.clone();
+            let handler = bind_raw_event_handler_inner(&parent_handle, handler_id, move |_hwnd, msg, _w, l| {
+                if msg != WM_NOTIFY {
+                    return None;
+                }
+
+                unsafe {
+                    let nmhdr = &*(l as *const NMHDR);
+                    if nmhdr.hwndFrom != handle || nmhdr.code != NM_CUSTOMDRAW {
+                        return None;
+                    }
+
+                    let colors = item_colors.borrow();
+                    let colors = match colors.as_ref() {
+                        Some(c) => c,
+                        None => { return None; }
+                    };
+
+                    let draw = &mut *(l as *mut NMLVCUSTOMDRAW);
+                    match draw.nmcd.dwDrawStage {
+                        CDDS_PREPAINT => Some(CDRF_NOTIFYITEMDRAW),
+                        CDDS_ITEMPREPAINT => {
+                            // Only the detailed view sends the sub items notifications
+                            apply_cell_colors(draw, colors(draw.nmcd.dwItemSpec as usize, 0));
+                            Some(CDRF_NOTIFYSUBITEMDRAW | CDRF_NEWFONT)
+                        },
+                        stage if stage == CDDS_ITEMPREPAINT | CDDS_SUBITEM => {
+                            apply_cell_colors(draw, colors(draw.nmcd.dwItemSpec as usize, draw.iSubItem as usize));
+                            Some(CDRF_NEWFONT)
+                        },
+                        _ => Some(CDRF_DODEFAULT)
+                    }
+                }
+            });
+
+            *self.handler2.borrow_mut() = Some(handler.unwrap());
+        }
+
+        self.invalidate();
+    }
+
+    /// Removes the callback set with `set_item_colors`
+    pub fn reset_item_colors(&self) {
+        self.item_colors.borrow_mut().take();
+
+        let handler = self.handler2.borrow_mut().take();
+        if let Some(h) = handler {
+            drop(unbind_raw_event_handler(&h));
+        }
+
+        self.invalidate();
+    }
+
+    /**
+        Allows the user to drag the rows of the list view with the mouse. The rows can be dropped on any tree view or list view of the same window.
+        Only available in the `Detailed` and `SmallIcon` styles.
+
+        `validator` is called each time the item under the cursor changes and returns `true` if the row can be dropped there.
+        When the row is dropped, `OnItemDrop` is raised on this list view. The row is not moved automatically, use `move_row` in the event.
+    */
+    pub fn enable_item_drag<F>(&self, validator: F)
+        where F: Fn(&ItemDrag) -> bool + 'static
+    {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.disable_item_drag();
+        *self.drag.borrow_mut() = Some(bind_item_drag(handle, Box::new(validator)));
+    }
+
+    /// Stops the drag and drop of rows started by `enable_item_drag`
+    pub fn disable_item_drag(&self) {
+        if let Some(drag) = self.drag.borrow_mut().take() {
+            drag.unbind();
+        }
+    }
+
+    /**
+        Moves the row at `from` so that it ends up at index `to`. The texts, image, state, group and data of the row are preserved.
+        `OnListViewItemRemoved` and `OnListViewItemInsert` are raised during the move.
+
+        Does nothing in a virtual list view or if one of the indices is out of bounds.
+    */
+    pub fn move_row(&self, from: usize, to: usize) {
+        use winapi::um::commctrl::{LVM_GETITEMW, LVM_INSERTITEMW, LVM_DELETEITEM, LVM_SETITEMTEXTW, LVIF_STATE, LVIF_PARAM, LVIF_INDENT, LVIF_GROUPID};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let len = self.len();
+        if wh::get_style(handle) & LVS_OWNERDATA == LVS_OWNERDATA || from >= len || to >= len || from == to {
+            return;
+        }
+
+        let columns = self.column_len().max(1);
+        let texts: Vec<Vec<u16>> = (0..columns).map(|c| to_utf16(&item_text(handle, from, c))).collect();
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_IMAGE | LVIF_STATE | LVIF_PARAM | LVIF_INDENT | LVIF_GROUPID;
+        item.iItem = from as _;
+        item.stateMask = !0;
+        wh::send_message(handle, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _);
+
+        wh::send_message(handle, LVM_DELETEITEM, from as _, 0);
+
+        let mut text = texts[0].clone();
+        item.mask |= LVIF_TEXT;
+        item.iItem = to as _;
+        item.pszText = text.as_mut_ptr();
+        let row = wh::send_message(handle, LVM_INSERTITEMW, 0, &mut item as *mut LVITEMW as _);
+        if row < 0 {
+            return;
+        }
+
+        for (column, text) in texts.iter().enumerate().skip(1) {
+            let mut text = text.clone();
+            let mut sub_item: LVITEMW = unsafe { mem::zeroed() };
+            sub_item.iSubItem = column as _;
+            sub_item.pszText = text.as_mut_ptr();
+            wh::send_message(handle, LVM_SETITEMTEXTW, row as _, &mut sub_item as *mut LVITEMW as _);
+        }
+    }
+
+    /// Checks or unchecks an item. The list view must have the `ListViewExFlags::CHECKBOXES` flag.
+    pub fn set_item_checked(&self, row_index: usize, checked: bool) {
+        use winapi::um::commctrl::{LVM_SETITEMSTATE, LVIS_STATEIMAGEMASK, INDEXTOSTATEIMAGEMASK};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.stateMask = LVIS_STATEIMAGEMASK;
+        item.state = INDEXTOSTATEIMAGEMASK(match checked { true => 2, false => 1 });
+
+        wh::send_message(handle, LVM_SETITEMSTATE, row_index as _, &mut item as *mut LVITEMW as _);
+    }
+
+    /// Returns `true` if the item is checked. Returns `false` if the list view does not have the `ListViewExFlags::CHECKBOXES` flag.
+    pub fn item_checked(&self, row_index: usize) -> bool {
+        use winapi::um::commctrl::{LVM_GETITEMSTATE, LVIS_STATEIMAGEMASK};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let state = wh::send_message(handle, LVM_GETITEMSTATE, row_index as _, LVIS_STATEIMAGEMASK as _) as u32;
+
+        ((state & LVIS_STATEIMAGEMASK) >> 12) == 2
+    }
+
+    /// Returns the index of the checked items
+    pub fn checked_items(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&row| self.item_checked(row)).collect()
+    }
+
+    /**
+        Enables or disables the group view. When enabled, the items are displayed under the header of their group
+        and the items without a group are hidden.
+
+        Groups are only available if Comclt32.dll version is >= 6.0 (see `enable_visual_styles`).
+    */
+    pub fn set_groups_enabled(&self, enabled: bool) {
+        use winapi::um::commctrl::LVM_ENABLEGROUPVIEW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_ENABLEGROUPVIEW, enabled as _, 0);
+    }
+
+    /// Returns `true` if the group view is enabled
+    pub fn groups_enabled(&self) -> bool {
+        use winapi::um::commctrl::LVM_ISGROUPVIEWENABLED;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_ISGROUPVIEWENABLED, 0, 0) != 0
+    }
+
+    /// Inserts a new group at the end of the list view. Does nothing if a group with the same id already exists.
+    pub fn insert_group<I: Into<InsertListViewGroup>>(&self, insert: I) {
+        use winapi::um::commctrl::{LVGROUP, LVM_INSERTGROUP, LVGF_HEADER, LVGF_GROUPID, LVGF_STATE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let insert = insert.into();
+
+        let mut header = to_utf16(&insert.header);
+        let mut group: LVGROUP = unsafe { mem::zeroed() };
+        group.cbSize = mem::size_of::<LVGROUP>() as _;
+        group.mask = LVGF_HEADER | LVGF_GROUPID | LVGF_STATE;
+        group.iGroupId = insert.id;
+        group.pszHeader = header.as_mut_ptr();
+        group.stateMask = group_state_mask();
+        group.state = group_state(insert.collapsible, insert.collapsed);
+
+        wh::send_message(handle, LVM_INSERTGROUP, -1isize as _, &mut group as *mut LVGROUP as _);
+    }
+
+    /// Returns `true` if the list view has a group with the id `group_id`
+    pub fn has_group(&self, group_id: i32) -> bool {
+        use winapi::um::commctrl::LVM_HASGROUP;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_HASGROUP, group_id as _, 0) != 0
+    }
+
+    /// Removes a group. The items of the group are not removed, but they are hidden while the group view is enabled.
+    pub fn remove_group(&self, group_id: i32) {
+        use winapi::um::commctrl::LVM_REMOVEGROUP;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_REMOVEGROUP, group_id as _, 0);
+    }
+
+    /// Sets the header text of a group
+    pub fn set_group_header(&self, group_id: i32, header: &str) {
+        use winapi::um::commctrl::{LVGROUP, LVM_SETGROUPINFO, LVGF_HEADER};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut header = to_utf16(header);
+        let mut group: LVGROUP = unsafe { mem::zeroed() };
+        group.cbSize = mem::size_of::<LVGROUP>() as _;
+        group.mask = LVGF_HEADER;
+        group.pszHeader = header.as_mut_ptr();
+
+        wh::send_message(handle, LVM_SETGROUPINFO, group_id as _, &mut group as *mut LVGROUP as _);
+    }
+
+    /// Collapses or expands a group. The group must have been inserted with `collapsible` set to `true`.
+    pub fn set_group_collapsed(&self, group_id: i32, collapsed: bool) {
+        use winapi::um::commctrl::{LVGROUP, LVM_SETGROUPINFO, LVGF_STATE, LVGS_COLLAPSED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut group: LVGROUP = unsafe { mem::zeroed() };
+        group.cbSize = mem::size_of::<LVGROUP>() as _;
+        group.mask = LVGF_STATE;
+        group.stateMask = LVGS_COLLAPSED;
+        group.state = match collapsed { true => LVGS_COLLAPSED, false => 0 };
+
+        wh::send_message(handle, LVM_SETGROUPINFO, group_id as _, &mut group as *mut LVGROUP as _);
+    }
+
+    /// Returns `true` if the group is collapsed
+    pub fn group_collapsed(&self, group_id: i32) -> bool {
+        use winapi::um::commctrl::{LVM_GETGROUPSTATE, LVGS_COLLAPSED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let state = wh::send_message(handle, LVM_GETGROUPSTATE, group_id as _, LVGS_COLLAPSED as _) as u32;
+        state & LVGS_COLLAPSED == LVGS_COLLAPSED
+    }
+
+    /// Moves an item into a group. Use `None` to remove the item from its group.
+    pub fn set_item_group(&self, row_index: usize, group_id: Option<i32>) {
+        use winapi::um::commctrl::{LVM_SETITEMW, LVIF_GROUPID, I_GROUPIDNONE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_GROUPID;
+        item.iItem = row_index as _;
+        item.iGroupId = group_id.unwrap_or(I_GROUPIDNONE);
+
+        wh::send_message(handle, LVM_SETITEMW, 0, &mut item as *mut LVITEMW as _);
+    }
+
+    /// Returns the id of the group of an item, or `None` if the item is not in a group
+    pub fn item_group(&self, row_index: usize) -> Option<i32> {
+        use winapi::um::commctrl::{LVM_GETITEMW, LVIF_GROUPID};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_GROUPID;
+        item.iItem = row_index as _;
+
+        if wh::send_message(handle, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _) == 0 {
+            return None;
+        }
+
+        match item.iGroupId < 0 {
+            true => None,
+            false => Some(item.iGroupId)
+        }
+    }
+
     /// Returns the index of the selected column. Only available if Comclt32.dll version is >= 6.0.
     pub fn selected_column(&self) -> usize {
         use winapi::um::commctrl::LVM_GETSELECTEDCOLUMN;
@@ -332,6 +839,251 @@ impl ListView {
         wh::send_message(handle, LVM_SETSELECTEDCOLUMN, index as _, 0);
     }
 
+    /**
+        Sorts the rows of the list view by comparing the text of the items in `column`.
+
+        In a virtual list view, the data source is not modified. Instead, the list view keeps a permutation of the rows
+        of the data source. Use `source_row` to map a row of the list view to a row of the data source.
+
+        ```rust
+        use native_windows_gui as nwg;
+
+        fn sort_column(list: &nwg::ListView, column: usize, sort: nwg::ListViewSort) {
+            match sort {
+                nwg::ListViewSort::Ascending => list.sort_by(column, |a, b| a.cmp(b)),
+                nwg::ListViewSort::Descending => list.sort_by(column, |a, b| b.cmp(a)),
+            }
+            list.set_sort_indicator(column, Some(sort));
+        }
+        ```
+    */
+    pub fn sort_by<F>(&self, column: usize, mut comparator: F)
+        where F: FnMut(&str, &str) -> Ordering
+    {
+        use winapi::um::commctrl::{LVM_SORTITEMS, LVM_GETITEMW, LVM_SETITEMW, LVIF_PARAM};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if let Some(source) = self.data_source() {
+            let count = source.row_count();
+            let texts: Vec<String> = (0..count).map(|row| source.cell_text(row, column)).collect();
+            let mut order: Vec<usize> = (0..count).collect();
+            order.sort_by(|&a, &b| comparator(&texts[a], &texts[b]));
+
+            *self.virtual_order.borrow_mut() = order;
+            self.invalidate();
+            return;
+        }
+
+        // Store the current index of the items in their lParam so that the texts can be fetched beforehand.
+        // The user data of the items is saved and restored once the rows are sorted.
+        let count = self.len();
+        let mut texts = Vec::with_capacity(count);
+        let mut params = Vec::with_capacity(count);
+        for row in 0..count {
+            let mut item: LVITEMW = unsafe { mem::zeroed() };
+            item.mask = LVIF_PARAM;
+            item.iItem = row as _;
+            wh::send_message(handle, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _);
+            params.push(item.lParam);
+
+            item.lParam = row as _;
+            wh::send_message(handle, LVM_SETITEMW, 0, &mut item as *mut LVITEMW as _);
+
+            texts.push(item_text(handle, row, column));
+        }
+
+        {
+            let mut compare = |a: usize, b: usize| comparator(&texts[a], &texts[b]);
+            let mut compare_ref: &mut dyn FnMut(usize, usize) -> Ordering = &mut compare;
+            let compare_ptr = &mut compare_ref as *mut &mut dyn FnMut(usize, usize) -> Ordering;
+
+            wh::send_message(handle, LVM_SORTITEMS, compare_ptr as _, compare_items as usize as _);
+        }
+
+        for row in 0..count {
+            let mut item: LVITEMW = unsafe { mem::zeroed() };
+            item.mask = LVIF_PARAM;
+            item.iItem = row as _;
+            wh::send_message(handle, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _);
+
+            if let Some(&param) = params.get(item.lParam as usize) {
+                item.lParam = param;
+                wh::send_message(handle, LVM_SETITEMW, 0, &mut item as *mut LVITEMW as _);
+            }
+        }
+    }
+
+    /**
+        Starts editing an item. `OnListViewBeginEdit` is raised first and the edition is cancelled if the event is rejected.
+        Once the user press ENTER or the editor loses the focus, `OnListViewEndEdit` is raised with the new text.
+        Pressing ESCAPE cancels the edition.
+
+        Items in the first column are edited using the native label edition if the list view has the `EDIT_LABELS` flag and `editor` is `Text`.
+        Otherwise, a `editor` control is displayed over the item.
+
+        In a virtual list view, the data source must be updated in the `OnListViewEndEdit` event.
+
+        Errors:
+          - If the editor control could not be created
+          - If the event handlers used by the edition could not be bound
+    */
+    pub fn edit_item(&self, row: usize, column: usize, editor: ListViewCellEditor) -> Result<(), NwgError> {
+        use winapi::um::commctrl::{LVM_EDITLABELW, LVM_ENSUREVISIBLE, LVM_GETSUBITEMRECT, LVIR_BOUNDS, LVIR_LABEL, LVN_BEGINLABELEDITW};
+        use winapi::um::winuser::{CB_ADDSTRING, CB_FINDSTRINGEXACT, CB_SETCURSEL, CBS_DROPDOWNLIST};
+        use winapi::um::winuser::{WS_CHILD, WS_BORDER, WS_VSCROLL, ES_AUTOHSCROLL, EM_SETSEL, WM_GETFONT, WM_SETFONT, MoveWindow, SetFocus, DestroyWindow};
+        use winapi::shared::windef::RECT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.cancel_edit();
+
+        if column == 0 && wh::get_style(handle) & LVS_EDITLABELS == LVS_EDITLABELS {
+            if let ListViewCellEditor::Text = editor {
+                unsafe { SetFocus(handle); }
+                wh::send_message(handle, LVM_EDITLABELW, row as _, 0);
+                return Ok(());
+            }
+        }
+
+        if send_edit_notification(handle, LVN_BEGINLABELEDITW, row, column, None) != 0 {
+            return Ok(());
+        }
+
+        wh::send_message(handle, LVM_ENSUREVISIBLE, row as _, 0);
+
+        let mut rect: RECT = unsafe { mem::zeroed() };
+        rect.top = column as _;
+        rect.left = match column == 0 { true => LVIR_LABEL, false => LVIR_BOUNDS };
+        if wh::send_message(handle, LVM_GETSUBITEMRECT, row as _, &mut rect as *mut RECT as _) == 0 {
+            return Ok(());
+        }
+
+        self.bind_edit_handler()?;
+
+        let text = item_text(handle, row, column);
+        let (class_name, flags, height) = match &editor {
+            ListViewCellEditor::Text => ("Edit", ES_AUTOHSCROLL, rect.bottom - rect.top),
+            ListViewCellEditor::Choices(_) => ("ComboBox", CBS_DROPDOWNLIST | WS_VSCROLL, 200),
+        };
+
+        let editor_handle = ControlBase::build_hwnd()
+            .class_name(class_name)
+            .forced_flags(WS_CHILD | WS_BORDER)
+            .flags(WS_VISIBLE | flags)
+            .size((0, 0))
+            .position((0, 0))
+            .text(&text)
+            .parent(Some(self.handle))
+            .build()?;
+
+        let editor_hwnd = editor_handle.hwnd().unwrap();
+
+        unsafe { MoveWindow(editor_hwnd, rect.left, rect.top, rect.right - rect.left, height, 1); }
+
+        let font = wh::send_message(handle, WM_GETFONT, 0, 0);
+        wh::send_message(editor_hwnd, WM_SETFONT, font as _, 0);
+
+        match &editor {
+            ListViewCellEditor::Text => {
+                wh::send_message(editor_hwnd, EM_SETSEL as u32, 0, -1);
+            },
+            ListViewCellEditor::Choices(choices) => {
+                for choice in choices.iter() {
+                    let choice = to_utf16(choice);
+                    wh::send_message(editor_hwnd, CB_ADDSTRING, 0, choice.as_ptr() as _);
+                }
+
+                let text = to_utf16(&text);
+                let index = wh::send_message(editor_hwnd, CB_FINDSTRINGEXACT, -1isize as _, text.as_ptr() as _);
+                wh::send_message(editor_hwnd, CB_SETCURSEL, index as _, 0);
+            }
+        }
+
+        let handler = match bind_cell_editor_handler(handle, editor_hwnd, self.edit_handler_id()) {
+            Ok(h) => h,
+            Err(e) => {
+                unsafe { DestroyWindow(editor_hwnd); }
+                return Err(e);
+            }
+        };
+
+        *self.cell_edit.borrow_mut() = Some(CellEdit { editor: editor_hwnd, handler, row, column });
+
+        unsafe { SetFocus(editor_hwnd); }
+
+        Ok(())
+    }
+
+    /// Stops editing an item without saving the new text. Does nothing if no item is being edited.
+    pub fn cancel_edit(&self) {
+        use winapi::um::commctrl::LVM_CANCELEDITLABEL;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_CANCELEDITLABEL, 0, 0);
+        finish_cell_edit(handle, &self.cell_edit, false);
+    }
+
+    /// Returns the (row, column) of the item being edited with a editor control, if any.
+    pub fn editing_item(&self) -> Option<(usize, usize)> {
+        self.cell_edit.borrow().as_ref().map(|e| (e.row, e.column))
+    }
+
+    /// Maps a row of the list view to a row of the data source. Only useful for a virtual list view sorted with `sort_by`.
+    /// Without sorting, or if the list view is not virtual, returns `row`.
+    pub fn source_row(&self, row: usize) -> usize {
+        self.virtual_order.borrow().get(row).copied().unwrap_or(row)
+    }
+
+    /// Sets the sort arrow in the header of a column. `None` removes the arrow.
+    /// This only changes the look of the header, use `sort_by` to sort the items.
+    pub fn set_sort_indicator(&self, column: usize, sort: Option<ListViewSort>) {
+        use winapi::um::commctrl::{LVM_GETHEADER, HDM_GETITEMW, HDM_SETITEMW, HDITEMW, HDI_FORMAT, HDF_SORTUP, HDF_SORTDOWN};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let header = wh::send_message(handle, LVM_GETHEADER, 0, 0) as HWND;
+        if header.is_null() {
+            return;
+        }
+
+        let mut item: HDITEMW = unsafe { mem::zeroed() };
+        item.mask = HDI_FORMAT;
+        if wh::send_message(header, HDM_GETITEMW, column as _, &mut item as *mut HDITEMW as _) == 0 {
+            return;
+        }
+
+        item.fmt &= !(HDF_SORTUP | HDF_SORTDOWN);
+        item.fmt |= match sort {
+            Some(ListViewSort::Ascending) => HDF_SORTUP,
+            Some(ListViewSort::Descending) => HDF_SORTDOWN,
+            None => 0
+        };
+
+        wh::send_message(header, HDM_SETITEMW, column as _, &mut item as *mut HDITEMW as _);
+    }
+
+    /// Returns the sort arrow displayed in the header of a column
+    pub fn sort_indicator(&self, column: usize) -> Option<ListViewSort> {
+        use winapi::um::commctrl::{LVM_GETHEADER, HDM_GETITEMW, HDITEMW, HDI_FORMAT, HDF_SORTUP, HDF_SORTDOWN};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let header = wh::send_message(handle, LVM_GETHEADER, 0, 0) as HWND;
+        if header.is_null() {
+            return None;
+        }
+
+        let mut item: HDITEMW = unsafe { mem::zeroed() };
+        item.mask = HDI_FORMAT;
+        wh::send_message(header, HDM_GETITEMW, column as _, &mut item as *mut HDITEMW as _);
+
+        if item.fmt & HDF_SORTUP == HDF_SORTUP {
+            Some(ListViewSort::Ascending)
+        } else if item.fmt & HDF_SORTDOWN == HDF_SORTDOWN {
+            Some(ListViewSort::Descending)
+        } else {
+            None
+        }
+    }
+
     /// Returns the number of selected items
     pub fn selected_count(&self) -> usize {
         use winapi::um::commctrl::LVM_GETSELECTEDCOUNT;
@@ -510,10 +1262,15 @@ impl ListView {
     }
 
     /// Inserts a new item into the list view
+    /// Does nothing in a virtual list view
     pub fn insert_item<I: Into<InsertListViewItem>>(&self, insert: I) {
         use winapi::um::commctrl::{LVM_INSERTITEMW, LVM_SETITEMW};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if wh::get_style(handle) & LVS_OWNERDATA == LVS_OWNERDATA {
+            return;
+        }
+
         let insert = insert.into();
 
         let row_insert = insert.index.unwrap_or(i32::max_value());
@@ -598,15 +1355,15 @@ impl ListView {
     }
 
     /// Updates the item at the selected position
-    /// Does nothing if there is no item at the selected position
+    /// Does nothing if there is no item at the selected position or in a virtual list view
     pub fn update_item<I: Into<InsertListViewItem>>(&self, row_index: usize, data: I) {
         use winapi::um::commctrl::LVM_SETITEMW;
 
-        if !self.has_item(row_index, 0) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if wh::get_style(handle) & LVS_OWNERDATA == LVS_OWNERDATA || !self.has_item(row_index, 0) {
             return;
         }
 
-        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let insert = data.into();
 
         let mut mask = check_image_mask(&insert);
@@ -636,10 +1393,15 @@ impl ListView {
 
     /// Remove all items on the seleted row. Returns `true` if an item was removed or false otherwise.
     /// To "remove" an item without deleting the row, use `update_item` and set the text to "".
+    /// Does nothing and returns `false` in a virtual list view.
     pub fn remove_item(&self, row_index: usize) -> bool {
         use winapi::um::commctrl::LVM_DELETEITEM;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if wh::get_style(handle) & LVS_OWNERDATA == LVS_OWNERDATA {
+            return false;
+        }
+
         wh::send_message(handle, LVM_DELETEITEM , row_index as _, 0) == 1
     }
 
@@ -824,23 +1586,80 @@ impl ListView {
 
     /// Winapi base flags used during window creation
     pub fn flags(&self) -> u32 {
-        WS_VISIBLE | WS_TABSTOP
+        WS_VISIBLE | WS_TABSTOP | LVS_NOCOLUMNHEADER
     }
 
     /// Winapi flags required by the control
     pub fn forced_flags(&self) -> u32 {
         use winapi::um::winuser::{WS_CHILD, WS_BORDER};
 
-        WS_CHILD | WS_BORDER | LVS_NOCOLUMNHEADER
+        WS_CHILD | WS_BORDER
+    }
+
+}
+
+impl ListView {
+
+    fn unbind_parent_handler(&self) {
+        let handler = self.handler0.borrow_mut().take();
+        if let Some(h) = handler {
+            drop(unbind_raw_event_handler(&h));
+        }
+    }
+
+    /// The id of the raw event handlers used by `edit_item`. Unique for each list view and outside the range reserved by NWG.
+    fn edit_handler_id(&self) -> UINT_PTR {
+        Rc::as_ptr(&self.cell_edit) as *const u8 as UINT_PTR
+    }
+
+    /// Ends the edition started by `edit_item` when the list view receives `NWG_LIST_VIEW_END_EDIT` or when it is scrolled.
+    /// The handler is bound the first time an item is edited.
+    fn bind_edit_handler(&self) -> Result<(), NwgError> {
+        use crate::bind_raw_event_handler_inner;
+        use crate::win32::window_helper::NWG_LIST_VIEW_END_EDIT;
+        use winapi::um::winuser::{WM_VSCROLL, WM_HSCROLL, WM_MOUSEWHEEL};
+
+        if self.handler1.borrow().is_some() {
+            return Ok(());
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let cell_edit = self.cell_edit.clone();
+        let handler = bind_raw_event_handler_inner(&self.handle, self.edit_handler_id(), move |_hwnd, msg, w, _l| {
+            match msg {
+                NWG_LIST_VIEW_END_EDIT => {
+                    finish_cell_edit(handle, &cell_edit, w != 0);
+                    Some(0)
+                },
+                WM_VSCROLL | WM_HSCROLL | WM_MOUSEWHEEL => {
+                    finish_cell_edit(handle, &cell_edit, true);
+                    None
+                },
+                _ => None
+            }
+        });
+
+        *self.handler1.borrow_mut() = Some(handler?);
+
+        Ok(())
     }
 
 }
 
 impl Drop for ListView {
     fn drop(&mut self) {
-        let handler = self.handler0.borrow();
-        if let Some(h) = handler.as_ref() {
-            drop(unbind_raw_event_handler(h));
+        self.disable_item_drag();
+
+        if let Some(edit) = self.cell_edit.borrow_mut().take() {
+            drop(unbind_raw_event_handler(&edit.handler));
+        }
+
+        self.unbind_parent_handler();
+
+        for handler in [&self.handler1, &self.handler2].iter() {
+            if let Some(h) = handler.borrow().as_ref() {
+                drop(unbind_raw_event_handler(h));
+            }
         }
 
         self.handle.destroy();
@@ -855,6 +1674,7 @@ pub struct ListViewBuilder {
     ex_flags: Option<ListViewExFlags>,
     style: ListViewStyle,
     item_count: u32,
+    data_source: Option<Rc<dyn ListViewDataSource>>,
     parent: Option<ControlHandle>
 }
 
@@ -900,10 +1720,19 @@ impl ListViewBuilder {
         self
     }
 
+    pub fn data_source(mut self, source: Rc<dyn ListViewDataSource>) -> ListViewBuilder {
+        self.data_source = Some(source);
+        self
+    }
+
     pub fn build(self, out: &mut ListView) -> Result<(), NwgError> {
         let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
         flags |= self.style.bits();
 
+        if self.data_source.is_some() {
+            flags |= LVS_OWNERDATA;
+        }
+
         let parent = match self.parent {
             Some(p) => Ok(p),
             None => Err(NwgError::no_parent("ListView"))
@@ -932,6 +1761,10 @@ impl ListViewBuilder {
             wh::send_message(out.handle.hwnd().unwrap(), LVM_SETEXTENDEDLISTVIEWSTYLE, flags as _, flags as _);
         }
 
+        if let Some(source) = self.data_source {
+            out.set_data_source(source)?;
+        }
+
         Ok(())
     }
 
@@ -974,6 +1807,17 @@ impl<'a> From<&'a str> for InsertListViewColumn {
     }
 }
 
+impl<'a> From<(i32, &'a str)> for InsertListViewGroup {
+    fn from(i: (i32, &'a str)) -> Self {
+        InsertListViewGroup {
+            id: i.0,
+            header: i.1.to_string(),
+            collapsible: true,
+            collapsed: false,
+        }
+    }
+}
+
 impl From<String> for InsertListViewColumn {
     fn from(i: String) -> Self {
         InsertListViewColumn {
@@ -985,6 +1829,181 @@ impl From<String> for InsertListViewColumn {
     }
 }
 
+/// Answers a `LVN_GETDISPINFOW` request of a virtual list view. `order` maps the rows of the list view to the rows of the source.
+unsafe fn fill_virtual_item(source: &dyn ListViewDataSource, order: &[usize], item: &mut LVITEMW) {
+    use winapi::um::commctrl::I_IMAGENONE;
+    use std::ptr;
+
+    if item.iItem < 0 {
+        return;
+    }
+
+    let row = order.get(item.iItem as usize).copied().unwrap_or(item.iItem as usize);
+    let column = item.iSubItem as usize;
+    if row >= source.row_count() {
+        return;
+    }
+
+    if item.mask & LVIF_TEXT == LVIF_TEXT && !item.pszText.is_null() && item.cchTextMax > 0 {
+        let text = to_utf16(&source.cell_text(row, column));
+        let len = (text.len() - 1).min(item.cchTextMax as usize - 1);
+        ptr::copy_nonoverlapping(text.as_ptr(), item.pszText, len);
+        *item.pszText.add(len) = 0;
+    }
+
+    if item.mask & LVIF_IMAGE == LVIF_IMAGE {
+        item.iImage = source.cell_image(row, column).unwrap_or(I_IMAGENONE);
+    }
+}
+
+/// Sets the colors of the cell painted by a `NM_CUSTOMDRAW` notification
+fn apply_cell_colors(draw: &mut winapi::um::commctrl::NMLVCUSTOMDRAW, colors: ListViewCellColors) {
+    use winapi::um::commctrl::CLR_DEFAULT;
+    use winapi::um::wingdi::RGB;
+
+    // The colors must always be set, otherwise the colors of the previous cell are reused
+    draw.clrText = colors.text.map(|[r, g, b]| RGB(r, g, b)).unwrap_or(CLR_DEFAULT);
+    draw.clrTextBk = colors.background.map(|[r, g, b]| RGB(r, g, b)).unwrap_or(CLR_DEFAULT);
+}
+
+fn group_state_mask() -> u32 {
+    use winapi::um::commctrl::{LVGS_COLLAPSIBLE, LVGS_COLLAPSED};
+    LVGS_COLLAPSIBLE | LVGS_COLLAPSED
+}
+
+fn group_state(collapsible: bool, collapsed: bool) -> u32 {
+    use winapi::um::commctrl::{LVGS_COLLAPSIBLE, LVGS_COLLAPSED};
+
+    let mut state = 0;
+    if collapsible { state |= LVGS_COLLAPSIBLE; }
+    if collapsed { state |= LVGS_COLLAPSED; }
+    state
+}
+
+/// Returns the text of an item
+fn item_text(handle: HWND, row: usize, column: usize) -> String {
+    use winapi::um::commctrl::LVM_GETITEMTEXTW;
+
+    // The list view does not give the text length, so the buffer grows until the text is not truncated
+    let mut size = 260;
+    loop {
+        let mut buffer: Vec<u16> = vec![0; size];
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.iSubItem = column as _;
+        item.pszText = buffer.as_mut_ptr();
+        item.cchTextMax = buffer.len() as _;
+        let len = wh::send_message(handle, LVM_GETITEMTEXTW, row as _, &mut item as *mut LVITEMW as _) as usize;
+
+        if len + 1 < size {
+            return from_utf16(&buffer);
+        }
+
+        size *= 2;
+    }
+}
+
+/// Sends a label edition notification to the parent of the list view, as if it was sent by the list view itself.
+fn send_edit_notification(handle: HWND, code: u32, row: usize, column: usize, text: Option<&str>) -> isize {
+    use winapi::um::commctrl::NMLVDISPINFOW;
+    use winapi::um::winuser::{WM_NOTIFY, GetDlgCtrlID};
+    use std::ptr;
+
+    let mut text = text.map(to_utf16);
+
+    let mut info: NMLVDISPINFOW = unsafe { mem::zeroed() };
+    info.hdr.hwndFrom = handle;
+    info.hdr.idFrom = unsafe { GetDlgCtrlID(handle) as _ };
+    info.hdr.code = code;
+    info.item.mask = LVIF_TEXT;
+    info.item.iItem = row as _;
+    info.item.iSubItem = column as _;
+    info.item.pszText = text.as_mut().map(|t| t.as_mut_ptr()).unwrap_or(ptr::null_mut());
+
+    let parent = wh::get_window_parent(handle);
+    wh::send_message(parent, WM_NOTIFY, info.hdr.idFrom, &mut info as *mut NMLVDISPINFOW as _)
+}
+
+/// Forwards the keys used to end the edition to the list view. Called on the editor created by `ListView::edit_item`.
+fn bind_cell_editor_handler(list: HWND, editor: HWND, handler_id: UINT_PTR) -> Result<RawEventHandler, NwgError> {
+    use crate::bind_raw_event_handler_inner;
+    use crate::win32::window_helper::NWG_LIST_VIEW_END_EDIT;
+    use winapi::um::winuser::{WM_KEYDOWN, WM_CHAR, WM_KILLFOCUS, WM_GETDLGCODE, DLGC_WANTALLKEYS, VK_RETURN, VK_ESCAPE};
+
+    bind_raw_event_handler_inner(&ControlHandle::Hwnd(editor), handler_id, move |_hwnd, msg, w, _l| {
+        match msg {
+            // The dialog message loop would otherwise steal ENTER and ESCAPE
+            WM_GETDLGCODE => Some(DLGC_WANTALLKEYS),
+            WM_KEYDOWN if w as i32 == VK_RETURN => {
+                wh::post_message(list, NWG_LIST_VIEW_END_EDIT, 1, 0);
+                Some(0)
+            },
+            WM_KEYDOWN if w as i32 == VK_ESCAPE => {
+                wh::post_message(list, NWG_LIST_VIEW_END_EDIT, 0, 0);
+                Some(0)
+            },
+            // Prevents the beep
+            WM_CHAR if w == 0x0D || w == 0x1B => Some(0),
+            WM_KILLFOCUS => {
+                wh::post_message(list, NWG_LIST_VIEW_END_EDIT, 1, 0);
+                None
+            },
+            _ => None
+        }
+    })
+}
+
+/// Destroys the editor created by `ListView::edit_item` and raise `OnListViewEndEdit`. If the edition is accepted, the item text is updated.
+fn finish_cell_edit(handle: HWND, cell_edit: &RefCell<Option<CellEdit>>, accept: bool) {
+    use winapi::um::commctrl::{LVN_ENDLABELEDITW, LVM_SETITEMTEXTW, LVM_REDRAWITEMS};
+    use winapi::um::winuser::{GetFocus, SetFocus};
+
+    let edit = match cell_edit.borrow_mut().take() {
+        Some(e) => e,
+        None => { return; }
+    };
+
+    drop(unbind_raw_event_handler(&edit.handler));
+
+    let text = unsafe { wh::get_window_text(edit.editor) };
+    unsafe {
+        if GetFocus() == edit.editor {
+            SetFocus(handle);
+        }
+    }
+    wh::destroy_window(edit.editor);
+
+    let new_text = match accept {
+        true => Some(&text as &str),
+        false => None
+    };
+
+    let accepted = send_edit_notification(handle, LVN_ENDLABELEDITW, edit.row, edit.column, new_text) != 0;
+    if !(accept && accepted) {
+        return;
+    }
+
+    if wh::get_style(handle) & LVS_OWNERDATA == LVS_OWNERDATA {
+        wh::send_message(handle, LVM_REDRAWITEMS, edit.row as _, edit.row as _);
+        return;
+    }
+
+    let mut text = to_utf16(&text);
+    let mut item: LVITEMW = unsafe { mem::zeroed() };
+    item.iSubItem = edit.column as _;
+    item.pszText = text.as_mut_ptr();
+    wh::send_message(handle, LVM_SETITEMTEXTW, edit.row as _, &mut item as *mut LVITEMW as _);
+}
+
+/// `LVM_SORTITEMS` callback. `l1` and `l2` are the item indices stored by `sort_by`
+unsafe extern "system" fn compare_items(l1: LPARAM, l2: LPARAM, sort: LPARAM) -> c_int {
+    let compare = &mut *(sort as *mut &mut dyn FnMut(usize, usize) -> Ordering);
+    match compare(l1 as usize, l2 as usize) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
  // Feature check
 
 #[cfg(feature="image-list")]
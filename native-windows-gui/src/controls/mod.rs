@@ -5,6 +5,7 @@ mod button;
 mod check_box;
 mod radio_button;
 mod text_input;
+mod text_validator;
 mod label;
 mod image_frame;
 
@@ -14,6 +15,12 @@ mod text_box;
 #[cfg(feature = "rich-textbox")]
 mod rich_text_box;
 
+#[cfg(any(feature = "textbox", feature = "rich-textbox"))]
+mod text_search;
+
+#[cfg(feature = "rich-textbox")]
+mod highlighter;
+
 #[cfg(feature = "status-bar")]
 mod status_bar;
 
@@ -41,6 +48,12 @@ mod list_box;
 #[cfg(feature = "datetime-picker")]
 mod date_picker;
 
+#[cfg(any(feature = "datetime-picker", feature = "month-calendar"))]
+mod date_picker_value;
+
+#[cfg(feature = "month-calendar")]
+mod month_calendar;
+
 #[cfg(feature = "progress-bar")]
 mod progress_bar;
 
@@ -65,6 +78,9 @@ mod list_view;
 #[cfg(feature = "number-select")]
 mod number_select;
 
+#[cfg(feature = "number-select")]
+mod number_format;
+
 #[cfg(feature = "extern-canvas")]
 mod extern_canvas;
 
@@ -83,14 +99,24 @@ pub use button::{Button, ButtonFlags, ButtonBuilder};
 pub use check_box::{CheckBox, CheckBoxState, CheckBoxFlags};
 pub use radio_button::{RadioButton, RadioButtonState, RadioButtonFlags};
 pub use text_input::{TextInput, TextInputFlags};
+pub use text_validator::{Validator, IntegerValidator, FloatValidator, InputMask};
 pub use label::{Label, LabelFlags};
 pub use image_frame::{ImageFrame, ImageFrameFlags};
 
+#[cfg(feature = "regex-validator")]
+pub use text_validator::RegexValidator;
+
 #[cfg(feature = "textbox")]
 pub use text_box::{TextBox, TextBoxFlags};
 
 #[cfg(feature = "rich-textbox")]
-pub use rich_text_box::{RichTextBox, RichTextBoxFlags};
+pub use rich_text_box::{RichTextBox, RichTextBoxFlags, CharFormat, ParaFormat, ParaAlignment, ParaNumbering};
+
+#[cfg(any(feature = "textbox", feature = "rich-textbox"))]
+pub use text_search::{FindOptions, find_text, utf16_offset_to_index, index_to_utf16_offset};
+
+#[cfg(feature = "rich-textbox")]
+pub use highlighter::{Highlighter, HighlightSpan, HighlightStyle, HighlightCache, StyleRun, KeywordHighlighter};
 
 #[cfg(feature = "status-bar")]
 pub use status_bar::StatusBar;
@@ -99,7 +125,7 @@ pub use status_bar::StatusBar;
 pub use tooltip::{Tooltip, TooltipIcon};
 
 #[cfg(feature = "trackbar")]
-pub use track_bar::{TrackBar, TrackBarFlags};
+pub use track_bar::{TrackBar, TrackBarFlags, TrackBarTooltipSide};
 
 #[cfg(feature = "menu")]
 pub use menu::{Menu, MenuBuilder, MenuItem, MenuSeparator, MenuItemBuilder};
@@ -114,13 +140,22 @@ pub use timer::{Timer, TimerBuilder};
 pub use notice::{Notice, NoticeSender, NoticeBuilder};
 
 #[cfg(feature = "combobox")]
-pub use combo_box::{ComboBox, ComboBoxFlags, ComboBoxBuilder};
+pub use combo_box::{ComboBox, ComboBoxFlags, ComboBoxBuilder, ComboBoxMatcher, PrefixMatcher, SubstringMatcher, FuzzyMatcher, rank_matches};
 
 #[cfg(feature = "listbox")]
 pub use list_box::{ListBox, ListBoxFlags, ListBoxBuilder};
 
 #[cfg(feature = "datetime-picker")]
-pub use date_picker::{DatePicker, DatePickerValue, DatePickerFlags, DatePickerBuilder};
+pub use date_picker::{DatePicker, DatePickerFlags, DatePickerBuilder};
+
+#[cfg(any(feature = "datetime-picker", feature = "month-calendar"))]
+pub use date_picker_value::DatePickerValue;
+
+#[cfg(feature = "month-calendar")]
+pub use month_calendar::{MonthCalendar, MonthCalendarFlags, MonthCalendarView, MonthCalendarBuilder};
+
+#[cfg(all(test, feature = "month-calendar"))]
+pub(crate) use month_calendar::BoldDays;
 
 #[cfg(feature = "progress-bar")]
 pub use progress_bar::{ProgressBar, ProgressBarState, ProgressBarFlags, ProgressBarBuilder};
@@ -129,7 +164,7 @@ pub use progress_bar::{ProgressBar, ProgressBarState, ProgressBarFlags, Progress
 pub use tabs::{TabsContainer, Tab, TabsContainerFlags, TabsContainerBuilder, TabBuilder};
 
 #[cfg(feature = "tree-view")]
-pub use treeview::{TreeView, TreeViewBuilder, TreeItem, TreeInsert, TreeItemAction, ExpandState, TreeItemState, TreeViewFlags};
+pub use treeview::{TreeView, TreeViewBuilder, TreeModel, TreeItem, TreeInsert, TreeItemAction, ExpandState, TreeItemState, TreeCheckState, TreeViewFlags};
 
 #[cfg(all(feature = "tree-view-iterator", feature = "tree-view") )]
 pub use treeview_iterator::TreeViewIterator;
@@ -141,13 +176,16 @@ pub use tray_notification::{TrayNotificationFlags, TrayNotification, TrayNotific
 pub use message_window::{MessageWindow, MessageWindowBuilder};
 
 #[cfg(feature = "list-view")]
-pub use list_view::{ListView, ListViewStyle, ListViewBuilder, ListViewFlags, ListViewExFlags, InsertListViewItem, ListViewItem, InsertListViewColumn, ListViewColumn};
+pub use list_view::{ListView, ListViewDataSource, ListViewSort, ListViewCellEditor, ListViewCellColors, InsertListViewGroup, ListViewStyle, ListViewBuilder, ListViewFlags, ListViewExFlags, InsertListViewItem, ListViewItem, InsertListViewColumn, ListViewColumn};
 
 #[cfg(all(feature="list-view", feature="image-list"))]
 pub use list_view::ListViewImageListType;
 
 #[cfg(feature = "number-select")]
-pub use number_select::{NumberSelect, NumberSelectBuilder, NumberSelectFlags};
+pub use number_select::{NumberSelect, NumberSelectBuilder, NumberSelectFlags, NumberSelectValue};
+
+#[cfg(feature = "number-select")]
+pub use number_format::NumberFormat;
 
 #[cfg(feature = "extern-canvas")]
 pub use extern_canvas::{ExternCanvas, ExternCanvasBuilder, ExternCanvasFlags};
@@ -0,0 +1,772 @@
+use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
+use winapi::um::commctrl::{MCS_DAYSTATE, MCS_MULTISELECT, MCS_WEEKNUMBERS, MCS_NOTODAY, MCS_NOTRAILINGDATES, MONTHDAYSTATE};
+use winapi::um::minwinbase::SYSTEMTIME;
+use winapi::shared::minwindef::{WPARAM, LPARAM};
+use winapi::shared::windef::{HWND, RECT};
+use crate::win32::window_helper as wh;
+use crate::win32::base_helper::check_hwnd;
+use crate::{Font, NwgError, RawEventHandler};
+use super::{ControlBase, ControlHandle, DatePickerValue};
+use super::date_picker_value::{to_systemtime, from_systemtime, days_in_month};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::mem;
+
+const NOT_BOUND: &'static str = "MonthCalendar is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: MonthCalendar handle is not HWND!";
+
+
+bitflags! {
+
+    /**
+        The MonthCalendarFlags flags
+
+        * NONE:              No flags. Equivalent to a invisible calendar.
+        * VISIBLE:           The calendar is immediatly visible after creation
+        * DISABLED:          The calendar cannot be interacted with by the user. It also has a grayed out look.
+        * TAB_STOP:          The control can be selected using tab navigation
+        * RANGE_SELECTION:   The user can select a range of days instead of a single day
+        * WEEK_NUMBERS:      Display the week numbers to the left of each row of days
+        * NO_TODAY:          Do not display the today date at the bottom of the control
+        * NO_TRAILING_DATES: Do not display the days of the previous and the next month
+        * BOLD_DAYS:         Ask the bold days callback which days should be bold. Set automatically by the `bold_days` builder parameter.
+    */
+    pub struct MonthCalendarFlags: u32 {
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+        const RANGE_SELECTION = MCS_MULTISELECT;
+        const WEEK_NUMBERS = MCS_WEEKNUMBERS;
+        const NO_TODAY = MCS_NOTODAY;
+        const NO_TRAILING_DATES = MCS_NOTRAILINGDATES;
+        const BOLD_DAYS = MCS_DAYSTATE;
+    }
+}
+
+/// The views of a month calendar. The user switches between the views by clicking on the title of the calendar.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MonthCalendarView {
+    /// The days of the month
+    Month,
+
+    /// The months of the year
+    Year,
+
+    /// The years of the decade
+    Decade,
+
+    /// The decades of the century
+    Century,
+}
+
+/// A callback that returns `true` if a day must be displayed in bold
+pub(crate) type BoldDaysCallback = Box<dyn Fn(&DatePickerValue) -> bool>;
+
+/// The bold days callback of a calendar, shared with the `MCN_GETDAYSTATE` handler
+#[derive(Default)]
+pub(crate) struct BoldDays {
+    pub(crate) callback: RefCell<Option<BoldDaysCallback>>,
+
+    /// The states sent to the control. The `MCN_GETDAYSTATE` answer only points to this buffer and the control
+    /// reads it after the handler returns, so it must live as long as the calendar. Do not move it into a local.
+    pub(crate) states: RefCell<Vec<MONTHDAYSTATE>>,
+}
+
+impl BoldDays {
+
+    /// Computes the states of `count` months, starting with the month of `start`
+    pub(crate) fn compute(&self, start: &SYSTEMTIME, count: usize) {
+        let callback = self.callback.borrow();
+        let mut states = self.states.borrow_mut();
+        states.clear();
+
+        let (mut year, mut month) = (start.wYear, start.wMonth);
+        for _ in 0..count {
+            let mut state: MONTHDAYSTATE = 0;
+            if let Some(callback) = callback.as_ref() {
+                for day in 1..=days_in_month(year, month) {
+                    if callback(&DatePickerValue::new(year, month, day)) {
+                        state |= 1 << (day - 1);
+                    }
+                }
+            }
+
+            states.push(state);
+
+            if month == 12 {
+                year += 1;
+                month = 1;
+            } else {
+                month += 1;
+            }
+        }
+    }
+
+}
+
+
+/**
+A month calendar control displays one or more months and lets the user select a day or a range of days.
+Unlike the `DatePicker`, the calendar is always visible.
+
+Requires the `month-calendar` feature.
+
+**Builder parameters:**
+  * `parent`:              **Required.** The calendar parent container.
+  * `size`:                The calendar size. The calendar displays as many months as the size can hold.
+  * `position`:            The calendar position.
+  * `flags`:               A combination of the MonthCalendarFlags values.
+  * `font`:                The font used for the calendar text
+  * `months`:              Resize the calendar to display `(columns, rows)` months. Overrides `size`.
+  * `selection`:           The selected day
+  * `selection_range`:     The selected range of days. Sets the `RANGE_SELECTION` flag.
+  * `max_selection_count`: The maximum number of days in a range selection. The default is 7.
+  * `range`:               The accepted range of dates. The value is inclusive.
+  * `bold_days`:           A callback that returns `true` for the days that are displayed in bold. Sets the `BOLD_DAYS` flag.
+  * `focus`:               The control receive focus after being created
+
+Invalid dates or dates outside of the range make `build` return a `NwgError::DateTimeError`.
+The time fields of the `DatePickerValue` are ignored by the calendar.
+
+**Control events:**
+  * `OnCalendarSelect`: When the user selects a day or a range of days
+  * `OnCalendarViewChange`: When the calendar switches between the month, year, decade and century views
+  * `MousePress(_)`: Generic mouse press events on the calendar
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnMouseWheel`: Generic mouse wheel event
+
+```rust
+use native_windows_gui as nwg;
+fn build_calendar(calendar: &mut nwg::MonthCalendar, window: &nwg::Window) {
+    let first = nwg::DatePickerValue::new(2020, 3, 2);
+    let last = nwg::DatePickerValue::new(2020, 3, 6);
+
+    nwg::MonthCalendar::builder()
+        .months((2, 1))
+        .selection_range(Some([first, last]))
+        .max_selection_count(14)
+        .bold_days(|date| date.day == 1 || date.day == 15)
+        .parent(window)
+        .build(calendar);
+}
+```
+*/
+#[derive(Default)]
+pub struct MonthCalendar {
+    pub handle: ControlHandle,
+    bold_days: Rc<BoldDays>,
+    handler0: RefCell<Option<RawEventHandler>>,
+}
+
+impl MonthCalendar {
+
+    pub fn builder<'a>() -> MonthCalendarBuilder<'a> {
+        MonthCalendarBuilder {
+            size: (230, 170),
+            position: (0, 0),
+            focus: false,
+            flags: None,
+            font: None,
+            parent: None,
+            months: None,
+            selection: None,
+            selection_range: None,
+            max_selection_count: None,
+            range: None,
+            bold_days: None
+        }
+    }
+
+    /// Return true if the user can select a range of days. See `MonthCalendarFlags::RANGE_SELECTION`.
+    pub fn range_selection(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::get_style(handle) & MCS_MULTISELECT == MCS_MULTISELECT
+    }
+
+    /// Return the selected day. If the calendar has a range selection, return the first day of the range.
+    pub fn selection(&self) -> DatePickerValue {
+        use winapi::um::commctrl::MCM_GETCURSEL;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if self.range_selection() {
+            return self.selection_range()[0];
+        }
+
+        let mut syst: SYSTEMTIME = unsafe { mem::zeroed() };
+        wh::send_message(handle, MCM_GETCURSEL, 0, &mut syst as *mut SYSTEMTIME as LPARAM);
+
+        date_of(&syst)
+    }
+
+    /**
+        Select a single day. The calendar scrolls to display the day.
+
+        Returns a `NwgError::DateTimeError` if the value is not a valid date or if it is outside of the control range.
+    */
+    pub fn set_selection(&self, date: DatePickerValue) -> Result<(), NwgError> {
+        use winapi::um::commctrl::MCM_SETCURSEL;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if self.range_selection() {
+            return self.set_selection_range(&[date, date]);
+        }
+
+        let date = self.check_date(handle, date)?;
+        let syst = to_systemtime(&date);
+        match wh::send_message(handle, MCM_SETCURSEL, 0, &syst as *const SYSTEMTIME as LPARAM) {
+            0 => Err(NwgError::date_time(format!("The MonthCalendar rejected {}", date.to_iso8601()))),
+            _ => Ok(())
+        }
+    }
+
+    /// Return the first and the last selected days. If the calendar does not have a range selection, both days are the selected day.
+    pub fn selection_range(&self) -> [DatePickerValue; 2] {
+        use winapi::um::commctrl::MCM_GETSELRANGE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if !self.range_selection() {
+            let date = self.selection();
+            return [date, date];
+        }
+
+        let mut range: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        wh::send_message(handle, MCM_GETSELRANGE, 0, &mut range as *mut [SYSTEMTIME; 2] as LPARAM);
+
+        [date_of(&range[0]), date_of(&range[1])]
+    }
+
+    /**
+        Select the days from `r[0]` to `r[1]` (inclusive). The calendar must have the `RANGE_SELECTION` flag.
+
+        Returns a `NwgError::DateTimeError` if one of the values is not a valid date, if it is outside of the control range,
+        if the first day is after the last day, or if the range has more days than `max_selection_count`.
+    */
+    pub fn set_selection_range(&self, r: &[DatePickerValue; 2]) -> Result<(), NwgError> {
+        use winapi::um::commctrl::MCM_SETSELRANGE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if !self.range_selection() {
+            return Err(NwgError::date_time("The MonthCalendar does not have the RANGE_SELECTION flag"));
+        }
+
+        let first = self.check_date(handle, r[0])?;
+        let last = self.check_date(handle, r[1])?;
+        if first > last {
+            return Err(NwgError::date_time(format!("The first selected day ({}) is after the last one ({})", first.to_iso8601(), last.to_iso8601())));
+        }
+
+        let values = [to_systemtime(&first), to_systemtime(&last)];
+        match wh::send_message(handle, MCM_SETSELRANGE, 0, &values as *const [SYSTEMTIME; 2] as LPARAM) {
+            0 => Err(NwgError::date_time(format!("The MonthCalendar rejected the selection from {} to {}. Is it longer than `max_selection_count`?", first.to_iso8601(), last.to_iso8601()))),
+            _ => Ok(())
+        }
+    }
+
+    /// Return the maximum number of days in a range selection
+    pub fn max_selection_count(&self) -> u32 {
+        use winapi::um::commctrl::MCM_GETMAXSELCOUNT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, MCM_GETMAXSELCOUNT, 0, 0) as u32
+    }
+
+    /// Sets the maximum number of days in a range selection. Does nothing if the calendar does not have the `RANGE_SELECTION` flag.
+    pub fn set_max_selection_count(&self, count: u32) {
+        use winapi::um::commctrl::MCM_SETMAXSELCOUNT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, MCM_SETMAXSELCOUNT, count as WPARAM, 0);
+    }
+
+    /**
+        Gets the minimum and maximum allowable dates of the calendar.
+        A limit that was never set is returned zeroed.
+    */
+    pub fn range(&self) -> [DatePickerValue; 2] {
+        use winapi::um::commctrl::MCM_GETRANGE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut tr: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        wh::send_message(handle, MCM_GETRANGE, 0, &mut tr as *mut [SYSTEMTIME; 2] as LPARAM);
+
+        [date_of(&tr[0]), date_of(&tr[1])]
+    }
+
+    /**
+        Sets the minimum and maximum allowable dates of the calendar.
+
+        Returns a `NwgError::DateTimeError` if one of the values is not a valid date or if the minimum is after the maximum.
+    */
+    pub fn set_range(&self, r: &[DatePickerValue; 2]) -> Result<(), NwgError> {
+        use winapi::um::commctrl::{MCM_SETRANGE, GDTR_MIN, GDTR_MAX};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        r[0].validate()?;
+        r[1].validate()?;
+        if r[0] > r[1] {
+            return Err(NwgError::date_time(format!("The range minimum ({}) is after the maximum ({})", r[0].to_iso8601(), r[1].to_iso8601())));
+        }
+
+        let values = [to_systemtime(&r[0]), to_systemtime(&r[1])];
+        match wh::send_message(handle, MCM_SETRANGE, GDTR_MIN | GDTR_MAX, &values as *const [SYSTEMTIME; 2] as LPARAM) {
+            0 => Err(NwgError::date_time("The MonthCalendar rejected the range")),
+            _ => Ok(())
+        }
+    }
+
+    /// Return the first and the last day of the months entirely displayed by the calendar
+    pub fn visible_range(&self) -> [DatePickerValue; 2] {
+        use winapi::um::commctrl::{MCM_GETMONTHRANGE, GMR_VISIBLE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut range: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        wh::send_message(handle, MCM_GETMONTHRANGE, GMR_VISIBLE as WPARAM, &mut range as *mut [SYSTEMTIME; 2] as LPARAM);
+
+        [date_of(&range[0]), date_of(&range[1])]
+    }
+
+    /// Return the number of months displayed by the calendar
+    pub fn visible_months(&self) -> u32 {
+        use winapi::um::commctrl::MCM_GETCALENDARCOUNT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, MCM_GETCALENDARCOUNT, 0, 0) as u32
+    }
+
+    /**
+        Return the size the calendar needs to display `columns` by `rows` months.
+        Use `set_size` to apply it. The size depends on the font and on the flags of the control.
+    */
+    pub fn size_for_months(&self, columns: u32, rows: u32) -> (u32, u32) {
+        use winapi::um::commctrl::{MCM_GETMINREQRECT, MCM_SIZERECTTOMIN};
+        use crate::win32::high_dpi;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut rect: RECT = unsafe { mem::zeroed() };
+        wh::send_message(handle, MCM_GETMINREQRECT, 0, &mut rect as *mut RECT as LPARAM);
+
+        // The months are separated by a gap that is not part of the minimum size of a month.
+        // The rectangle is made larger than needed, and `MCM_SIZERECTTOMIN` shrinks it to the months that fit.
+        let (columns, rows) = (columns.max(1) as i32, rows.max(1) as i32);
+        let (width, height) = (rect.right - rect.left, rect.bottom - rect.top);
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: width * columns + (columns - 1) * width / 8,
+            bottom: height * rows + (rows - 1) * height / 8,
+        };
+
+        wh::send_message(handle, MCM_SIZERECTTOMIN, 0, &mut rect as *mut RECT as LPARAM);
+
+        let (width, height) = unsafe { high_dpi::physical_to_logical(rect.right - rect.left, rect.bottom - rect.top) };
+        (width as u32, height as u32)
+    }
+
+    /// Return the current view of the calendar
+    pub fn view(&self) -> MonthCalendarView {
+        use winapi::um::commctrl::{MCM_GETCURRENTVIEW, MCMV_YEAR, MCMV_DECADE, MCMV_CENTURY};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        match wh::send_message(handle, MCM_GETCURRENTVIEW, 0, 0) as u32 {
+            MCMV_YEAR => MonthCalendarView::Year,
+            MCMV_DECADE => MonthCalendarView::Decade,
+            MCMV_CENTURY => MonthCalendarView::Century,
+            _ => MonthCalendarView::Month
+        }
+    }
+
+    /// Sets the view of the calendar. This raises a `OnCalendarViewChange` event.
+    pub fn set_view(&self, view: MonthCalendarView) {
+        use winapi::um::commctrl::{MCM_SETCURRENTVIEW, MCMV_MONTH, MCMV_YEAR, MCMV_DECADE, MCMV_CENTURY};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let view = match view {
+            MonthCalendarView::Month => MCMV_MONTH,
+            MonthCalendarView::Year => MCMV_YEAR,
+            MonthCalendarView::Decade => MCMV_DECADE,
+            MonthCalendarView::Century => MCMV_CENTURY,
+        };
+
+        wh::send_message(handle, MCM_SETCURRENTVIEW, 0, view as LPARAM);
+    }
+
+    /**
+        Sets the callback that selects the days displayed in bold. The callback receives every day of the displayed months.
+
+        The calendar must have the `BOLD_DAYS` flag, otherwise the callback is never called.
+        The calendar calls the callback when it displays new months. Use `refresh_bold_days` if the callback now returns other values.
+    */
+    pub fn set_bold_days<F: Fn(&DatePickerValue) -> bool + 'static>(&self, callback: F) {
+        self.set_boxed_bold_days(Box::new(callback));
+    }
+
+    /// Remove the bold days callback. All the days are displayed normally.
+    pub fn reset_bold_days(&self) {
+        *self.bold_days.callback.borrow_mut() = None;
+        self.refresh_bold_days();
+    }
+
+    /// Ask the bold days callback which of the displayed days should be bold
+    pub fn refresh_bold_days(&self) {
+        use winapi::um::commctrl::{MCM_GETMONTHRANGE, MCM_SETDAYSTATE, GMR_DAYSTATE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if wh::get_style(handle) & MCS_DAYSTATE != MCS_DAYSTATE {
+            return;
+        }
+
+        // Includes the partially displayed months
+        let mut range: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        let count = wh::send_message(handle, MCM_GETMONTHRANGE, GMR_DAYSTATE as WPARAM, &mut range as *mut [SYSTEMTIME; 2] as LPARAM);
+
+        self.bold_days.compute(&range[0], count as usize);
+
+        let states = self.bold_days.states.borrow();
+        wh::send_message(handle, MCM_SETDAYSTATE, states.len() as WPARAM, states.as_ptr() as LPARAM);
+    }
+
+    /// Return the font of the control
+    pub fn font(&self) -> Option<Font> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let font_handle = wh::get_window_font(handle);
+        if font_handle.is_null() {
+            None
+        } else {
+            Some(Font { handle: font_handle })
+        }
+    }
+
+    /// Sets the font of the control
+    pub fn set_font(&self, font: Option<&Font>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_font(handle, font.map(|f| f.handle), true); }
+    }
+
+    /// Return true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Sets the keyboard focus on the calendar.
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_focus(handle); }
+    }
+
+    /// Return true if the control user can interact with the control, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Return true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Return the size of the calendar in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the calendar in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Return the position of the calendar in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the calendar in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        winapi::um::commctrl::MONTHCAL_CLASS
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | WS_TABSTOP
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        use winapi::um::winuser::WS_CHILD;
+
+        WS_CHILD
+    }
+
+    /// Checks that `date` is valid and inside the range of the calendar. Return the date without the time fields.
+    fn check_date(&self, handle: HWND, date: DatePickerValue) -> Result<DatePickerValue, NwgError> {
+        let date = DatePickerValue::new(date.year, date.month, date.day);
+        date.validate()?;
+
+        let (min, max) = unsafe { get_range(handle) };
+        if min.map(|min| date < min).unwrap_or(false) || max.map(|max| date > max).unwrap_or(false) {
+            return Err(NwgError::date_time(format!("{} is outside of the MonthCalendar range", date.to_iso8601())));
+        }
+
+        Ok(date)
+    }
+
+    fn set_boxed_bold_days(&self, callback: BoldDaysCallback) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        *self.bold_days.callback.borrow_mut() = Some(callback);
+
+        if self.handler0.borrow().is_none() {
+            self.bind_parent_handler(handle);
+        }
+
+        self.refresh_bold_days();
+    }
+
+    /// Answers the `MCN_GETDAYSTATE` notifications the calendar sends to its parent when it displays new months
+    fn bind_parent_handler(&self, handle: HWND) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::WM_NOTIFY;
+        use winapi::um::commctrl::{MCN_GETDAYSTATE, NMDAYSTATE};
+        use winapi::shared::basetsd::UINT_PTR;
+        use winapi::um::winuser::NMHDR;
+
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let bold_days = self.bold_days.clone();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as UINT_PTR, move |_hwnd, msg, _w, l| {
+            if msg != WM_NOTIFY {
+                return None;
+            }
+
+            let header = unsafe { &*(l as *const NMHDR) };
+            if header.hwndFrom != handle || header.code != MCN_GETDAYSTATE {
+                return None;
+            }
+
+            let info = unsafe { &mut *(l as *mut NMDAYSTATE) };
+            bold_days.compute(&info.stStart, info.cDayState as usize);
+
+            // The calendar reads the states once the handler returned, so the pointer must target the shared buffer
+            info.prgDayState = bold_days.states.borrow_mut().as_mut_ptr();
+
+            Some(0)
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+}
+
+impl PartialEq for MonthCalendar {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Drop for MonthCalendar {
+    fn drop(&mut self) {
+        use crate::unbind_raw_event_handler;
+
+        if let Some(h) = self.handler0.borrow().as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        self.handle.destroy();
+    }
+}
+
+pub struct MonthCalendarBuilder<'a> {
+    size: (i32, i32),
+    position: (i32, i32),
+    flags: Option<MonthCalendarFlags>,
+    font: Option<&'a Font>,
+    focus: bool,
+    parent: Option<ControlHandle>,
+    months: Option<(u32, u32)>,
+    selection: Option<DatePickerValue>,
+    selection_range: Option<[DatePickerValue; 2]>,
+    max_selection_count: Option<u32>,
+    range: Option<[DatePickerValue; 2]>,
+    bold_days: Option<BoldDaysCallback>
+}
+
+impl<'a> MonthCalendarBuilder<'a> {
+
+    pub fn flags(mut self, flags: MonthCalendarFlags) -> MonthCalendarBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> MonthCalendarBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> MonthCalendarBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn font(mut self, font: Option<&'a Font>) -> MonthCalendarBuilder<'a> {
+        self.font = font;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> MonthCalendarBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn months(mut self, months: (u32, u32)) -> MonthCalendarBuilder<'a> {
+        self.months = Some(months);
+        self
+    }
+
+    pub fn selection(mut self, selection: Option<DatePickerValue>) -> MonthCalendarBuilder<'a> {
+        self.selection = selection;
+        self
+    }
+
+    pub fn selection_range(mut self, selection: Option<[DatePickerValue; 2]>) -> MonthCalendarBuilder<'a> {
+        self.selection_range = selection;
+        self
+    }
+
+    pub fn max_selection_count(mut self, count: u32) -> MonthCalendarBuilder<'a> {
+        self.max_selection_count = Some(count);
+        self
+    }
+
+    pub fn range(mut self, range: Option<[DatePickerValue; 2]>) -> MonthCalendarBuilder<'a> {
+        self.range = range;
+        self
+    }
+
+    pub fn bold_days<F: Fn(&DatePickerValue) -> bool + 'static>(mut self, callback: F) -> MonthCalendarBuilder<'a> {
+        self.bold_days = Some(Box::new(callback));
+        self
+    }
+
+    pub fn focus(mut self, focus: bool) -> MonthCalendarBuilder<'a> {
+        self.focus = focus;
+        self
+    }
+
+    pub fn build(self, out: &mut MonthCalendar) -> Result<(), NwgError> {
+        let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+        if self.selection_range.is_some() {
+            flags |= MCS_MULTISELECT;
+        }
+
+        if self.bold_days.is_some() {
+            flags |= MCS_DAYSTATE;
+        }
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("MonthCalendar"))
+        }?;
+
+        *out = MonthCalendar::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        if self.font.is_some() {
+            out.set_font(self.font);
+        } else {
+            out.set_font(Font::global_default().as_ref());
+        }
+
+        if let Some((columns, rows)) = self.months {
+            let (width, height) = out.size_for_months(columns, rows);
+            out.set_size(width, height);
+        }
+
+        if let Some(range) = self.range {
+            out.set_range(&range)?;
+        }
+
+        if let Some(count) = self.max_selection_count {
+            out.set_max_selection_count(count);
+        }
+
+        if let Some(selection) = self.selection {
+            out.set_selection(selection)?;
+        }
+
+        if let Some(selection) = self.selection_range {
+            out.set_selection_range(&selection)?;
+        }
+
+        if let Some(callback) = self.bold_days {
+            out.set_boxed_bold_days(callback);
+        }
+
+        if self.focus {
+            out.set_focus();
+        }
+
+        Ok(())
+    }
+
+}
+
+/// Returns the date of a SYSTEMTIME. The calendar leaves the time fields undefined.
+fn date_of(value: &SYSTEMTIME) -> DatePickerValue {
+    let value = from_systemtime(value);
+    DatePickerValue::new(value.year, value.month, value.day)
+}
+
+/// Returns the minimum and the maximum of the calendar, if they are set
+unsafe fn get_range(handle: HWND) -> (Option<DatePickerValue>, Option<DatePickerValue>) {
+    use winapi::um::commctrl::{MCM_GETRANGE, GDTR_MIN, GDTR_MAX};
+
+    let mut tr: [SYSTEMTIME; 2] = mem::zeroed();
+    let limits = wh::send_message(handle, MCM_GETRANGE, 0, &mut tr as *mut [SYSTEMTIME; 2] as LPARAM) as WPARAM;
+
+    let min = match limits & GDTR_MIN == GDTR_MIN { true => Some(date_of(&tr[0])), false => None };
+    let max = match limits & GDTR_MAX == GDTR_MAX { true => Some(date_of(&tr[1])), false => None };
+
+    (min, max)
+}
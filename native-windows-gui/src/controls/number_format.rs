@@ -0,0 +1,191 @@
+/*!
+    Formatting and parsing of the value of a `NumberSelect`. See `NumberSelect::set_format`.
+*/
+
+
+/**
+    How the value of a `NumberSelect` is displayed. With the `winnls` feature, `NumberFormat::from_locale` uses the separators of a locale.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let weight = nwg::NumberFormat {
+        thousand_separator: ",".to_string(),
+        suffix: " kg".to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(weight.format_float(12345.678, 1), "12,345.7 kg");
+    assert_eq!(weight.parse_float("12,345.7 kg"), Some(12345.7));
+    ```
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Separates the integer part from the decimals. `.` by default.
+    pub decimal_separator: String,
+
+    /// Separates the groups of digits of the integer part. Empty (no separator) by default.
+    pub thousand_separator: String,
+
+    /// The size of the groups of digits, from the right, in the format of `Locale::digit_grouping`.
+    /// `3;0` (the default) repeats groups of 3 digits, `3;2;0` is 3 digits then groups of 2, and `3` only separates the last 3 digits.
+    pub grouping: String,
+
+    /// Displayed before the number (ex: "$")
+    pub prefix: String,
+
+    /// Displayed after the number (ex: " kg")
+    pub suffix: String,
+}
+
+impl Default for NumberFormat {
+    fn default() -> NumberFormat {
+        NumberFormat {
+            decimal_separator: ".".to_string(),
+            thousand_separator: String::new(),
+            grouping: "3;0".to_string(),
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+}
+
+impl NumberFormat {
+
+    /// Uses the decimal separator, the thousand separator and the digit grouping of `locale`. Requires the `winnls` feature.
+    #[cfg(feature = "winnls")]
+    pub fn from_locale(locale: &crate::Locale) -> NumberFormat {
+        NumberFormat {
+            decimal_separator: locale.decimal_separator(),
+            thousand_separator: locale.thousand_separator(),
+            grouping: locale.digit_grouping(),
+            ..Default::default()
+        }
+    }
+
+    pub fn format_int(&self, value: i64) -> String {
+        let digits = (value as i128).abs().to_string();
+        self.compose(value < 0, &digits, None)
+    }
+
+    /// Formats `value` with `decimals` digits after the decimal separator
+    pub fn format_float(&self, value: f64, decimals: u8) -> String {
+        let text = format!("{:.*}", decimals as usize, value.abs());
+        let (integer, fraction) = match text.find('.') {
+            Some(i) => (&text[..i], Some(&text[i+1..])),
+            None => (&text[..], None)
+        };
+
+        // A value rounded to zero is displayed without the sign
+        let negative = value < 0.0 && text.bytes().any(|b| b != b'0' && b != b'.');
+        self.compose(negative, integer, fraction)
+    }
+
+    /**
+        Reads an integer typed by the user. The prefix, the suffix, the thousand separators and the whitespaces are ignored.
+        Returns `None` if the text is not an integer.
+    */
+    pub fn parse_int(&self, text: &str) -> Option<i64> {
+        self.normalize(text)?.parse().ok()
+    }
+
+    /**
+        Reads a number typed by the user. The prefix, the suffix, the thousand separators and the whitespaces are ignored.
+        `.` is also accepted as the decimal separator if it is not the thousand separator.
+        Returns `None` if the text is not a number.
+    */
+    pub fn parse_float(&self, text: &str) -> Option<f64> {
+        self.normalize(text)?.parse().ok()
+    }
+
+    fn compose(&self, negative: bool, integer: &str, fraction: Option<&str>) -> String {
+        let mut text = self.prefix.clone();
+        if negative {
+            text.push('-');
+        }
+
+        text.push_str(&self.group(integer));
+
+        if let Some(fraction) = fraction {
+            text.push_str(&self.decimal_separator);
+            text.push_str(fraction);
+        }
+
+        text.push_str(&self.suffix);
+        text
+    }
+
+    /// Inserts the thousand separator between the groups of `digits`
+    fn group(&self, digits: &str) -> String {
+        let mut sizes: Vec<usize> = self.grouping.split(';')
+            .filter_map(|size| size.trim().parse().ok())
+            .collect();
+
+        let repeat = sizes.last() == Some(&0);
+        sizes.retain(|&size| size > 0);
+
+        if self.thousand_separator.is_empty() || sizes.is_empty() {
+            return digits.to_string();
+        }
+
+        let mut groups = Vec::new();
+        let mut end = digits.len();
+        let mut index = 0;
+        while end > 0 {
+            let size = match sizes.get(index) {
+                Some(&size) => size,
+                None if repeat => sizes[sizes.len() - 1],
+                None => end
+            };
+
+            let start = end.saturating_sub(size);
+            groups.push(&digits[start..end]);
+            end = start;
+            index += 1;
+        }
+
+        groups.reverse();
+        groups.join(&self.thousand_separator)
+    }
+
+    /// Returns `text` in the format of the rust parser: without prefix, suffix and separators, and `.` as the decimal separator
+    fn normalize(&self, text: &str) -> Option<String> {
+        let mut text = text.trim();
+
+        let prefix = self.prefix.trim();
+        if !prefix.is_empty() && text.starts_with(prefix) {
+            text = text[prefix.len()..].trim_start();
+        }
+
+        let suffix = self.suffix.trim();
+        if !suffix.is_empty() && text.ends_with(suffix) {
+            text = text[..(text.len() - suffix.len())].trim_end();
+        }
+
+        let mut number = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            if !self.decimal_separator.is_empty() && rest.starts_with(self.decimal_separator.as_str()) {
+                number.push('.');
+                rest = &rest[self.decimal_separator.len()..];
+                continue;
+            }
+
+            if !self.thousand_separator.is_empty() && rest.starts_with(self.thousand_separator.as_str()) {
+                rest = &rest[self.thousand_separator.len()..];
+                continue;
+            }
+
+            match c {
+                '0'..='9' | '-' | '+' | '.' => number.push(c),
+                c if c.is_whitespace() => {},
+                _ => { return None; }
+            }
+
+            rest = &rest[c.len_utf8()..];
+        }
+
+        Some(number)
+    }
+
+}
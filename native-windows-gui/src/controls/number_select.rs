@@ -1,11 +1,13 @@
 use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP, WS_EX_CONTROLPARENT};
-use std::cell::RefCell;
+use winapi::shared::windef::HWND;
+use winapi::shared::minwindef::LPARAM;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::check_hwnd;
 use crate::{NwgError, Font, RawEventHandler, bind_raw_event_handler_inner, unbind_raw_event_handler};
-use super::{ControlBase, ControlHandle, TextInput, Button, ButtonFlags, TextInputFlags};
+use super::{ControlBase, ControlHandle, TextInput, Button, ButtonFlags, TextInputFlags, NumberFormat};
 
 const NOT_BOUND: &'static str = "UpDown is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: UpDown handle is not HWND!";
@@ -28,6 +30,33 @@ bitflags! {
     }
 }
 
+/// The value of a `NumberSelect`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NumberSelectValue {
+    Int(i64),
+    Float(f64),
+}
+
+impl NumberSelectValue {
+
+    /// Returns the value as an integer. Float values are rounded.
+    pub fn as_int(&self) -> i64 {
+        match *self {
+            NumberSelectValue::Int(v) => v,
+            NumberSelectValue::Float(v) => v.round() as i64,
+        }
+    }
+
+    /// Returns the value as a float
+    pub fn as_float(&self) -> f64 {
+        match *self {
+            NumberSelectValue::Int(v) => v as f64,
+            NumberSelectValue::Float(v) => v,
+        }
+    }
+
+}
+
 #[derive(Copy, Clone)]
 enum NumberSelectData {
     Int { value: i64, step: i64, max: i64, min: i64 },
@@ -36,17 +65,52 @@ enum NumberSelectData {
 
 impl NumberSelectData {
 
-    pub fn formatted_value(&self) -> String {
+    pub fn formatted_value(&self, format: &NumberFormat) -> String {
         match self {
-            NumberSelectData::Int{ value, ..} => format!("{}", value),
-            NumberSelectData::Float{ value, decimals, ..} => format!("{:.*}", *decimals as usize, value),
+            NumberSelectData::Int{ value, ..} => format.format_int(*value),
+            NumberSelectData::Float{ value, decimals, ..} => format.format_float(*value, *decimals),
+        }
+    }
+
+    pub fn value(&self) -> NumberSelectValue {
+        match *self {
+            NumberSelectData::Int{ value, ..} => NumberSelectValue::Int(value),
+            NumberSelectData::Float{ value, ..} => NumberSelectValue::Float(value),
+        }
+    }
+
+    /// Sets the value, clamped between min and max
+    pub fn set_value(&mut self, v: NumberSelectValue) {
+        match self {
+            NumberSelectData::Int{ value, min, max, ..} => {
+                *value = i64::min(i64::max(v.as_int(), *min), *max);
+            },
+            NumberSelectData::Float{ value, min, max, ..} => {
+                *value = f64::min(f64::max(v.as_float(), *min), *max);
+            }
+        }
+    }
+
+    /// Sets the value typed by the user. Returns `false` if the text is not a number.
+    pub fn parse(&mut self, text: &str, format: &NumberFormat) -> bool {
+        let value = match self {
+            NumberSelectData::Int{ .. } => format.parse_int(text).map(NumberSelectValue::Int),
+            NumberSelectData::Float{ .. } => format.parse_float(text).filter(|v| v.is_finite()).map(NumberSelectValue::Float),
+        };
+
+        match value {
+            Some(value) => {
+                self.set_value(value);
+                true
+            },
+            None => false
         }
     }
 
     pub fn decrease(&mut self) {
         match self {
             NumberSelectData::Int{ value, step, min, ..} => {
-                *value -= *step;
+                *value = value.saturating_sub(*step);
                 *value = i64::max(*value, *min);
             },
             NumberSelectData::Float{ value, step, min, ..} => {
@@ -59,7 +123,7 @@ impl NumberSelectData {
     pub fn increase(&mut self) {
         match self {
             NumberSelectData::Int{ value, step, max, ..} => {
-                *value += *step;
+                *value = value.saturating_add(*step);
                 *value = i64::min(*value, *max);
             },
             NumberSelectData::Float{ value, step, max, ..} => {
@@ -71,6 +135,80 @@ impl NumberSelectData {
 
 }
 
+/// The state of a number select, shared with its event handlers
+#[derive(Default)]
+struct NumberSelectState {
+    data: RefCell<NumberSelectData>,
+    format: RefCell<NumberFormat>,
+
+    /// The mouse wheel rotation that did not reach a full notch yet
+    wheel_delta: Cell<i32>,
+}
+
+impl NumberSelectState {
+
+    /// Changes the value with `update` and displays it in `edit`. Raises `OnNumberSelectChanged` on `control` if the value changed.
+    fn update<F: FnOnce(&mut NumberSelectData, &NumberFormat)>(&self, control: HWND, edit: HWND, update: F) {
+        // The borrows must be released before the event handlers are called
+        let (old, new, text) = {
+            let mut data = self.data.borrow_mut();
+            let format = self.format.borrow();
+            let old = data.value();
+            update(&mut data, &format);
+            (old, data.value(), data.formatted_value(&format))
+        };
+
+        unsafe { wh::set_window_text(edit, &text); }
+
+        if old != new {
+            wh::send_message(control, wh::NWG_NUMBER_SELECT_CHANGED, 0, &new as *const NumberSelectValue as LPARAM);
+        }
+    }
+
+    /// Moves the value by `steps` steps
+    fn step(&self, control: HWND, edit: HWND, steps: i32) {
+        let text = unsafe { wh::get_window_text(edit) };
+        self.update(control, edit, |data, format| {
+            data.parse(&text, format);
+            for _ in 0..steps.abs() {
+                match steps > 0 {
+                    true => data.increase(),
+                    false => data.decrease()
+                }
+            }
+        });
+    }
+
+    /// Moves the value by the number of notches of a mouse wheel rotation. High resolution wheels send fractions of a notch,
+    /// so the rotation is accumulated until it reaches a full notch.
+    fn wheel(&self, control: HWND, edit: HWND, w: usize) {
+        use winapi::um::winuser::{GET_WHEEL_DELTA_WPARAM, WHEEL_DELTA};
+
+        let mut delta = self.wheel_delta.get();
+
+        // The remainder is dropped when the wheel changes direction
+        let rotation = GET_WHEEL_DELTA_WPARAM(w) as i32;
+        if delta.signum() * rotation.signum() < 0 {
+            delta = 0;
+        }
+
+        delta += rotation;
+        let steps = delta / WHEEL_DELTA as i32;
+        self.wheel_delta.set(delta % WHEEL_DELTA as i32);
+
+        if steps != 0 {
+            self.step(control, edit, steps);
+        }
+    }
+
+    /// Reads the value typed by the user. If the text is not a number, the previous value is displayed again.
+    fn commit_text(&self, control: HWND, edit: HWND) {
+        let text = unsafe { wh::get_window_text(edit) };
+        self.update(control, edit, |data, format| { data.parse(&text, format); });
+    }
+
+}
+
 impl Default for NumberSelectData {
     fn default() -> NumberSelectData {
         NumberSelectData::Int { 
@@ -86,6 +224,9 @@ impl Default for NumberSelectData {
 A NumberSelect control is a pair of arrow buttons that the user can click to increment or decrement a value.
 NumberSelect is implemented as a custom control because the one provided by winapi really sucks.
 
+The value can also be typed, or changed with the mouse wheel and the keyboard: up and down move the value by one step,
+page up and page down by ten steps. The typed text is read when the user presses enter or when the control loses the focus.
+
 Requires the `number-select` feature. 
 
 **Builder parameters:**
@@ -96,8 +237,10 @@ Requires the `number-select` feature.
   * `enabled`:  If the number select can be used by the user. It also has a grayed out look if disabled.
   * `flags`:    A combination of the NumberSelectFlags values.
   * `font`:     The font used for the number select text
+  * `format`:   How the value is displayed. See `NumberFormat`.
 
 **Control events:**
+  * `OnNumberSelectChanged`: When the user changes the value
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse mouse event
 
@@ -115,11 +258,12 @@ fn build_number_select(num_select: &mut nwg::NumberSelect, window: &nwg::Window,
 #[derive(Default)]
 pub struct NumberSelect {
     pub handle: ControlHandle,
-    data: Rc<RefCell<NumberSelectData>>,
+    state: Rc<NumberSelectState>,
     edit: TextInput,
     btn_up: Button,
     btn_down: Button,
-    handler: Option<RawEventHandler>
+    handler0: Option<RawEventHandler>,
+    handler1: Option<RawEventHandler>,
 }
 
 impl NumberSelect {
@@ -129,6 +273,7 @@ impl NumberSelect {
             size: (100, 25),
             position: (0, 0),
             data: NumberSelectData::default(),
+            format: NumberFormat::default(),
             enabled: true,
             flags: None,
             font: None,
@@ -136,6 +281,40 @@ impl NumberSelect {
         }
     }
 
+    /// Returns the value of the control. The text typed by the user is only read when it is validated (see `NumberSelect`).
+    pub fn value(&self) -> NumberSelectValue {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.data.borrow().value()
+    }
+
+    /// Sets the value of the control, clamped between its min and max. A float value is rounded in an integer number select.
+    /// This does not raise `OnNumberSelectChanged`.
+    pub fn set_value(&self, value: NumberSelectValue) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let text = {
+            let mut data = self.state.data.borrow_mut();
+            data.set_value(value);
+            data.formatted_value(&self.state.format.borrow())
+        };
+
+        self.edit.set_text(&text);
+    }
+
+    /// Returns how the value is displayed
+    pub fn format(&self) -> NumberFormat {
+        self.state.format.borrow().clone()
+    }
+
+    /// Sets how the value is displayed and how the typed text is read
+    pub fn set_format(&self, format: NumberFormat) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let text = self.state.data.borrow().formatted_value(&format);
+        *self.state.format.borrow_mut() = format;
+        self.edit.set_text(&text);
+    }
+
     /// Returns the font of the control
     pub fn font(&self) -> Option<Font> {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
@@ -235,8 +414,10 @@ impl NumberSelect {
 impl Drop for NumberSelect {
 
     fn drop(&mut self) {
-        if let Some(h) = self.handler.as_ref() {
-            drop(unbind_raw_event_handler(h));
+        for handler in [&self.handler0, &self.handler1].iter() {
+            if let Some(h) = handler.as_ref() {
+                drop(unbind_raw_event_handler(h));
+            }
         }
 
         self.handle.destroy();
@@ -248,6 +429,7 @@ pub struct NumberSelectBuilder<'a> {
     size: (i32, i32),
     position: (i32, i32),
     data: NumberSelectData,
+    format: NumberFormat,
     enabled: bool,
     flags: Option<NumberSelectFlags>,
     font: Option<&'a Font>,
@@ -355,6 +537,11 @@ impl<'a> NumberSelectBuilder<'a> {
         self
     }
 
+    pub fn format(mut self, format: NumberFormat) -> NumberSelectBuilder<'a> {
+        self.format = format;
+        self
+    }
+
     pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> NumberSelectBuilder<'a> {
         self.parent = Some(p.into());
         self
@@ -375,12 +562,15 @@ impl<'a> NumberSelectBuilder<'a> {
 
         let (w, h) = self.size;
 
-        if out.handler.is_some() {
-            unbind_raw_event_handler(out.handler.as_ref().unwrap())?;
+        for handler in [&out.handler0, &out.handler1].iter() {
+            if let Some(h) = handler.as_ref() {
+                unbind_raw_event_handler(h)?;
+            }
         }
 
         *out = NumberSelect::default();
-        *out.data.borrow_mut() = self.data;
+        *out.state.data.borrow_mut() = self.data;
+        *out.state.format.borrow_mut() = self.format.clone();
         
         out.handle = ControlBase::build_hwnd()
             .class_name(out.class_name())
@@ -393,7 +583,7 @@ impl<'a> NumberSelectBuilder<'a> {
             .build()?;
 
         TextInput::builder()
-            .text(&self.data.formatted_value())
+            .text(&self.data.formatted_value(&self.format))
             .size((w-19, h))
             .parent(&out.handle)
             .flags(text_flags)
@@ -427,14 +617,14 @@ impl<'a> NumberSelectBuilder<'a> {
             out.edit.set_font(font_ref);
         }
 
-        let handler_data = out.data.clone();
+        let control = out.handle.hwnd().unwrap();
+        let edit = out.edit.handle.hwnd().unwrap();
         let plus_button = out.btn_up.handle.clone();
         let minus_button = out.btn_down.handle.clone();
-        let text_handle = out.edit.handle.clone();
 
+        let state = out.state.clone();
         let handler = bind_raw_event_handler_inner(&out.handle, 0x4545, move |_hwnd, msg, w, l| {
-            use winapi::shared::windef::HWND;
-            use winapi::um::winuser::{WM_COMMAND, BN_CLICKED};
+            use winapi::um::winuser::{WM_COMMAND, WM_MOUSEWHEEL, BN_CLICKED, EN_KILLFOCUS};
             use winapi::shared::minwindef::HIWORD;
             
             match msg {
@@ -442,28 +632,57 @@ impl<'a> NumberSelectBuilder<'a> {
                     let handle = ControlHandle::Hwnd(l as HWND);
                     let message = HIWORD(w as u32) as u16;
                     if message == BN_CLICKED && handle == plus_button {
-                        let mut data = handler_data.borrow_mut();
-                        data.increase();
-
-                        let handle = text_handle.hwnd().unwrap();
-                        let text = data.formatted_value();
-                        unsafe { wh::set_window_text(handle, &text); }
+                        state.step(control, edit, 1);
                     } else if message == BN_CLICKED && handle == minus_button {
-                        let mut data = handler_data.borrow_mut();
-                        data.decrease();
-
-                        let handle = text_handle.hwnd().unwrap();
-                        let text = data.formatted_value();
-                        unsafe { wh::set_window_text(handle, &text); }
+                        state.step(control, edit, -1);
+                    } else if message == EN_KILLFOCUS && l as HWND == edit {
+                        state.commit_text(control, edit);
                     }
                 },
-                
+                WM_MOUSEWHEEL => {
+                    state.wheel(control, edit, w);
+                    return Some(0);
+                },
                 _ => {}
             }
             None
         });
 
-        out.handler = Some(handler.unwrap());
+        out.handler0 = Some(handler.unwrap());
+
+        // The keyboard and the mouse wheel messages are sent to the text input when it has the focus
+        let state = out.state.clone();
+        let handler = bind_raw_event_handler_inner(&out.edit.handle, 0x4546, move |_hwnd, msg, w, _l| {
+            use winapi::um::winuser::{WM_KEYDOWN, WM_CHAR, WM_MOUSEWHEEL, VK_UP, VK_DOWN, VK_PRIOR, VK_NEXT, VK_RETURN};
+
+            match msg {
+                WM_KEYDOWN => {
+                    let steps = match w as i32 {
+                        VK_UP => 1,
+                        VK_DOWN => -1,
+                        VK_PRIOR => 10,
+                        VK_NEXT => -10,
+                        VK_RETURN => {
+                            state.commit_text(control, edit);
+                            return Some(0);
+                        },
+                        _ => { return None; }
+                    };
+
+                    state.step(control, edit, steps);
+                    Some(0)
+                },
+                // Prevents the beep of the text input when enter is pressed
+                WM_CHAR if w == 0x0D => Some(0),
+                WM_MOUSEWHEEL => {
+                    state.wheel(control, edit, w);
+                    Some(0)
+                },
+                _ => None
+            }
+        });
+
+        out.handler1 = Some(handler.unwrap());
 
         Ok(())
     }
@@ -1,10 +1,19 @@
-use winapi::shared::minwindef::{WPARAM, LPARAM};
+use winapi::shared::minwindef::{UINT, DWORD, WPARAM, LPARAM, LPBYTE};
+use winapi::shared::basetsd::DWORD_PTR;
+use winapi::shared::ntdef::LONG;
+use winapi::shared::windef::{HWND, COLORREF};
 use winapi::um::winuser::{ES_AUTOVSCROLL, ES_AUTOHSCROLL, WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
+use winapi::um::wingdi::{RGB, GetRValue, GetGValue, GetBValue};
 use crate::win32::window_helper as wh;
-use crate::win32::base_helper::check_hwnd;
-use crate::{Font, NwgError};
-use super::{ControlBase, ControlHandle};
+use crate::win32::base_helper::{check_hwnd, to_utf16, from_utf16};
+use crate::win32::richedit::*;
+use crate::win32::edit_helper;
+use crate::{Font, NwgError, RawEventHandler};
+use super::{ControlBase, ControlHandle, FindOptions, find_text, Highlighter, HighlightCache, HighlightStyle, StyleRun};
+use std::cell::RefCell;
 use std::ops::Range;
+use std::rc::Rc;
+use std::{mem, ptr, slice};
 
 const NOT_BOUND: &'static str = "RichTextBox is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: RichTextBox handle is not HWND!";
@@ -30,6 +39,92 @@ bitflags! {
 }
 
 
+/**
+    The character format of the text of a rich text box. See `RichTextBox::set_char_format`.
+
+    When the format is set, the `None` values are not changed. When the format is read, the values that
+    are not the same for all the selected text are `None`.
+
+    ```rust
+    use native_windows_gui as nwg;
+    let title = nwg::CharFormat {
+        bold: Some(true),
+        font_size: Some(16.0),
+        text_color: Some([0, 0, 128]),
+        ..Default::default()
+    };
+    ```
+*/
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CharFormat {
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub strikeout: Option<bool>,
+
+    /// The color of the text. When the format is read, `None` is also returned if the text uses the default color.
+    pub text_color: Option<[u8; 3]>,
+
+    /// The color behind the text. When the format is read, `None` is also returned if the text uses the default color.
+    pub background_color: Option<[u8; 3]>,
+
+    /// The name of the font family (ex: "Segoe UI"). Truncated to 31 characters.
+    pub font_face: Option<String>,
+
+    /// The size of the font in points
+    pub font_size: Option<f32>,
+}
+
+/// The alignment of a paragraph
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParaAlignment {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+/// The bullets or the numbers displayed before the lines of a paragraph
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParaNumbering {
+    None,
+    Bullet,
+    Arabic,
+    LowerLetter,
+    UpperLetter,
+    LowerRoman,
+    UpperRoman,
+}
+
+/**
+    The format of the paragraphs of a rich text box. See `RichTextBox::set_para_format`.
+    Like `CharFormat`, the `None` values are not changed when the format is set, and are returned for the values that are not the same
+    for all the selected paragraphs.
+
+    The distances are in twips (1/1440 of an inch, or 1/20 of a point).
+*/
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParaFormat {
+    pub alignment: Option<ParaAlignment>,
+    pub numbering: Option<ParaNumbering>,
+
+    /// The indentation of the first line of the paragraph
+    pub start_indent: Option<i32>,
+
+    /// The indentation of the right side of the paragraph
+    pub right_indent: Option<i32>,
+
+    /// The indentation of the second line and the following lines, relative to the first line
+    pub offset: Option<i32>,
+
+    /// The space above the paragraph
+    pub space_before: Option<i32>,
+
+    /// The space below the paragraph
+    pub space_after: Option<i32>,
+}
+
+
 /**
 An edit control is a rectangular control window to permit the user to enter and edit text by typing on the keyboard
 This control allow multi line input. For a single line of text, use `TextInput`.
@@ -40,6 +135,9 @@ See: https://docs.microsoft.com/en-us/windows/win32/controls/about-rich-edit-con
 
 Note: Use `\r\n` to input a new line not just `\n`.
 
+Note: The character offsets used by the rich text box (selection, lines, find) are counted in UTF-16 code units,
+and a paragraph break counts as a single character even if `text` returns it as `\r\n`.
+
 **Builder parameters:**
   * `parent`:   **Required.** The text box parent container.
   * `text`:     The text box text.
@@ -50,6 +148,7 @@ Note: Use `\r\n` to input a new line not just `\n`.
   * `limit`:    The maximum number of character that can be inserted in the control
   * `readonly`: If the textbox should allow user input or not
   * `focus`:    The control receive focus after being created
+  * `highlighter`: A highlighter that formats the text while it is edited. See `set_highlighter`.
 
 **Control events:**
   * `OnMouseMove`:   Generic mouse mouse event
@@ -59,9 +158,40 @@ Note: Use `\r\n` to input a new line not just `\n`.
   * `OnKeyRelease`:  Generic key release event
 
 */
-#[derive(Default, PartialEq, Eq)]
+#[derive(Default)]
 pub struct RichTextBox {
-    pub handle: ControlHandle
+    pub handle: ControlHandle,
+    highlight: Rc<Highlight>,
+    handler0: RefCell<Option<RawEventHandler>>,
+}
+
+/// The highlighter of a rich text box, shared with the `EN_CHANGE` handler
+#[derive(Default)]
+struct Highlight {
+    highlighter: RefCell<Option<Box<dyn Highlighter>>>,
+    cache: RefCell<HighlightCache>,
+}
+
+impl Highlight {
+
+    /// Formats the lines that changed since the last update
+    fn apply(&self, handle: HWND) {
+        let highlighter = self.highlighter.borrow();
+        let highlighter = match highlighter.as_ref() {
+            Some(h) => h,
+            None => { return; }
+        };
+
+        // Formatting the text can send `EN_CHANGE` again
+        let mut cache = match self.cache.try_borrow_mut() {
+            Ok(cache) => cache,
+            Err(_) => { return; }
+        };
+
+        let runs = cache.update(&raw_text(handle), highlighter.as_ref());
+        format_runs(handle, &runs);
+    }
+
 }
 
 impl RichTextBox {
@@ -76,6 +206,7 @@ impl RichTextBox {
             readonly: false,
             focus: false,
             font: None,
+            highlighter: None,
             parent: None
         }
     }
@@ -157,6 +288,82 @@ impl RichTextBox {
         wh::send_message(handle, EM_SETSEL as u32, r.start as usize, r.end as isize);
     }
 
+    /// Replace the selected text with `text`. If no text is selected, `text` is inserted at the caret.
+    /// Unlike `set_text`, the change can be undone by the user.
+    pub fn replace_selection<'a>(&self, text: &'a str) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        edit_helper::replace_selection(handle, text);
+    }
+
+    /// Return the range of characters of the first occurence of `pattern` after `options.start`, or `None` if there is no match.
+    /// Use `set_selection` to select the result.
+    pub fn find<'a>(&self, pattern: &'a str, options: FindOptions) -> Option<Range<u32>> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        find_text(&raw_text(handle), pattern, options)
+    }
+
+    /// Return the number of lines in the rich text box
+    pub fn line_count(&self) -> u32 {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        edit_helper::line_count(handle)
+    }
+
+    /// Return the text of the line at `index` without the line break, or `None` if the line does not exist
+    pub fn line(&self, index: u32) -> Option<String> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        edit_helper::line(handle, index)
+    }
+
+    /// Return the index of the line that contains the character at `offset`
+    pub fn line_from_char(&self, offset: u32) -> u32 {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        edit_helper::line_from_char(handle, offset)
+    }
+
+    /// Return the offset of the first character of the line at `index`, or `None` if the line does not exist
+    pub fn char_from_line(&self, index: u32) -> Option<u32> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        edit_helper::char_from_line(handle, index)
+    }
+
+    /// Scroll the rich text box vertically so that the line at `index` is the first visible line
+    pub fn scroll_to_line(&self, index: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        edit_helper::scroll_to_line(handle, index);
+    }
+
+    /**
+        Highlight the text of the control with `highlighter`. Each time the text is edited, only the lines that changed
+        (see `Highlighter::invalidated`) are highlighted again.
+
+        The highlighter sets the bold, italic, underline and color format of all the text. The control is not redrawn while
+        the text is formatted, and the selection and the scroll position are kept.
+        Note that the format changes are also recorded by the undo history of the control.
+    */
+    pub fn set_highlighter<H: Highlighter + 'static>(&self, highlighter: H) {
+        self.set_boxed_highlighter(Box::new(highlighter));
+    }
+
+    /// Remove the highlighter of the control. The text is displayed with the default format again.
+    pub fn reset_highlighter(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if self.highlight.highlighter.borrow_mut().take().is_some() {
+            self.highlight.cache.borrow_mut().clear();
+
+            let text = raw_text(handle);
+            let length = text.encode_utf16().count() as u32;
+            format_runs(handle, &[StyleRun { range: 0..length, style: None }]);
+        }
+    }
+
+    /// Highlight all the text again. Use this if the rules of the highlighter changed, or if the text was formatted by `set_rtf` or `set_char_format`.
+    pub fn refresh_highlighting(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.highlight.cache.borrow_mut().clear();
+        self.highlight.apply(handle);
+    }
+
     /// Return the length of the user input in the control. This is better than test.len() as it
     /// does not allocate a string in memory
     pub fn len(&self) -> u32 {
@@ -184,6 +391,66 @@ impl RichTextBox {
         wh::send_message(handle, EM_SETREADONLY as u32, r as WPARAM, 0);
     }
 
+    /// Return the character format of the selected text.
+    /// If no text is selected, return the format of the text that would be inserted at the caret.
+    pub fn char_format(&self) -> CharFormat {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut raw: CHARFORMAT2W = unsafe { mem::zeroed() };
+        raw.cbSize = mem::size_of::<CHARFORMAT2W>() as UINT;
+        wh::send_message(handle, EM_GETCHARFORMAT, SCF_SELECTION as WPARAM, &mut raw as *mut CHARFORMAT2W as LPARAM);
+
+        CharFormat::from_raw(&raw)
+    }
+
+    /// Set the character format of the selected text.
+    /// If no text is selected, set the format of the text that will be inserted at the caret.
+    pub fn set_char_format(&self, fmt: &CharFormat) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut raw = fmt.to_raw();
+        wh::send_message(handle, EM_SETCHARFORMAT, SCF_SELECTION as WPARAM, &mut raw as *mut CHARFORMAT2W as LPARAM);
+    }
+
+    /// Return the format of the paragraphs in the selection
+    pub fn para_format(&self) -> ParaFormat {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut raw: PARAFORMAT2 = unsafe { mem::zeroed() };
+        raw.cbSize = mem::size_of::<PARAFORMAT2>() as UINT;
+        wh::send_message(handle, EM_GETPARAFORMAT, 0, &mut raw as *mut PARAFORMAT2 as LPARAM);
+
+        ParaFormat::from_raw(&raw)
+    }
+
+    /// Set the format of the paragraphs in the selection
+    pub fn set_para_format(&self, fmt: &ParaFormat) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut raw = fmt.to_raw();
+        wh::send_message(handle, EM_SETPARAFORMAT, 0, &mut raw as *mut PARAFORMAT2 as LPARAM);
+    }
+
+    /// Return the content of the control as a RTF document. See `rtf_to_text` and `tokenize_rtf` to read it.
+    pub fn rtf(&self) -> String {
+        self.stream_out(SF_RTF)
+    }
+
+    /// Return the selected text as a RTF document
+    pub fn selection_rtf(&self) -> String {
+        self.stream_out(SF_RTF | SFF_SELECTION)
+    }
+
+    /// Replace the content of the control with a RTF document. See `RtfWriter` to generate one.
+    pub fn set_rtf<'a>(&self, rtf: &'a str) -> Result<(), NwgError> {
+        self.stream_in(rtf, SF_RTF)
+    }
+
+    /// Replace the selected text with a RTF document. If no text is selected, the document is inserted at the caret.
+    pub fn set_selection_rtf<'a>(&self, rtf: &'a str) -> Result<(), NwgError> {
+        self.stream_in(rtf, SF_RTF | SFF_SELECTION)
+    }
+
     /// Remove all text from the textbox
     pub fn clear(&self) {
         self.set_text("");
@@ -267,6 +534,84 @@ impl RichTextBox {
         "RICHEDIT50W"
     }
 
+    fn set_boxed_highlighter(&self, highlighter: Box<dyn Highlighter>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        *self.highlight.highlighter.borrow_mut() = Some(highlighter);
+        self.highlight.cache.borrow_mut().clear();
+
+        if self.handler0.borrow().is_none() {
+            self.bind_parent_handler(handle);
+        }
+
+        self.highlight.apply(handle);
+    }
+
+    /// Highlights the text when the control sends `EN_CHANGE` to its parent
+    fn bind_parent_handler(&self, handle: HWND) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{WM_COMMAND, EN_CHANGE};
+        use winapi::shared::minwindef::HIWORD;
+        use winapi::shared::basetsd::UINT_PTR;
+
+        // Unlike the edit control, the rich edit control only sends the notifications in its event mask
+        let mask = wh::send_message(handle, EM_GETEVENTMASK, 0, 0) as DWORD;
+        wh::send_message(handle, EM_SETEVENTMASK, 0, (mask | ENM_CHANGE) as LPARAM);
+
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let highlight = self.highlight.clone();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as UINT_PTR, move |_hwnd, msg, w, l| {
+            if msg == WM_COMMAND && l as HWND == handle && HIWORD(w as u32) as u16 == EN_CHANGE {
+                highlight.apply(handle);
+            }
+
+            None
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+    fn stream_out(&self, format: UINT) -> String {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut stream = EDITSTREAM {
+            dwCookie: &mut buffer as *mut Vec<u8> as DWORD_PTR,
+            dwError: 0,
+            pfnCallback: Some(stream_out_callback),
+        };
+
+        wh::send_message(handle, EM_STREAMOUT, format as WPARAM, &mut stream as *mut EDITSTREAM as LPARAM);
+
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    fn stream_in(&self, rtf: &str, format: UINT) -> Result<(), NwgError> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        // The rich edit control loads invalid documents as plain text
+        if !rtf.starts_with("{\\rtf") {
+            return Err(NwgError::rtf("The document does not start with {\\rtf"));
+        }
+
+        let mut remaining = rtf.as_bytes();
+        let mut stream = EDITSTREAM {
+            dwCookie: &mut remaining as *mut &[u8] as DWORD_PTR,
+            dwError: 0,
+            pfnCallback: Some(stream_in_callback),
+        };
+
+        wh::send_message(handle, EM_STREAMIN, format as WPARAM, &mut stream as *mut EDITSTREAM as LPARAM);
+
+        // `EDITSTREAM` is packed, so the field is copied before it is used
+        let error = stream.dwError;
+        match error {
+            0 => Ok(()),
+            e => Err(NwgError::rtf(format!("The rich text box could not read the document (error {})", e)))
+        }
+    }
+
     /// Winapi base flags used during window creation
     pub fn flags(&self) -> u32 {
         WS_VISIBLE | ES_AUTOVSCROLL | ES_AUTOHSCROLL | WS_TABSTOP
@@ -281,8 +626,334 @@ impl RichTextBox {
 
 }
 
+/// Return the text as it is stored by the control. Unlike `text`, the paragraphs end with a single `\r`, so the
+/// character offsets match the ones used by the control.
+fn raw_text(handle: HWND) -> String {
+    let mut length = GETTEXTLENGTHEX { flags: GTL_NUMCHARS | GTL_PRECISE, codepage: CP_UNICODE };
+    let length = wh::send_message(handle, EM_GETTEXTLENGTHEX, &mut length as *mut GETTEXTLENGTHEX as WPARAM, 0);
+    if length <= 0 {
+        return String::new();
+    }
+
+    let mut buffer: Vec<u16> = vec![0; length as usize + 1];
+    let mut params = GETTEXTEX {
+        cb: (buffer.len() * mem::size_of::<u16>()) as DWORD,
+        flags: GT_DEFAULT,
+        codepage: CP_UNICODE,
+        lpDefaultChar: ptr::null(),
+        lpUsedDefChar: ptr::null_mut(),
+    };
+
+    let copied = wh::send_message(handle, EM_GETTEXTEX, &mut params as *mut GETTEXTEX as WPARAM, buffer.as_mut_ptr() as LPARAM);
+    String::from_utf16_lossy(&buffer[..(copied as usize).min(length as usize)])
+}
+
+/**
+    Formats the runs returned by a `HighlightCache`. The control is not redrawn until all the runs are formatted,
+    and the selection and the scroll position are restored after.
+*/
+fn format_runs(handle: HWND, runs: &[StyleRun]) {
+    use winapi::um::winuser::{WM_SETREDRAW, EM_GETSEL, EM_SETSEL, InvalidateRect};
+    use winapi::shared::windef::POINT;
+
+    if runs.is_empty() {
+        return;
+    }
+
+    let (mut start, mut end) = (0u32, 0u32);
+    wh::send_message(handle, EM_GETSEL as u32, &mut start as *mut u32 as WPARAM, &mut end as *mut u32 as LPARAM);
+
+    let mut scroll = POINT { x: 0, y: 0 };
+    wh::send_message(handle, EM_GETSCROLLPOS, 0, &mut scroll as *mut POINT as LPARAM);
+    wh::send_message(handle, WM_SETREDRAW, 0, 0);
+
+    // Highlighting only changes the format of the text, it must not be undone by the user
+    let document = text_document(handle);
+    if let Some(document) = document {
+        unsafe { (&*document).Undo(TOM_SUSPEND, ptr::null_mut()); }
+    }
+
+    for run in runs {
+        let mut format = highlight_format(run.style);
+        wh::send_message(handle, EM_SETSEL as u32, run.range.start as WPARAM, run.range.end as LPARAM);
+        wh::send_message(handle, EM_SETCHARFORMAT, SCF_SELECTION as WPARAM, &mut format as *mut CHARFORMAT2W as LPARAM);
+    }
+
+    wh::send_message(handle, EM_SETSEL as u32, start as WPARAM, end as LPARAM);
+    wh::send_message(handle, EM_SETSCROLLPOS, 0, &mut scroll as *mut POINT as LPARAM);
+    if let Some(document) = document {
+        unsafe {
+            (&*document).Undo(TOM_RESUME, ptr::null_mut());
+            (&*document).Release();
+        }
+    }
+
+    wh::send_message(handle, WM_SETREDRAW, 1, 0);
+
+    unsafe { InvalidateRect(handle, ptr::null(), 1); }
+}
+
+/// Returns the text object model document of a rich edit control. The caller must release it.
+fn text_document(handle: HWND) -> Option<*mut ITextDocument> {
+    use winapi::um::unknwnbase::IUnknown;
+    use winapi::shared::winerror::S_OK;
+    use winapi::Interface;
+
+    let mut ole: *mut IUnknown = ptr::null_mut();
+    if wh::send_message(handle, EM_GETOLEINTERFACE, 0, &mut ole as *mut *mut IUnknown as LPARAM) == 0 || ole.is_null() {
+        return None;
+    }
+
+    let mut document: *mut ITextDocument = ptr::null_mut();
+    unsafe {
+        let result = (&*ole).QueryInterface(&ITextDocument::uuidof(), &mut document as *mut *mut ITextDocument as _);
+        (&*ole).Release();
+
+        match result == S_OK && !document.is_null() {
+            true => Some(document),
+            false => None
+        }
+    }
+}
+
+/// Returns the format of a highlighted run. The text that is not highlighted uses the default format of the control.
+fn highlight_format(style: Option<HighlightStyle>) -> CHARFORMAT2W {
+    let style = style.unwrap_or_default();
+
+    let mut raw: CHARFORMAT2W = unsafe { mem::zeroed() };
+    raw.cbSize = mem::size_of::<CHARFORMAT2W>() as UINT;
+    raw.dwMask = CFM_BOLD | CFM_ITALIC | CFM_UNDERLINE | CFM_COLOR | CFM_BACKCOLOR;
+
+    let effects = [(style.bold, CFE_BOLD), (style.italic, CFE_ITALIC), (style.underline, CFE_UNDERLINE)];
+    for &(value, effect) in effects.iter() {
+        if value {
+            raw.dwEffects |= effect;
+        }
+    }
+
+    match style.text_color {
+        Some([r, g, b]) => { raw.crTextColor = RGB(r, g, b); },
+        None => { raw.dwEffects |= CFE_AUTOCOLOR; }
+    }
+
+    match style.background_color {
+        Some([r, g, b]) => { raw.crBackColor = RGB(r, g, b); },
+        None => { raw.dwEffects |= CFE_AUTOBACKCOLOR; }
+    }
+
+    raw
+}
+
+/// Appends the data written by the control to the `Vec<u8>` in `cookie`
+unsafe extern "system" fn stream_out_callback(cookie: DWORD_PTR, buffer: LPBYTE, count: LONG, written: *mut LONG) -> DWORD {
+    let out = &mut *(cookie as *mut Vec<u8>);
+    out.extend_from_slice(slice::from_raw_parts(buffer, count as usize));
+    *written = count;
+    0
+}
+
+/// Copies the start of the `&[u8]` in `cookie` to the control, and advances it
+unsafe extern "system" fn stream_in_callback(cookie: DWORD_PTR, buffer: LPBYTE, count: LONG, read: *mut LONG) -> DWORD {
+    let remaining = &mut *(cookie as *mut &[u8]);
+    let length = remaining.len().min(count as usize);
+    ptr::copy_nonoverlapping(remaining.as_ptr(), buffer, length);
+    *remaining = &remaining[length..];
+    *read = length as LONG;
+    0
+}
+
+impl CharFormat {
+
+    fn to_raw(&self) -> CHARFORMAT2W {
+        let mut raw: CHARFORMAT2W = unsafe { mem::zeroed() };
+        raw.cbSize = mem::size_of::<CHARFORMAT2W>() as UINT;
+
+        let effects = [
+            (self.bold, CFM_BOLD, CFE_BOLD),
+            (self.italic, CFM_ITALIC, CFE_ITALIC),
+            (self.underline, CFM_UNDERLINE, CFE_UNDERLINE),
+            (self.strikeout, CFM_STRIKEOUT, CFE_STRIKEOUT),
+        ];
+
+        for &(value, mask, effect) in effects.iter() {
+            if let Some(value) = value {
+                raw.dwMask |= mask;
+                if value {
+                    raw.dwEffects |= effect;
+                }
+            }
+        }
+
+        if let Some([r, g, b]) = self.text_color {
+            raw.dwMask |= CFM_COLOR;
+            raw.crTextColor = RGB(r, g, b);
+        }
+
+        if let Some([r, g, b]) = self.background_color {
+            raw.dwMask |= CFM_BACKCOLOR;
+            raw.crBackColor = RGB(r, g, b);
+        }
+
+        if let Some(face) = self.font_face.as_ref() {
+            // The last character of the buffer is kept for the null terminator
+            let face = to_utf16(face);
+            let length = (face.len() - 1).min(raw.szFaceName.len() - 1);
+            raw.dwMask |= CFM_FACE;
+            raw.szFaceName[..length].copy_from_slice(&face[..length]);
+        }
+
+        if let Some(size) = self.font_size {
+            raw.dwMask |= CFM_SIZE;
+            raw.yHeight = (size * 20.0).round() as _;
+        }
+
+        raw
+    }
+
+    fn from_raw(raw: &CHARFORMAT2W) -> CharFormat {
+        let has = |mask: u32| raw.dwMask & mask == mask;
+        let effect = |mask: u32, effect: u32| match has(mask) {
+            true => Some(raw.dwEffects & effect == effect),
+            false => None
+        };
+
+        let color = |mask: u32, auto: u32, color: COLORREF| match has(mask) && raw.dwEffects & auto == 0 {
+            true => Some([GetRValue(color), GetGValue(color), GetBValue(color)]),
+            false => None
+        };
+
+        CharFormat {
+            bold: effect(CFM_BOLD, CFE_BOLD),
+            italic: effect(CFM_ITALIC, CFE_ITALIC),
+            underline: effect(CFM_UNDERLINE, CFE_UNDERLINE),
+            strikeout: effect(CFM_STRIKEOUT, CFE_STRIKEOUT),
+            text_color: color(CFM_COLOR, CFE_AUTOCOLOR, raw.crTextColor),
+            background_color: color(CFM_BACKCOLOR, CFE_AUTOBACKCOLOR, raw.crBackColor),
+            font_face: match has(CFM_FACE) {
+                true => Some(from_utf16(&raw.szFaceName)),
+                false => None
+            },
+            font_size: match has(CFM_SIZE) {
+                true => Some(raw.yHeight as f32 / 20.0),
+                false => None
+            },
+        }
+    }
+
+}
+
+impl ParaFormat {
+
+    fn to_raw(&self) -> PARAFORMAT2 {
+        let mut raw: PARAFORMAT2 = unsafe { mem::zeroed() };
+        raw.cbSize = mem::size_of::<PARAFORMAT2>() as UINT;
+
+        if let Some(alignment) = self.alignment {
+            raw.dwMask |= PFM_ALIGNMENT;
+            raw.wAlignment = match alignment {
+                ParaAlignment::Left => PFA_LEFT,
+                ParaAlignment::Center => PFA_CENTER,
+                ParaAlignment::Right => PFA_RIGHT,
+                ParaAlignment::Justify => PFA_JUSTIFY,
+            };
+        }
+
+        if let Some(numbering) = self.numbering {
+            raw.dwMask |= PFM_NUMBERING;
+            raw.wNumbering = match numbering {
+                ParaNumbering::None => 0,
+                ParaNumbering::Bullet => PFN_BULLET,
+                ParaNumbering::Arabic => PFN_ARABIC,
+                ParaNumbering::LowerLetter => PFN_LCLETTER,
+                ParaNumbering::UpperLetter => PFN_UCLETTER,
+                ParaNumbering::LowerRoman => PFN_LCROMAN,
+                ParaNumbering::UpperRoman => PFN_UCROMAN,
+            };
+        }
+
+        raw.dxStartIndent = self.start_indent.unwrap_or(0);
+        raw.dxRightIndent = self.right_indent.unwrap_or(0);
+        raw.dxOffset = self.offset.unwrap_or(0);
+        raw.dySpaceBefore = self.space_before.unwrap_or(0);
+        raw.dySpaceAfter = self.space_after.unwrap_or(0);
+
+        let distances = [
+            (self.start_indent, PFM_STARTINDENT),
+            (self.right_indent, PFM_RIGHTINDENT),
+            (self.offset, PFM_OFFSET),
+            (self.space_before, PFM_SPACEBEFORE),
+            (self.space_after, PFM_SPACEAFTER),
+        ];
+
+        for &(value, mask) in distances.iter() {
+            if value.is_some() {
+                raw.dwMask |= mask;
+            }
+        }
+
+        raw
+    }
+
+    fn from_raw(raw: &PARAFORMAT2) -> ParaFormat {
+        let distance = |mask: u32, value: i32| match raw.dwMask & mask == mask {
+            true => Some(value),
+            false => None
+        };
+
+        let alignment = match raw.dwMask & PFM_ALIGNMENT == PFM_ALIGNMENT {
+            true => match raw.wAlignment {
+                PFA_LEFT => Some(ParaAlignment::Left),
+                PFA_CENTER => Some(ParaAlignment::Center),
+                PFA_RIGHT => Some(ParaAlignment::Right),
+                PFA_JUSTIFY => Some(ParaAlignment::Justify),
+                _ => None
+            },
+            false => None
+        };
+
+        let numbering = match raw.dwMask & PFM_NUMBERING == PFM_NUMBERING {
+            true => match raw.wNumbering {
+                0 => Some(ParaNumbering::None),
+                PFN_BULLET => Some(ParaNumbering::Bullet),
+                PFN_ARABIC => Some(ParaNumbering::Arabic),
+                PFN_LCLETTER => Some(ParaNumbering::LowerLetter),
+                PFN_UCLETTER => Some(ParaNumbering::UpperLetter),
+                PFN_LCROMAN => Some(ParaNumbering::LowerRoman),
+                PFN_UCROMAN => Some(ParaNumbering::UpperRoman),
+                _ => None
+            },
+            false => None
+        };
+
+        ParaFormat {
+            alignment,
+            numbering,
+            start_indent: distance(PFM_STARTINDENT, raw.dxStartIndent),
+            right_indent: distance(PFM_RIGHTINDENT, raw.dxRightIndent),
+            offset: distance(PFM_OFFSET, raw.dxOffset),
+            space_before: distance(PFM_SPACEBEFORE, raw.dySpaceBefore),
+            space_after: distance(PFM_SPACEAFTER, raw.dySpaceAfter),
+        }
+    }
+
+}
+
+impl PartialEq for RichTextBox {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for RichTextBox {}
+
 impl Drop for RichTextBox {
     fn drop(&mut self) {
+        use crate::unbind_raw_event_handler;
+
+        if let Some(h) = self.handler0.borrow().as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         self.handle.destroy();
     }
 }
@@ -295,6 +966,7 @@ pub struct RichTextBoxBuilder<'a> {
     readonly: bool,
     focus: bool,
     font: Option<&'a Font>,
+    highlighter: Option<Box<dyn Highlighter>>,
     parent: Option<ControlHandle>
 }
 
@@ -340,6 +1012,11 @@ impl<'a> RichTextBoxBuilder<'a> {
         self
     }
 
+    pub fn highlighter<H: Highlighter + 'static>(mut self, highlighter: H) -> RichTextBoxBuilder<'a> {
+        self.highlighter = Some(Box::new(highlighter));
+        self
+    }
+
     pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> RichTextBoxBuilder<'a> {
         self.parent = Some(p.into());
         self
@@ -377,6 +1054,10 @@ impl<'a> RichTextBoxBuilder<'a> {
             out.set_font(Font::global_default().as_ref());
         }
 
+        if let Some(highlighter) = self.highlighter {
+            out.set_boxed_highlighter(highlighter);
+        }
+
         if self.focus {
             out.set_focus();
         }
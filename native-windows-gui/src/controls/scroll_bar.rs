@@ -42,12 +42,13 @@ Requires the `scroll-bar` feature.
   * `focus`:            The control receive focus after being created
   * `flags`:            A combination of the ScrollBarFlags values.
   * `range`:            The value range of the scroll bar
+  * `page_size`:        The number of values in a page of the scroll bar
   * `pos`:              The current value of the scroll bar
 
 
 **Control events:**
-  * `OnVerticalScroll`: When the value of a scrollbar with the VERTICAL flags is changed
-  * `OnHorizontalScroll`: When the value of a scrollbar with the HORIZONTAL flags is changed
+  * `OnVerticalScroll`: When the value of a scrollbar with the VERTICAL flags is changed. Sends `EventData::OnScroll`.
+  * `OnHorizontalScroll`: When the value of a scrollbar with the HORIZONTAL flags is changed. Sends `EventData::OnScroll`.
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse event
   * `OnMouseWheel`: Generic mouse wheel event
@@ -58,6 +59,7 @@ use native_windows_gui as nwg;
 fn build_scrollbar(button: &mut nwg::ScrollBar, window: &nwg::Window) {
     nwg::ScrollBar::builder()
         .range(Some(0..100))
+        .page_size(Some(10))
         .pos(Some(10))
         .parent(window)
         .build(button);
@@ -82,6 +84,7 @@ impl ScrollBar {
             parent: None,
             focus: false,
             range: None,
+            page_size: None,
             pos: None
         }
     }
@@ -131,6 +134,38 @@ impl ScrollBar {
         );
     }
 
+    /// Returns the number of values in a page of the scrollbar. Page up and page down move the slider by this amount.
+    pub fn page_size(&self) -> usize {
+        use winapi::um::winuser::{SIF_PAGE, SB_CTL, SCROLLINFO, GetScrollInfo};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut si: SCROLLINFO = unsafe { mem::zeroed() };
+        si.cbSize = mem::size_of::<SCROLLINFO>() as u32;
+        si.fMask = SIF_PAGE;
+        unsafe { GetScrollInfo(handle, SB_CTL as i32, &mut si); }
+
+        si.nPage as usize
+    }
+
+    /**
+        Sets the number of values in a page of the scrollbar, usually the number of items visible at once.
+        The size of the slider is proportional to the page and the last position of the scrollbar becomes `range.end - size + 1`.
+        A size of 0 uses the default slider size.
+    */
+    pub fn set_page_size(&self, size: usize) {
+        use winapi::um::winuser::{SIF_PAGE, SB_CTL, SCROLLINFO, SetScrollInfo};
+        use winapi::shared::minwindef::TRUE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut si: SCROLLINFO = unsafe { mem::zeroed() };
+        si.cbSize = mem::size_of::<SCROLLINFO>() as u32;
+        si.fMask = SIF_PAGE;
+        si.nPage = size as u32;
+        unsafe { SetScrollInfo(handle, SB_CTL as i32, &si, TRUE); }
+    }
+
     /// Returns true if the control currently has the keyboard focus
     pub fn focus(&self) -> bool {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
@@ -211,7 +246,7 @@ impl ScrollBar {
     unsafe fn hook_scrollbar_controls(&self) {
         use crate::bind_raw_event_handler_inner;
         use winapi::um::winuser::{WM_HSCROLL, WM_VSCROLL, SIF_ALL, SB_CTL, SIF_POS, SB_TOP, SB_BOTTOM, SB_PAGEUP, SB_PAGEDOWN,
-            SB_LINERIGHT, SB_LINELEFT, SB_PAGELEFT, SB_PAGERIGHT, SB_THUMBTRACK, SB_LINEUP, SB_LINEDOWN, WM_MOUSEWHEEL,
+            SB_LEFT, SB_RIGHT, SB_LINERIGHT, SB_LINELEFT, SB_PAGELEFT, SB_PAGERIGHT, SB_THUMBTRACK, SB_LINEUP, SB_LINEDOWN, WM_MOUSEWHEEL,
             GET_WHEEL_DELTA_WPARAM, SCROLLINFO, GetScrollInfo, SetScrollInfo};
         use winapi::shared::{minwindef::{TRUE, LOWORD}, windef::HWND};
 
@@ -231,6 +266,8 @@ impl ScrollBar {
 
                     let event = LOWORD(w as u32) as isize;
                     match event {
+                        SB_LEFT => {  si.nPos = si.nMin; },
+                        SB_RIGHT => { si.nPos = si.nMax; },
                         SB_LINELEFT => { si.nPos -= 1; },
                         SB_LINERIGHT => { si.nPos += 1; },
                         SB_PAGELEFT => { si.nPos -= si.nPage as i32; },
@@ -329,6 +366,7 @@ pub struct ScrollBarBuilder {
     parent: Option<ControlHandle>,
     focus: bool,
     range: Option<Range<usize>>,
+    page_size: Option<usize>,
     pos: Option<usize>,
 }
 
@@ -364,6 +402,11 @@ impl ScrollBarBuilder {
         self
     }
 
+    pub fn page_size(mut self, size: Option<usize>) -> ScrollBarBuilder {
+        self.page_size = size;
+        self
+    }
+
     pub fn pos(mut self, pos: Option<usize>) -> ScrollBarBuilder {
         self.pos = pos;
         self
@@ -397,6 +440,10 @@ impl ScrollBarBuilder {
             out.set_range(range);
         }
 
+        if let Some(size) = self.page_size {
+            out.set_page_size(size);
+        }
+
         if let Some(pos) = self.pos {
             out.set_pos(pos);
         }
@@ -1,8 +1,9 @@
 use winapi::shared::minwindef::{WPARAM, LPARAM};
 use winapi::um::winuser::{WS_VSCROLL, WS_HSCROLL, ES_AUTOVSCROLL, ES_AUTOHSCROLL, WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
 use crate::win32::window_helper as wh;
+use crate::win32::edit_helper;
 use crate::{Font, NwgError};
-use super::{ControlBase, ControlHandle};
+use super::{ControlBase, ControlHandle, FindOptions, find_text};
 use std::ops::Range;
 
 const NOT_BOUND: &'static str = "TextBox is not yet bound to a winapi object";
@@ -40,6 +41,9 @@ Requires the `textbox` feature.
 
 Note: Use `\r\n` to input a new line not just `\n`.
 
+Note: The character offsets used by the text box (selection, lines, find) are counted in UTF-16 code units.
+Use `utf16_offset_to_index` and `index_to_utf16_offset` to convert them to the byte indices of `text`.
+
 **Builder parameters:**
   * `parent`:   **Required.** The text box parent container.
   * `text`:     The text box text.
@@ -183,6 +187,55 @@ impl TextBox {
         wh::send_message(handle, EM_SETSEL as u32, r.start as usize, r.end as isize);
     }
 
+    /// Replace the selected text with `text`. If no text is selected, `text` is inserted at the caret.
+    /// Unlike `set_text`, the change can be undone by the user.
+    pub fn replace_selection<'a>(&self, text: &'a str) {
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        edit_helper::replace_selection(handle, text);
+    }
+
+    /// Return the range of characters of the first occurence of `pattern` after `options.start`, or `None` if there is no match.
+    /// Use `set_selection` to select the result.
+    pub fn find<'a>(&self, pattern: &'a str, options: FindOptions) -> Option<Range<u32>> {
+        find_text(&self.text(), pattern, options)
+    }
+
+    /// Return the number of lines in the text box
+    pub fn line_count(&self) -> u32 {
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        edit_helper::line_count(handle)
+    }
+
+    /// Return the text of the line at `index` without the line break, or `None` if the line does not exist
+    pub fn line(&self, index: u32) -> Option<String> {
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        edit_helper::line(handle, index)
+    }
+
+    /// Return the index of the line that contains the character at `offset`
+    pub fn line_from_char(&self, offset: u32) -> u32 {
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        edit_helper::line_from_char(handle, offset)
+    }
+
+    /// Return the offset of the first character of the line at `index`, or `None` if the line does not exist
+    pub fn char_from_line(&self, index: u32) -> Option<u32> {
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        edit_helper::char_from_line(handle, index)
+    }
+
+    /// Scroll the text box vertically so that the line at `index` is the first visible line
+    pub fn scroll_to_line(&self, index: u32) {
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        edit_helper::scroll_to_line(handle, index);
+    }
+
     /// Return the length of the user input in the control. This is better than test.len() as it
     /// does not allocate a string in memory
     pub fn len(&self) -> u32 {
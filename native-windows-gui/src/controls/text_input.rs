@@ -2,12 +2,14 @@
  	An edit control is a rectangular control window to permit the user to enter and edit text by typing on the keyboard
 */
 use winapi::shared::minwindef::{UINT, WPARAM, LPARAM};
-use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, ES_NUMBER, ES_LEFT, ES_CENTER, ES_RIGHT, WS_TABSTOP, ES_AUTOHSCROLL};
+use winapi::shared::windef::HWND;
+use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, ES_NUMBER, ES_LEFT, ES_CENTER, ES_RIGHT, WS_TABSTOP, ES_AUTOHSCROLL, WS_CLIPCHILDREN};
 use crate::win32::window_helper as wh; 
 use crate::win32::base_helper::check_hwnd;
-use crate::{Font, NwgError, HTextAlign, RawEventHandler};
-use super::{ControlBase, ControlHandle};
-use std::cell::RefCell;
+use crate::{Font, NwgError, HTextAlign, RawEventHandler, Validator};
+use super::{ControlBase, ControlHandle, Button, ButtonFlags};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::ops::Range;
 use std::char;
 
@@ -55,9 +57,13 @@ TextInput is not behind any features.
   * `align`:            The alignment of the text in the text input
   * `background_color`: The color of the textinput top and bottom padding. This is not the white background under the text.
   * `focus`:            The control receive focus after being created
+  * `validator`:        Filters the text typed by the user. See `TextInput::set_validator`
+  * `placeholder`:      The grey text displayed when the text input is empty
+  * `clear_button`:     Displays a button that clears the text at the right of the text input when it is not empty
 
 **Control events:**
   * `OnTextInput`: When a TextInput value is changed
+  * `OnValidationChanged`: When the text of a TextInput with a validator becomes valid or invalid
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
@@ -71,12 +77,43 @@ fn build_box(tbox: &mut nwg::TextInput, window: &nwg::Window, font: &nwg::Font)
         .parent(window)
         .build(tbox);
 }
+
+fn build_age_input(tbox: &mut nwg::TextInput, window: &nwg::Window) {
+    nwg::TextInput::builder()
+        .validator(nwg::IntegerValidator::range(0, 150))
+        .parent(window)
+        .build(tbox);
+}
+
+fn build_search_box(tbox: &mut nwg::TextInput, window: &nwg::Window) {
+    nwg::TextInput::builder()
+        .placeholder(Some("Search..."))
+        .clear_button(true)
+        .parent(window)
+        .build(tbox);
+}
 ```
 */
 #[derive(Default)]
 pub struct TextInput {
     pub handle: ControlHandle,
+    validation: Rc<Validation>,
+    clear_button: Button,
     handler0: RefCell<Option<RawEventHandler>>,
+    handler1: RefCell<Option<RawEventHandler>>,
+    handler2: RefCell<Option<RawEventHandler>>,
+}
+
+/// The validator of a text input, shared with the `EN_UPDATE` handler
+#[derive(Default)]
+struct Validation {
+    validator: RefCell<Option<Box<dyn Validator>>>,
+
+    /// The last text accepted by the validator. Restored when an edit is rejected.
+    text: RefCell<String>,
+
+    valid: Cell<bool>,
+    updating: Cell<bool>,
 }
 
 impl TextInput {
@@ -95,6 +132,9 @@ impl TextInput {
             font: None,
             parent: None,
             background_color: None,
+            validator: None,
+            placeholder: None,
+            clear_button: false,
         }
     }
 
@@ -113,6 +153,35 @@ impl TextInput {
     pub fn set_font(&self, font: Option<&Font>) {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         unsafe { wh::set_window_font(handle, font.map(|f| f.handle), true); }
+
+        // Setting the font resets the margins of the control
+        if let Some(button) = self.clear_button.handle.hwnd() {
+            self.clear_button.set_font(font);
+            place_clear_button(handle, button);
+        }
+    }
+
+    /// Return the grey text displayed when the text input is empty
+    pub fn placeholder(&self) -> String {
+        use winapi::um::commctrl::EM_GETCUEBANNER;
+        use crate::win32::base_helper::from_utf16;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut buffer = [0u16; 256];
+        wh::send_message(handle, EM_GETCUEBANNER, buffer.as_mut_ptr() as WPARAM, buffer.len() as LPARAM);
+        from_utf16(&buffer)
+    }
+
+    /// Set or remove the grey text displayed when the text input is empty. The text is still displayed when the control has the focus.
+    pub fn set_placeholder<'a>(&self, text: Option<&'a str>) {
+        use winapi::um::commctrl::EM_SETCUEBANNER;
+        use crate::win32::base_helper::to_utf16;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let text = to_utf16(text.unwrap_or(""));
+        wh::send_message(handle, EM_SETCUEBANNER, 1, text.as_ptr() as LPARAM);
     }
 
     /// Return the password character displayed by the text input. If the input is not a password, return None.
@@ -291,12 +360,38 @@ impl TextInput {
         unsafe { wh::get_window_text(handle) }
     }
 
-    /// Set the text displayed in the TextInput
+    /// Set the text displayed in the TextInput.
+    /// If the TextInput has a validator, the text is filtered like the user input.
     pub fn set_text<'a>(&self, v: &'a str) {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         unsafe { wh::set_window_text(handle, v) }
     }
 
+    /**
+        Sets the validator of the text input. Each time the text is edited (typing, paste, `set_text`), it goes through `Validator::filter`.
+        Rejected edits are reverted and a reformatted text replaces the edited text.
+
+        `OnValidationChanged` is raised when the result of `Validator::is_valid` changes. The current text of the control is kept as is.
+    */
+    pub fn set_validator<V: Validator + 'static>(&self, validator: V) {
+        self.set_boxed_validator(Box::new(validator));
+    }
+
+    /// Removes the validator of the text input. The user can type any text again.
+    pub fn reset_validator(&self) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.validation.validator.borrow_mut().take();
+    }
+
+    /// Returns `true` if the text of the control is accepted by its validator. Always `true` if there is no validator.
+    pub fn is_valid(&self) -> bool {
+        let text = self.text();
+        match self.validation.validator.borrow().as_ref() {
+            Some(validator) => validator.is_valid(&text),
+            None => true
+        }
+    }
+
     /// Winapi class name used during control creation
     pub fn class_name(&self) -> &'static str {
         "EDIT"
@@ -414,15 +509,179 @@ impl TextInput {
         }
     }
 
+    fn set_boxed_validator(&self, validator: Box<dyn Validator>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let text = self.text();
+        self.validation.valid.set(validator.is_valid(&text));
+        *self.validation.text.borrow_mut() = text;
+        *self.validation.validator.borrow_mut() = Some(validator);
+
+        if self.handler1.borrow().is_none() {
+            self.bind_parent_handler(handle);
+        }
+    }
+
+    /**
+        Handles the notifications that the control sends to its parent: filters the text on `EN_UPDATE`
+        and shows the clear button on `EN_CHANGE` if the text is not empty.
+    */
+    fn bind_parent_handler(&self, handle: HWND) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{WM_COMMAND, EN_UPDATE, EN_CHANGE, GetWindowTextLengthW};
+        use winapi::shared::minwindef::HIWORD;
+        use winapi::shared::basetsd::UINT_PTR;
+
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let validation = self.validation.clone();
+        let clear_button = self.clear_button.handle.hwnd();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as UINT_PTR, move |_hwnd, msg, w, l| {
+            if msg != WM_COMMAND || l as HWND != handle {
+                return None;
+            }
+
+            match HIWORD(w as u32) as u16 {
+                EN_UPDATE => validation.update(handle),
+                EN_CHANGE => if let Some(button) = clear_button {
+                    let empty = unsafe { GetWindowTextLengthW(handle) } == 0;
+                    unsafe { wh::set_window_visibility(button, !empty); }
+                },
+                _ => {}
+            }
+
+            None
+        });
+
+        *self.handler1.borrow_mut() = Some(handler.unwrap());
+    }
+
+    /// Creates the button that clears the text. The button is a child of the control, displayed in its right margin.
+    fn add_clear_button(&mut self) -> Result<(), NwgError> {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{WM_COMMAND, WM_WINDOWPOSCHANGED, BN_CLICKED};
+        use winapi::shared::minwindef::HIWORD;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        // Without `WS_CLIPCHILDREN`, the control would paint over the button
+        wh::set_style(handle, wh::get_style(handle) | WS_CLIPCHILDREN);
+
+        Button::builder()
+            .text("×")
+            .flags(ButtonFlags::NONE)
+            .parent(&self.handle)
+            .build(&mut self.clear_button)?;
+
+        let button = self.clear_button.handle.hwnd().expect(BAD_HANDLE);
+        place_clear_button(handle, button);
+        unsafe { wh::set_window_visibility(button, !self.text().is_empty()); }
+
+        // The id 0 is used by `hook_non_client_size`
+        let handler = bind_raw_event_handler_inner(&self.handle, 1, move |hwnd, msg, w, l| {
+            match msg {
+                WM_COMMAND if l as HWND == button && HIWORD(w as u32) as u16 == BN_CLICKED => unsafe {
+                    wh::set_window_text(hwnd, "");
+                    wh::set_focus(hwnd);
+                },
+                WM_WINDOWPOSCHANGED => place_clear_button(hwnd, button),
+                _ => {}
+            }
+
+            None
+        });
+
+        *self.handler2.borrow_mut() = Some(handler.unwrap());
+
+        self.bind_parent_handler(handle);
+
+        Ok(())
+    }
+
+}
+
+/// Places the clear button at the right of the client area of the text input and reserves its space in the right margin
+fn place_clear_button(handle: HWND, button: HWND) {
+    use winapi::um::winuser::{GetClientRect, SetWindowPos, EM_SETMARGINS, EC_RIGHTMARGIN, SWP_NOZORDER, SWP_NOACTIVATE};
+    use winapi::shared::windef::RECT;
+    use winapi::shared::minwindef::MAKELONG;
+    use std::{mem, ptr};
+
+    let mut client: RECT = unsafe { mem::zeroed() };
+    unsafe { GetClientRect(handle, &mut client); }
+
+    let size = client.bottom - client.top;
+    unsafe { SetWindowPos(button, ptr::null_mut(), client.right - size, 0, size, size, SWP_NOZORDER | SWP_NOACTIVATE); }
+
+    wh::send_message(handle, EM_SETMARGINS as UINT, EC_RIGHTMARGIN as WPARAM, MAKELONG(0, size as u16) as LPARAM);
+}
+
+impl Validation {
+
+    /// Filters the text of the control and raises `OnValidationChanged` if the valid state of the text changed
+    fn update(&self, handle: HWND) {
+        use winapi::um::winuser::{EM_GETSEL, EM_SETSEL, MessageBeep, MB_OK};
+
+        if self.updating.get() {
+            return;
+        }
+
+        let valid = {
+            let validator = self.validator.borrow();
+            let validator = match validator.as_ref() {
+                Some(v) => v,
+                None => { return; }
+            };
+
+            let text = unsafe { wh::get_window_text(handle) };
+            match validator.filter(&text) {
+                Some(ref filtered) if *filtered == text => {
+                    *self.text.borrow_mut() = text;
+                },
+                filtered => {
+                    let new_text = match filtered {
+                        Some(filtered) => filtered,
+                        None => {
+                            unsafe { MessageBeep(MB_OK); }
+                            self.text.borrow().clone()
+                        }
+                    };
+
+                    // Keeps the same number of characters between the caret and the end of the text
+                    let mut caret = 0u32;
+                    wh::send_message(handle, EM_GETSEL as u32, &mut caret as *mut u32 as WPARAM, 0);
+                    let after_caret = (text.encode_utf16().count() as u32).saturating_sub(caret);
+                    let caret = (new_text.encode_utf16().count() as u32).saturating_sub(after_caret);
+
+                    self.updating.set(true);
+                    unsafe { wh::set_window_text(handle, &new_text); }
+                    wh::send_message(handle, EM_SETSEL as u32, caret as WPARAM, caret as LPARAM);
+                    self.updating.set(false);
+
+                    *self.text.borrow_mut() = new_text;
+                }
+            }
+
+            validator.is_valid(&self.text.borrow())
+        };
+
+        // The borrows are released because the event handlers may change the validator
+        if valid != self.valid.get() {
+            self.valid.set(valid);
+            wh::send_message(handle, wh::NWG_VALIDATION_CHANGED, valid as WPARAM, 0);
+        }
+    }
+
 }
 
 impl Drop for TextInput {
     fn drop(&mut self) {
         use crate::unbind_raw_event_handler;
         
-        let handler = self.handler0.borrow();
-        if let Some(h) = handler.as_ref() {
-            drop(unbind_raw_event_handler(h));
+        for handler in [&self.handler0, &self.handler1, &self.handler2].iter() {
+            if let Some(h) = handler.borrow().as_ref() {
+                drop(unbind_raw_event_handler(h));
+            }
         }
         
         self.handle.destroy();
@@ -442,6 +701,9 @@ pub struct TextInputBuilder<'a> {
     parent: Option<ControlHandle>,
     background_color: Option<[u8; 3]>,
     focus: bool,
+    validator: Option<Box<dyn Validator>>,
+    placeholder: Option<&'a str>,
+    clear_button: bool,
 }
 
 impl<'a> TextInputBuilder<'a> {
@@ -501,6 +763,21 @@ impl<'a> TextInputBuilder<'a> {
         self
     }
 
+    pub fn validator<V: Validator + 'static>(mut self, validator: V) -> TextInputBuilder<'a> {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: Option<&'a str>) -> TextInputBuilder<'a> {
+        self.placeholder = placeholder;
+        self
+    }
+
+    pub fn clear_button(mut self, clear_button: bool) -> TextInputBuilder<'a> {
+        self.clear_button = clear_button;
+        self
+    }
+
     pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> TextInputBuilder<'a> {
         self.parent = Some(p.into());
         self
@@ -538,6 +815,14 @@ impl<'a> TextInputBuilder<'a> {
 
         out.hook_non_client_size(self.background_color);
 
+        if self.clear_button {
+            out.add_clear_button()?;
+        }
+
+        if self.placeholder.is_some() {
+            out.set_placeholder(self.placeholder);
+        }
+
         if self.limit > 0 {
             out.set_limit(self.limit);
         }
@@ -560,6 +845,10 @@ impl<'a> TextInputBuilder<'a> {
             out.set_font(Font::global_default().as_ref());
         }
 
+        if let Some(validator) = self.validator {
+            out.set_boxed_validator(validator);
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,140 @@
+/*!
+    Search in the text of a `TextBox` or a `RichTextBox`.
+
+    The edit controls count characters in UTF-16 code units, so the offsets they use (selection, line index, etc.)
+    are not the byte indices of the `String` returned by `text`. The functions of this module translate between the two.
+*/
+use std::ops::Range;
+
+
+/**
+    The options of `TextBox::find` and `RichTextBox::find`.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    // Find the next occurence of the word "hello" after the selection
+    fn find_next(text_box: &nwg::TextBox) -> Option<std::ops::Range<u32>> {
+        let options = nwg::FindOptions { whole_word: true, start: text_box.selection().end, ..Default::default() };
+        text_box.find("hello", options)
+    }
+    ```
+*/
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FindOptions {
+    /// If `false`, the characters are compared without their case
+    pub match_case: bool,
+
+    /// Only match whole words: the characters before and after the match must not be letters, digits or `_`
+    pub whole_word: bool,
+
+    /// The offset (in UTF-16 code units) where the search begins
+    pub start: u32,
+}
+
+
+/**
+    Returns the byte index in `text` of the UTF-16 `offset`. `offset` can be the length of the text.
+
+    Returns `None` if `offset` is after the end of the text or if it falls in the middle of a surrogate pair.
+
+    ```rust
+    use native_windows_gui as nwg;
+    assert_eq!(nwg::utf16_offset_to_index("é😀a", 3), Some(6));
+    assert_eq!(nwg::utf16_offset_to_index("é😀a", 2), None);
+    ```
+*/
+pub fn utf16_offset_to_index(text: &str, offset: u32) -> Option<usize> {
+    let mut units = 0;
+    for (index, c) in text.char_indices() {
+        if units == offset {
+            return Some(index);
+        }
+
+        units += c.len_utf16() as u32;
+        if units > offset {
+            return None;
+        }
+    }
+
+    match units == offset {
+        true => Some(text.len()),
+        false => None
+    }
+}
+
+/**
+    Returns the UTF-16 offset of the byte `index` in `text`.
+
+    Panics if `index` is not on a char boundary, like slicing `text` would.
+
+    ```rust
+    use native_windows_gui as nwg;
+    assert_eq!(nwg::index_to_utf16_offset("é😀a", 6), 3);
+    ```
+*/
+pub fn index_to_utf16_offset(text: &str, index: usize) -> u32 {
+    text[..index].encode_utf16().count() as u32
+}
+
+/**
+    Returns the UTF-16 range of the first occurence of `pattern` in `text`, starting at `options.start`.
+
+    Without `match_case`, the characters are compared one by one using their lowercase form, so a character that
+    lowercases to several characters (ex: "ß" and "SS") does not match.
+
+    Returns `None` if `pattern` is empty, if it is not found, or if `options.start` is not a valid offset in `text`.
+*/
+pub fn find_text(text: &str, pattern: &str, options: FindOptions) -> Option<Range<u32>> {
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let start = utf16_offset_to_index(text, options.start)?;
+    let mut offset = options.start;
+    let mut previous = text[..start].chars().next_back();
+    let mut rest = &text[start..];
+
+    loop {
+        if let Some(length) = match_at(rest, pattern, options.match_case) {
+            let next = rest[length..].chars().next();
+            if !options.whole_word || !(is_word_char(previous) || is_word_char(next)) {
+                let end = offset + rest[..length].encode_utf16().count() as u32;
+                return Some(offset..end);
+            }
+        }
+
+        let c = rest.chars().next()?;
+        offset += c.len_utf16() as u32;
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+/// Returns the length in bytes of the text matched by `pattern` at the beginning of `text`
+fn match_at(text: &str, pattern: &str, match_case: bool) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for p in pattern.chars() {
+        let (_, c) = chars.next()?;
+        let equal = match match_case {
+            true => c == p,
+            false => c == p || c.to_lowercase().eq(p.to_lowercase())
+        };
+
+        if !equal {
+            return None;
+        }
+    }
+
+    match chars.next() {
+        Some((index, _)) => Some(index),
+        None => Some(text.len())
+    }
+}
+
+fn is_word_char(c: Option<char>) -> bool {
+    match c {
+        Some(c) => c.is_alphanumeric() || c == '_',
+        None => false
+    }
+}
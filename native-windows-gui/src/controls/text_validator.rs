@@ -0,0 +1,367 @@
+/*!
+    Validation of the text typed by the user in a `TextInput`. See `TextInputBuilder::validator`.
+*/
+
+
+/**
+    Validates the text of a `TextInput`. NWG implements `IntegerValidator`, `FloatValidator`, `InputMask` and
+    `RegexValidator` (with the `regex-validator` feature).
+
+    `filter` is called each time the text is edited, so it also sees text that is not complete yet (ex: "-" for an integer).
+    `is_valid` is called on the filtered text to decide if the value can be used.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    /// Only accepts lowercase ascii text
+    struct Lowercase;
+
+    impl nwg::Validator for Lowercase {
+        fn is_valid(&self, text: &str) -> bool {
+            !text.is_empty()
+        }
+
+        fn filter(&self, text: &str) -> Option<String> {
+            match text.chars().all(|c| c.is_ascii_alphabetic()) {
+                true => Some(text.to_ascii_lowercase()),
+                false => None
+            }
+        }
+    }
+    ```
+*/
+pub trait Validator {
+    /// Returns `true` if `text` is a complete and valid value
+    fn is_valid(&self, text: &str) -> bool;
+
+    /// Returns the text to display after the user edited the text of the control, or `None` to reject the edit.
+    /// Rejected edits (keystrokes, paste, cut) are reverted. By default, every edit is accepted.
+    fn filter(&self, text: &str) -> Option<String> {
+        Some(text.to_string())
+    }
+}
+
+
+/// Accepts integers between `min` and `max` (inclusive). A `None` bound is not checked.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntegerValidator {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl IntegerValidator {
+
+    /// Accepts the integers between `min` and `max` (inclusive)
+    pub fn range(min: i64, max: i64) -> IntegerValidator {
+        IntegerValidator { min: Some(min), max: Some(max) }
+    }
+
+}
+
+impl Validator for IntegerValidator {
+
+    fn is_valid(&self, text: &str) -> bool {
+        match text.parse::<i64>() {
+            Ok(value) => in_range(value, self.min, self.max),
+            Err(_) => false
+        }
+    }
+
+    fn filter(&self, text: &str) -> Option<String> {
+        let negative = text.starts_with('-');
+        let digits = match negative {
+            true => &text[1..],
+            false => text
+        };
+
+        if !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        if negative && self.min.map(|min| min >= 0).unwrap_or(false) {
+            return None;
+        }
+
+        // Typing more digits moves the value away from zero, so a value out of range cannot become valid
+        if !digits.is_empty() {
+            let value = text.parse::<i64>().ok()?;
+            if out_of_reach(value as f64, self.min.map(|v| v as f64), self.max.map(|v| v as f64)) {
+                return None;
+            }
+        }
+
+        Some(text.to_string())
+    }
+
+}
+
+
+/// Accepts decimal numbers between `min` and `max` (inclusive), with at most `decimals` digits after the `.`
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FloatValidator {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub decimals: Option<usize>,
+}
+
+impl FloatValidator {
+
+    /// Accepts the numbers between `min` and `max` (inclusive)
+    pub fn range(min: f64, max: f64) -> FloatValidator {
+        FloatValidator { min: Some(min), max: Some(max), decimals: None }
+    }
+
+}
+
+impl Validator for FloatValidator {
+
+    fn is_valid(&self, text: &str) -> bool {
+        if self.filter(text).is_none() || !text.chars().any(|c| c.is_ascii_digit()) {
+            return false;
+        }
+
+        match text.parse::<f64>() {
+            Ok(value) => in_range(value, self.min, self.max),
+            Err(_) => false
+        }
+    }
+
+    fn filter(&self, text: &str) -> Option<String> {
+        let negative = text.starts_with('-');
+        let number = match negative {
+            true => &text[1..],
+            false => text
+        };
+
+        let (integer, fraction) = match number.find('.') {
+            Some(i) => (&number[..i], Some(&number[i+1..])),
+            None => (number, None)
+        };
+
+        let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if !digits(integer) || !fraction.map(digits).unwrap_or(true) {
+            return None;
+        }
+
+        if let (Some(fraction), Some(decimals)) = (fraction, self.decimals) {
+            if fraction.len() > decimals || decimals == 0 {
+                return None;
+            }
+        }
+
+        if negative && self.min.map(|min| min >= 0.0).unwrap_or(false) {
+            return None;
+        }
+
+        // Only the integer part is checked, because typing decimals can still bring the value back in the range
+        if !integer.is_empty() {
+            let value: f64 = integer.parse().ok()?;
+            let value = if negative { -value } else { value };
+            if out_of_reach(value, self.min.map(|m| m.trunc()), self.max.map(|m| m.trunc())) {
+                return None;
+            }
+        }
+
+        Some(text.to_string())
+    }
+
+}
+
+fn in_range<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+    min.map(|min| value >= min).unwrap_or(true) && max.map(|max| value <= max).unwrap_or(true)
+}
+
+/// Returns `true` if typing more digits after `value` can never bring it between `min` and `max`
+fn out_of_reach(value: f64, min: Option<f64>, max: Option<f64>) -> bool {
+    match value < 0.0 {
+        true => min.map(|min| value < min).unwrap_or(false),
+        false => max.map(|max| value > max).unwrap_or(false)
+    }
+}
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum MaskSlot {
+    Digit,
+    Letter,
+    AlphaNumeric,
+    Literal(char),
+}
+
+impl MaskSlot {
+    fn accepts(&self, c: char) -> bool {
+        match *self {
+            MaskSlot::Digit => c.is_ascii_digit(),
+            MaskSlot::Letter => c.is_alphabetic(),
+            MaskSlot::AlphaNumeric => c.is_alphanumeric(),
+            MaskSlot::Literal(l) => c == l,
+        }
+    }
+}
+
+/**
+    A fixed format input, like a date or a phone number. The literal characters of the mask are inserted automatically
+    while the user types, and the characters that do not fit the mask are rejected.
+
+    Mask characters:
+      * `0`: a digit
+      * `a`: a letter
+      * `*`: a letter or a digit
+      * `\`: the next character is a literal (ex: `\0`)
+      * Any other character is a literal
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let phone = nwg::InputMask::new("(000) 000-0000");
+    assert_eq!(phone.apply("5551234").as_deref(), Some("(555) 123-4"));
+    assert!(phone.is_complete("(555) 123-4567"));
+    ```
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputMask {
+    slots: Vec<MaskSlot>,
+}
+
+impl InputMask {
+
+    pub fn new(mask: &str) -> InputMask {
+        let mut slots = Vec::with_capacity(mask.len());
+        let mut chars = mask.chars();
+        while let Some(c) = chars.next() {
+            let slot = match c {
+                '0' => MaskSlot::Digit,
+                'a' => MaskSlot::Letter,
+                '*' => MaskSlot::AlphaNumeric,
+                '\\' => match chars.next() {
+                    Some(escaped) => MaskSlot::Literal(escaped),
+                    None => MaskSlot::Literal('\\'),
+                },
+                c => MaskSlot::Literal(c),
+            };
+
+            slots.push(slot);
+        }
+
+        InputMask { slots }
+    }
+
+    /// Returns the number of characters of a complete value
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /**
+        Places the characters of `text` in the mask and inserts the missing literals before them. The literals already in `text`
+        are kept if they are at the right position and ignored otherwise, so a formatted value can be edited or pasted again.
+
+        Returns `None` if a character does not fit the mask or if the text is too long.
+    */
+    pub fn apply(&self, text: &str) -> Option<String> {
+        let mut output = String::with_capacity(text.len());
+        let mut slots = self.slots.iter().peekable();
+
+        for c in text.chars() {
+            loop {
+                let slot = *slots.peek()?;
+                if slot.accepts(c) {
+                    output.push(c);
+                    slots.next();
+                    break;
+                }
+
+                match *slot {
+                    MaskSlot::Literal(l) => {
+                        output.push(l);
+                        slots.next();
+                    },
+                    _ if self.is_literal(c) => break,
+                    _ => { return None; }
+                }
+            }
+        }
+
+        Some(output)
+    }
+
+    /// Returns `true` if `text` fills the whole mask
+    pub fn is_complete(&self, text: &str) -> bool {
+        match self.apply(text) {
+            Some(formatted) => formatted == text && formatted.chars().count() == self.slots.len(),
+            None => false
+        }
+    }
+
+    fn is_literal(&self, c: char) -> bool {
+        self.slots.iter().any(|s| *s == MaskSlot::Literal(c))
+    }
+
+}
+
+impl Validator for InputMask {
+
+    fn is_valid(&self, text: &str) -> bool {
+        self.is_complete(text)
+    }
+
+    fn filter(&self, text: &str) -> Option<String> {
+        self.apply(text)
+    }
+
+}
+
+
+/**
+    Accepts the text matching a regular expression. Requires the `regex-validator` feature.
+
+    A regex cannot tell if an incomplete text can still match, so by default every edit is accepted and only `is_valid` checks the regex.
+    Use `partial` to also reject the edits that do not match a second, more permissive, regex.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let hex_color = nwg::RegexValidator::new("^#[0-9a-fA-F]{6}$").unwrap()
+        .partial("^#?[0-9a-fA-F]{0,6}$").unwrap();
+    ```
+*/
+#[cfg(feature="regex-validator")]
+#[derive(Clone, Debug)]
+pub struct RegexValidator {
+    full: regex::Regex,
+    partial: Option<regex::Regex>,
+}
+
+#[cfg(feature="regex-validator")]
+impl RegexValidator {
+
+    /// Creates a validator that accepts the text matching `pattern`
+    pub fn new(pattern: &str) -> Result<RegexValidator, regex::Error> {
+        Ok(RegexValidator {
+            full: regex::Regex::new(pattern)?,
+            partial: None
+        })
+    }
+
+    /// Rejects the edits that produce a text that does not match `pattern`
+    pub fn partial(mut self, pattern: &str) -> Result<RegexValidator, regex::Error> {
+        self.partial = Some(regex::Regex::new(pattern)?);
+        Ok(self)
+    }
+
+}
+
+#[cfg(feature="regex-validator")]
+impl Validator for RegexValidator {
+
+    fn is_valid(&self, text: &str) -> bool {
+        self.full.is_match(text)
+    }
+
+    fn filter(&self, text: &str) -> Option<String> {
+        match &self.partial {
+            Some(partial) if !partial.is_match(text) => None,
+            _ => Some(text.to_string())
+        }
+    }
+
+}
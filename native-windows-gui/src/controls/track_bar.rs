@@ -1,6 +1,6 @@
 use winapi::shared::minwindef::{WPARAM, LPARAM};
 use winapi::um::winuser::{WS_VISIBLE, WS_TABSTOP};
-use winapi::um::commctrl::{TBS_AUTOTICKS, TBS_VERT, TBS_HORZ, TBS_TOP, TBS_BOTTOM, TBS_LEFT, TBS_RIGHT, TBS_NOTICKS, TBS_ENABLESELRANGE};
+use winapi::um::commctrl::{TBS_AUTOTICKS, TBS_VERT, TBS_HORZ, TBS_TOP, TBS_BOTTOM, TBS_LEFT, TBS_RIGHT, TBS_NOTICKS, TBS_ENABLESELRANGE, TBS_TOOLTIPS};
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::check_hwnd;
 use crate::{NwgError, RawEventHandler};
@@ -29,9 +29,22 @@ bitflags! {
         const NO_TICK = TBS_NOTICKS;
         const RANGE = TBS_ENABLESELRANGE;
         const TAB_STOP = WS_TABSTOP;
+        const TOOLTIPS = TBS_TOOLTIPS;
     }
 }
 
+/**
+    The side of the trackbar where the tooltip displaying the slider position is shown.
+    Top and Bottom are used by horizontal trackbars, Left and Right by vertical trackbars.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TrackBarTooltipSide {
+    Top,
+    Left,
+    Bottom,
+    Right,
+}
+
 /**
 A trackbar is a window that contains a slider (sometimes called a thumb) in a channel, and optional tick marks.
 When the user moves the slider, using either the mouse or the direction keys, the trackbar sends notification messages to indicate the change.
@@ -47,12 +60,16 @@ Requires the `trackbar` feature.
   * `range`:            The value range of the trackbar
   * `selected_range`:   The selected value range of the trackbar. Used with `TrackBarFlags::RANGE`
   * `pos`:              The current value of the trackbar
+  * `tick_frequency`:   The interval between the tick marks. Used with `TrackBarFlags::AUTO_TICK`
+  * `tooltip_side`:     Display the slider position in a tooltip on the selected side while dragging.
   * `background_color`: The background color the of the trackbar
 
 
 **Control events:**
-  * `OnVerticalScroll`: When the value of a trackbar with the VERTICAL flags is changed
-  * `OnHorizontalScroll`: When the value of a trackbar with the HORIZONTAL flags is changed
+  * `TrackBarChanged`: Each time the value of the trackbar changes, including while the slider is being dragged. Not sent for `ScrollAction::EndScroll` and `ScrollAction::ThumbPosition`. Sends `EventData::OnScroll`.
+  * `TrackBarUpdated`: When the user releases the slider
+  * `OnVerticalScroll`: When the value of a trackbar with the VERTICAL flags is changed. Sends `EventData::OnScroll`.
+  * `OnHorizontalScroll`: When the value of a trackbar with the HORIZONTAL flags is changed. Sends `EventData::OnScroll`.
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
@@ -87,6 +104,8 @@ impl TrackBar {
             pos: None,
             flags: None,
             parent: None,
+            tick_frequency: None,
+            tooltip_side: None,
             background_color: None
         }
     }
@@ -181,6 +200,75 @@ impl TrackBar {
         wh::send_message(handle, TBM_GETTIC, index as WPARAM, 0) as usize
     }
 
+    /// Sets the interval between the tick marks. A frequency of 2 displays a tick for every other value.
+    /// Only work for trackbar with the `AUTO_TICK` flags
+    pub fn set_tick_frequency(&self, frequency: usize) {
+        use winapi::um::commctrl::TBM_SETTICFREQ;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, TBM_SETTICFREQ, frequency as WPARAM, 0);
+    }
+
+    /// Retrieves the number of logical positions the slider moves in response to the arrow keys.
+    pub fn line_size(&self) -> usize {
+        use winapi::um::commctrl::TBM_GETLINESIZE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, TBM_GETLINESIZE, 0, 0) as usize
+    }
+
+    /// Sets the number of logical positions the slider moves in response to the arrow keys. The default is 1.
+    pub fn set_line_size(&self, size: usize) {
+        use winapi::um::commctrl::TBM_SETLINESIZE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, TBM_SETLINESIZE, 0, size as LPARAM);
+    }
+
+    /// Retrieves the number of logical positions the slider moves in response to the page up and page down keys
+    /// or to a mouse click in the channel.
+    pub fn page_size(&self) -> usize {
+        use winapi::um::commctrl::TBM_GETPAGESIZE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, TBM_GETPAGESIZE, 0, 0) as usize
+    }
+
+    /// Sets the number of logical positions the slider moves in response to the page up and page down keys
+    /// or to a mouse click in the channel. The default is a fifth of the range.
+    pub fn set_page_size(&self, size: usize) {
+        use winapi::um::commctrl::TBM_SETPAGESIZE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, TBM_SETPAGESIZE, 0, size as LPARAM);
+    }
+
+    /// Returns true if the trackbar displays its position in a tooltip while the slider is dragged.
+    /// Only trackbar created with the `TOOLTIPS` flags have a tooltip.
+    pub fn tooltips(&self) -> bool {
+        use winapi::um::commctrl::TBM_GETTOOLTIPS;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, TBM_GETTOOLTIPS, 0, 0) != 0
+    }
+
+    /// Sets the side of the trackbar where the position tooltip is displayed.
+    /// Only work for trackbar with the `TOOLTIPS` flags
+    pub fn set_tooltip_side(&self, side: TrackBarTooltipSide) {
+        use winapi::um::commctrl::{TBM_SETTIPSIDE, TBTS_TOP, TBTS_LEFT, TBTS_BOTTOM, TBTS_RIGHT};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let side = match side {
+            TrackBarTooltipSide::Top => TBTS_TOP,
+            TrackBarTooltipSide::Left => TBTS_LEFT,
+            TrackBarTooltipSide::Bottom => TBTS_BOTTOM,
+            TrackBarTooltipSide::Right => TBTS_RIGHT,
+        };
+
+        wh::send_message(handle, TBM_SETTIPSIDE, side, 0);
+    }
+
     //
     // Basic methods
     //
@@ -318,6 +406,8 @@ pub struct TrackBarBuilder {
     pos: Option<usize>,
     flags: Option<TrackBarFlags>,
     parent: Option<ControlHandle>,
+    tick_frequency: Option<usize>,
+    tooltip_side: Option<TrackBarTooltipSide>,
     background_color: Option<[u8; 3]>,
 }
 
@@ -363,13 +453,26 @@ impl TrackBarBuilder {
         self
     }
 
+    pub fn tick_frequency(mut self, frequency: Option<usize>) -> TrackBarBuilder {
+        self.tick_frequency = frequency;
+        self
+    }
+
+    pub fn tooltip_side(mut self, side: Option<TrackBarTooltipSide>) -> TrackBarBuilder {
+        self.tooltip_side = side;
+        self
+    }
+
     pub fn background_color(mut self, color: Option<[u8;3]>) -> TrackBarBuilder {
         self.background_color = color;
         self
     }
 
     pub fn build(self, out: &mut TrackBar) -> Result<(), NwgError> {
-        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+        let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+        if self.tooltip_side.is_some() {
+            flags |= TBS_TOOLTIPS;
+        }
 
         let parent = match self.parent {
             Some(p) => Ok(p),
@@ -385,6 +488,13 @@ impl TrackBarBuilder {
             .parent(Some(parent))
             .build()?;
 
+        if let Some(frequency) = self.tick_frequency {
+            out.set_tick_frequency(frequency);
+        }
+
+        if let Some(side) = self.tooltip_side {
+            out.set_tooltip_side(side);
+        }
 
         if self.background_color.is_some() {
             out.hook_background_color(self.background_color.unwrap());
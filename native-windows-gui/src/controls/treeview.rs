@@ -3,13 +3,15 @@ A tree-view control is a window that displays a hierarchical list of items
 */
 
 use winapi::shared::minwindef::{WPARAM, LPARAM};
+use winapi::shared::windef::HWND;
 use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
-use winapi::um::commctrl::{HIMAGELIST, HTREEITEM, TVIS_EXPANDED, TVIS_SELECTED, TVITEMW};
+use winapi::um::commctrl::{HIMAGELIST, HTREEITEM, TVIS_EXPANDED, TVIS_SELECTED, TVIS_STATEIMAGEMASK, TVITEMW, TVS_CHECKBOXES, TVS_EDITLABELS};
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::{check_hwnd, to_utf16, from_utf16};
-use crate::{Font, NwgError};
+use crate::{Font, NwgError, RawEventHandler, ItemDrag, unbind_raw_event_handler};
+use crate::win32::item_drag::{ItemDragHandler, bind_item_drag};
 use super::{ControlBase, ControlHandle};
-use std::{mem, ptr};
+use std::{mem, ptr, any::Any, cell::{Cell, RefCell}, collections::HashSet, rc::Rc};
 
 #[cfg(feature="image-list")]
 use crate::ImageList;
@@ -25,11 +27,15 @@ bitflags! {
         * VISIBLE:  The tree view is immediatly visible after creation
         * DISABLED: The tree view cannot be interacted with by the user. It also has a grayed out look.
         * TAB_STOP: The tree view can be selected using tab navigation
+        * CHECKBOXES: Display a checkbox next to the items. See `TreeView::set_check_state`
+        * EDIT_LABELS: The user can rename the items by clicking on the selected item. See `Event::OnTreeItemRenamed`
     */
     pub struct TreeViewFlags: u32 {
         const VISIBLE = WS_VISIBLE;
         const DISABLED = WS_DISABLED;
         const TAB_STOP = WS_TABSTOP;
+        const CHECKBOXES = TVS_CHECKBOXES;
+        const EDIT_LABELS = TVS_EDITLABELS;
     }
 }
 
@@ -37,13 +43,61 @@ bitflags! {
     /**
         A tree item state
 
-        * SELECTED:  The tree view is immediatly visible after creation
-        * DISABLED: The tree view cannot be interacted with by the user. It also has a grayed out look.
-        * TAB_STOP: The tree view can be selected using tab navigation
+        * SELECTED: The item is selected
+        * EXPANDED: The children of the item are visible
+        * CHECKED: The checkbox of the item is checked. See `TreeViewFlags::CHECKBOXES`
+        * PARTIAL: The checkbox of the item is partially checked
     */
     pub struct TreeItemState: u32 {
         const SELECTED = TVIS_SELECTED;
         const EXPANDED = TVIS_EXPANDED;
+        const CHECKED = 0x2000;
+        const PARTIAL = 0x4000;
+    }
+}
+
+impl TreeItemState {
+    /// Converts the raw state of a tree item. The checkbox state is stored as an index in `TVIS_STATEIMAGEMASK`.
+    pub(crate) fn from_raw(state: u32) -> TreeItemState {
+        let mut item_state = TreeItemState::from_bits_truncate(state & 0xFF);
+        match TreeCheckState::from_raw(state) {
+            Some(TreeCheckState::Checked) => item_state |= TreeItemState::CHECKED,
+            Some(TreeCheckState::Partial) => item_state |= TreeItemState::PARTIAL,
+            _ => {}
+        }
+
+        item_state
+    }
+}
+
+/// The state of the checkbox of a tree item. See `TreeViewFlags::CHECKBOXES`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TreeCheckState {
+    Unchecked,
+    Checked,
+
+    /// Some of the children are checked. Requires Comclt32.dll version >= 6.0 to be displayed.
+    Partial,
+}
+
+impl TreeCheckState {
+    fn from_raw(state: u32) -> Option<TreeCheckState> {
+        match (state & TVIS_STATEIMAGEMASK) >> 12 {
+            1 => Some(TreeCheckState::Unchecked),
+            2 => Some(TreeCheckState::Checked),
+            3 => Some(TreeCheckState::Partial),
+            _ => None
+        }
+    }
+
+    fn to_raw(&self) -> u32 {
+        let index = match self {
+            TreeCheckState::Unchecked => 1,
+            TreeCheckState::Checked => 2,
+            TreeCheckState::Partial => 3,
+        };
+
+        index << 12
     }
 }
 
@@ -105,6 +159,58 @@ impl TreeItem {
     }
 }
 
+/**
+    The items of a tree view created from a model. See `TreeView::set_model`.
+
+    `Node` identifies an item of the model (ex: a path or a database key). Each tree view item keeps
+    a copy of its node that can be read with `TreeView::item_node`.
+
+    ```rust
+    use native_windows_gui as nwg;
+    use std::path::PathBuf;
+
+    struct FileSystem;
+
+    impl nwg::TreeModel for FileSystem {
+        type Node = PathBuf;
+
+        fn children(&self, node: Option<&PathBuf>) -> Vec<PathBuf> {
+            let path = node.cloned().unwrap_or_else(|| PathBuf::from("C:\\"));
+            match std::fs::read_dir(path) {
+                Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+                Err(_) => Vec::new()
+            }
+        }
+
+        fn label(&self, node: &PathBuf) -> String {
+            node.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+        }
+
+        fn has_children(&self, node: &PathBuf) -> bool {
+            node.is_dir()
+        }
+    }
+    ```
+*/
+pub trait TreeModel {
+    /// The type of the nodes of the model
+    type Node: Clone + 'static;
+
+    /// Returns the children of `node`, or the root items if `node` is `None`.
+    /// Only called when the user expands `node` for the first time.
+    fn children(&self, node: Option<&Self::Node>) -> Vec<Self::Node>;
+
+    /// Returns the text of the item
+    fn label(&self, node: &Self::Node) -> String;
+
+    /// Returns `true` if the item can be expanded. The children are not loaded until the item is expanded.
+    fn has_children(&self, node: &Self::Node) -> bool;
+
+    /// Returns the index of the image of the item in the tree view image list
+    fn icon(&self, _node: &Self::Node) -> Option<i32> { None }
+}
+
+
 /**
 A tree-view control is a window that displays a hierarchical list of items
 
@@ -119,6 +225,7 @@ Requires the `tree-view` feature
   * `font`:       The font used for the treeview text
   * `parent`:     The treeview parent container.
   * `image_list`: Image list containing the icon to use in the tree-view
+  * `model`:      Fills the tree view with the items of a `TreeModel`. See `TreeView::set_model`
 
 **Control events:**
   * `MousePress(_)`: Generic mouse press events on the tree view
@@ -133,11 +240,26 @@ Requires the `tree-view` feature
   * `OnTreeItemExpanded`: After an item was expanded or collapsed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemChanged`: After the state of an item was changed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemSelectionChanged`: After the current selection was changed. Sends a `EventData::OnTreeItemChanged`.
+  * `OnTreeItemRenamed`: After the user renamed an item. Sends a `EventData::OnTreeItemRename`.
+  * `OnItemDrop`: When an item is dropped after a drag started in this tree view. See `TreeView::enable_item_drag`
 */
-#[derive(Default, PartialEq, Eq)]
+#[derive(Default)]
 pub struct TreeView {
-    pub handle: ControlHandle
-} 
+    pub handle: ControlHandle,
+    model: Rc<RefCell<Option<Rc<dyn TreeModelLoader>>>>,
+    drag: RefCell<Option<ItemDragHandler>>,
+    check_propagation: Rc<CheckPropagation>,
+
+    handler0: RefCell<Option<RawEventHandler>>,
+}
+
+#[derive(Default)]
+struct CheckPropagation {
+    enabled: Cell<bool>,
+
+    /// Set while the states are propagated, so that the changes made by NWG are not propagated again
+    updating: Cell<bool>,
+}
 
 
 impl TreeView {
@@ -152,7 +274,212 @@ impl TreeView {
             font: None,
             parent: None,
             image_list: None,
+            model: None,
+        }
+    }
+
+    /**
+        Removes all the items of the tree view and fills it with the root items of `model`.
+        The children of an item are loaded from the model when the item is expanded for the first time.
+
+        Items can still be inserted with `insert_item`, but they do not have a model node.
+    */
+    pub fn set_model<M: TreeModel + 'static>(&self, model: Rc<M>) {
+        let loader: Rc<dyn TreeModelLoader> = Rc::new(TreeModelBinding { model });
+        *self.model.borrow_mut() = Some(loader);
+        self.reload_model();
+    }
+
+    /// Removes the model of the tree view. The items are not removed.
+    pub fn reset_model(&self) {
+        self.model.borrow_mut().take();
+    }
+
+    /// Removes all the items of the tree view and loads the root items of the model again.
+    /// Does nothing if the tree view does not have a model.
+    pub fn reload_model(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let model = self.model.borrow().clone();
+        if let Some(model) = model {
+            self.clear();
+            model.load_children(handle, ptr::null_mut());
+        }
+    }
+
+    /// Removes the children of `item`. They are loaded again from the model when the item is expanded.
+    /// Useful when the children of the node changed in the model.
+    pub fn reload_item(&self, item: &TreeItem) {
+        use winapi::um::commctrl::{TVM_DELETEITEM, TVGN_CHILD};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let model = match self.model.borrow().clone() {
+            Some(m) => m,
+            None => { return; }
+        };
+
+        self.set_expand_state(item, ExpandState::CollapseReset);
+
+        while let Some(child) = next_treeview_item(&self.handle, TVGN_CHILD, item.handle) {
+            wh::send_message(handle, TVM_DELETEITEM, 0, child.handle as LPARAM);
         }
+
+        model.update_item(handle, item.handle);
+    }
+
+    /**
+        Allows the user to drag the items of the tree view with the mouse. The items can be dropped on any tree view or list view of the same window.
+
+        `validator` is called each time the item under the cursor changes and returns `true` if the item can be dropped there.
+        When the item is dropped, `OnItemDrop` is raised on this tree view. The item is not moved automatically, use `move_item` in the event.
+    */
+    pub fn enable_item_drag<F>(&self, validator: F)
+        where F: Fn(&ItemDrag) -> bool + 'static
+    {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.disable_item_drag();
+        *self.drag.borrow_mut() = Some(bind_item_drag(handle, Box::new(validator)));
+    }
+
+    /// Stops the drag and drop of items started by `enable_item_drag`
+    pub fn disable_item_drag(&self) {
+        if let Some(drag) = self.drag.borrow_mut().take() {
+            drag.unbind();
+        }
+    }
+
+    /**
+        Moves an item and its children under `parent` (or at the root if `parent` is `None`). The text, images, state, 
+        children count and data of the items are preserved. The old items are deleted, so `OnTreeItemDelete` is raised for them.
+
+        Returns the new item, or `None` if `parent` is `item` or one of its children.
+    */
+    pub fn move_item(&self, item: &TreeItem, parent: Option<&TreeItem>, position: TreeInsert) -> Option<TreeItem> {
+        use winapi::um::commctrl::{TVGN_PARENT, TVGN_CARET, TVM_GETNEXTITEM, TVM_SELECTITEM, TVM_DELETEITEM, TVI_FIRST, TVI_LAST, TVI_ROOT, TVI_SORT};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut ancestor = parent.map(|p| TreeItem { handle: p.handle });
+        while let Some(a) = ancestor {
+            if a.handle == item.handle {
+                return None;
+            }
+            ancestor = next_treeview_item(&self.handle, TVGN_PARENT, a.handle);
+        }
+
+        let insert = match position {
+            TreeInsert::First => TVI_FIRST,
+            TreeInsert::Last => TVI_LAST,
+            TreeInsert::Root => TVI_ROOT,
+            TreeInsert::Sort => TVI_SORT,
+            TreeInsert::After(i) => i
+        };
+
+        let selected = wh::send_message(handle, TVM_GETNEXTITEM, TVGN_CARET, 0) as HTREEITEM == item.handle;
+        let parent = parent.map(|p| p.handle).unwrap_or(ptr::null_mut());
+        let mut snapshot = take_item_snapshot(handle, item.handle);
+        wh::send_message(handle, TVM_DELETEITEM, 0, item.handle as LPARAM);
+        let new_item = restore_item_snapshot(handle, &mut snapshot, parent, insert);
+
+        if selected {
+            wh::send_message(handle, TVM_SELECTITEM, TVGN_CARET, new_item as LPARAM);
+        }
+
+        Some(TreeItem { handle: new_item })
+    }
+
+    /**
+        Sets the state of the checkbox of an item. The tree view must have the `CHECKBOXES` flag.
+        If the check propagation is enabled, the children and the parents of the item are also updated.
+    */
+    pub fn set_check_state(&self, item: &TreeItem, state: TreeCheckState) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        match self.check_propagation.enabled.get() {
+            true => propagate_check_state(handle, &self.check_propagation, item.handle, state),
+            false => set_raw_check_state(handle, item.handle, state)
+        }
+    }
+
+    /// Returns the state of the checkbox of an item. Returns `None` if the item is not in the tree view or if the tree view does not have checkboxes.
+    pub fn check_state(&self, item: &TreeItem) -> Option<TreeCheckState> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        raw_check_state(handle, item.handle)
+    }
+
+    /**
+        If enabled, checking or unchecking an item also checks or unchecks all its children, and the parents
+        are set to `Partial` if only some of their children are checked. Disabled by default.
+
+        This also applies to the changes made by the user.
+    */
+    pub fn set_check_propagation(&self, enabled: bool) {
+        self.check_propagation.enabled.set(enabled);
+    }
+
+    /// Returns `true` if the check propagation is enabled
+    pub fn check_propagation(&self) -> bool {
+        self.check_propagation.enabled.get()
+    }
+
+    /// Starts the edition of the text of an item. `OnTreeItemRenamed` is raised if the user accepts the edition.
+    /// The tree view must have the `EDIT_LABELS` flag.
+    pub fn edit_item(&self, item: &TreeItem) {
+        use winapi::um::commctrl::TVM_EDITLABELW;
+        use winapi::um::winuser::SetFocus;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { SetFocus(handle); }
+        wh::send_message(handle, TVM_EDITLABELW, 0, item.handle as LPARAM);
+    }
+
+    /// Ends the edition of the text of an item. If `cancel` is `true`, the new text is discarded.
+    pub fn end_edit(&self, cancel: bool) {
+        use winapi::um::commctrl::TVM_ENDEDITLABELNOW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, TVM_ENDEDITLABELNOW, cancel as WPARAM, 0);
+    }
+
+    /**
+        Stores a value in an item. The previous value is dropped. The value is dropped when the item is removed from the tree view.
+
+        The model node of items created by a `TreeModel` is stored the same way, so replacing it breaks the lazy loading of the item.
+    */
+    pub fn set_item_data<T: Any>(&self, item: &TreeItem, data: T) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { free_item_data(replace_item_data(handle, item.handle, into_item_data(Box::new(data)))); }
+    }
+
+    /// Returns a copy of the value stored in an item with `set_item_data`.
+    /// Returns `None` if the item has no value or if the value is not a `T`.
+    pub fn item_data<T: Clone + 'static>(&self, item: &TreeItem) -> Option<T> {
+        self.with_item_data(item, |data: &T| data.clone())
+    }
+
+    /// Calls `f` with a reference to the value stored in an item with `set_item_data` and returns its result.
+    /// Returns `None` if the item has no value or if the value is not a `T`.
+    pub fn with_item_data<T: Any, R, F: FnOnce(&T) -> R>(&self, item: &TreeItem, f: F) -> Option<R> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let data = unsafe { item_any_data(handle, item.handle)? };
+        let result = data.downcast_ref::<T>().map(f);
+        result
+    }
+
+    /// Removes the value stored in an item and returns it.
+    /// If the value is borrowed by `with_item_data`, it is removed from the item and dropped once it is released, and `None` is returned.
+    pub fn take_item_data(&self, item: &TreeItem) -> Option<Box<dyn Any>> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            take_owned_item_data(replace_item_data(handle, item.handle, 0))
+                .and_then(|data| Rc::try_unwrap(data).ok())
+        }
+    }
+
+    /// Returns a copy of the model node of `item`. Returns `None` if the item was not created by a model
+    /// or if `N` is not the node type of the model.
+    pub fn item_node<N: Clone + 'static>(&self, item: &TreeItem) -> Option<N> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { item_any_data(handle, item.handle).and_then(|d| d.downcast_ref::<N>().cloned()) }
     }
 
     /// Sets the image list of the treeview
@@ -387,6 +714,84 @@ impl TreeView {
         crate::TreeViewIterator::new(self, item.handle)
     }
 
+    /**
+        Returns the first item, in the order of `iter`, for which `predicate` returns `true`.
+        The children of the items that were never expanded in a tree view with a model are not loaded.
+
+        ```rust
+        use native_windows_gui as nwg;
+        fn find_readme(tree: &nwg::TreeView) -> Option<nwg::TreeItem> {
+            tree.find(|item| tree.item_text(item).as_deref() == Some("README.md"))
+        }
+        ```
+    */
+    #[cfg(feature="tree-view-iterator")]
+    pub fn find<F>(&self, mut predicate: F) -> Option<TreeItem>
+        where F: FnMut(&TreeItem) -> bool
+    {
+        self.iter().find(|item| predicate(item))
+    }
+
+    /// Returns every item, in the order of `iter`, for which `predicate` returns `true`
+    #[cfg(feature="tree-view-iterator")]
+    pub fn find_all<F>(&self, mut predicate: F) -> Vec<TreeItem>
+        where F: FnMut(&TreeItem) -> bool
+    {
+        self.iter().filter(|item| predicate(item)).collect()
+    }
+
+    /**
+        Returns the item at `path`, where each value is the text of an item starting from a root item.
+        If many siblings have the same text, the first one is used. Returns `None` if the path is empty or if an item does not exist.
+
+        ```rust
+        use native_windows_gui as nwg;
+        fn select_leaf(tree: &nwg::TreeView) {
+            if let Some(item) = tree.item_by_path(&["root", "child", "leaf"]) {
+                tree.select_item(&item);
+            }
+        }
+        ```
+    */
+    #[cfg(feature="tree-view-iterator")]
+    pub fn item_by_path(&self, path: &[&str]) -> Option<TreeItem> {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: Option<TreeItem> = None;
+        for &text in path {
+            let first = match item {
+                Some(ref parent) => self.first_child(parent),
+                None => self.root()
+            };
+
+            let child = std::iter::successors(first, |i| self.next_sibling(i))
+                .find(|i| self.item_text(i).as_deref() == Some(text))?;
+
+            item = Some(child);
+        }
+
+        item
+    }
+
+    /// Returns the text of `item` and of all its parents, starting from the root item. This is the inverse of `item_by_path`.
+    /// Returns `None` if the item is not in the tree view.
+    #[cfg(feature="tree-view-iterator")]
+    pub fn path_of(&self, item: &TreeItem) -> Option<Vec<String>> {
+        use winapi::um::commctrl::TVGN_PARENT;
+
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut path = vec![self.item_text(item)?];
+        let mut parent = next_treeview_item(&self.handle, TVGN_PARENT, item.handle);
+        while let Some(p) = parent {
+            path.push(self.item_text(&p)?);
+            parent = next_treeview_item(&self.handle, TVGN_PARENT, p.handle);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
     /// Returns the text of the selected item. Return None if the item is not in the tree view.
     /// The returned text value cannot be bigger than 260 characters
     pub fn item_text(&self, tree_item: &TreeItem) -> Option<String> {
@@ -439,14 +844,14 @@ impl TreeView {
         let mut item: TVITEMW = unsafe { mem::zeroed() };
         item.hItem = tree_item.handle;
         item.mask = TVIF_STATE | TVIF_HANDLE;
-        item.stateMask = 0xFF;
+        item.stateMask = 0xFF | TVIS_STATEIMAGEMASK;
         
         let result = wh::send_message(handle, TVM_GETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
         if result == 0 {
             return None;
         }
 
-        Some(TreeItemState::from_bits_truncate(item.state))
+        Some(TreeItemState::from_raw(item.state))
     }
 
     /// Expands or collapses the list of child items associated with the specified parent item, if any. 
@@ -612,9 +1017,101 @@ impl TreeView {
     }
 }
 
+impl TreeView {
+
+    /// Frees the data of the items when they are deleted and loads the children from the model when an item is expanded
+    fn bind_parent_handler(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::commctrl::{NMTREEVIEWW, NMTVITEMCHANGE, TVN_DELETEITEMW, TVN_ITEMEXPANDINGW, TVN_ITEMCHANGEDW, TVE_EXPAND, TVGN_CHILD, DefSubclassProc};
+        use winapi::um::winuser::{WM_NOTIFY, NMHDR};
+        use winapi::shared::basetsd::UINT_PTR;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let tree_handle = self.handle;
+        let model = self.model.clone();
+        let check_propagation = self.check_propagation.clone();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as UINT_PTR, move |hwnd, msg, w, l| {
+            if msg != WM_NOTIFY {
+                return None;
+            }
+
+            unsafe {
+                let nmhdr = &*(l as *const NMHDR);
+                if nmhdr.hwndFrom != handle {
+                    return None;
+                }
+
+                match nmhdr.code {
+                    TVN_DELETEITEMW => {
+                        // The data must stay alive until `OnTreeItemDelete` is processed
+                        let result = DefSubclassProc(hwnd, msg, w, l);
+                        let data = &*(l as *const NMTREEVIEWW);
+                        free_item_data(data.itemOld.lParam);
+                        Some(result)
+                    },
+                    TVN_ITEMEXPANDINGW => {
+                        let data = &*(l as *const NMTREEVIEWW);
+                        let item = data.itemNew.hItem;
+                        let model = model.borrow().clone();
+                        if let Some(model) = model {
+                            let loaded = next_treeview_item(&tree_handle, TVGN_CHILD, item).is_some();
+
+                            if data.action as usize & TVE_EXPAND == TVE_EXPAND && !loaded {
+                                model.load_children(handle, item);
+                            }
+                        }
+                        None
+                    },
+                    TVN_ITEMCHANGEDW => {
+                        let data = &*(l as *const NMTVITEMCHANGE);
+                        let old_state = TreeCheckState::from_raw(data.uStateOld);
+                        let new_state = TreeCheckState::from_raw(data.uStateNew);
+                        let propagate = check_propagation.enabled.get() && !check_propagation.updating.get();
+
+                        // The state goes from "no checkbox" to `Unchecked` when an item is inserted
+                        if propagate && old_state.is_some() && new_state.is_some() && old_state != new_state {
+                            // The user cycles through the 3 states. `Partial` cannot be selected manually.
+                            let state = match old_state {
+                                Some(TreeCheckState::Checked) => TreeCheckState::Unchecked,
+                                _ => TreeCheckState::Checked
+                            };
+
+                            propagate_check_state(handle, &check_propagation, data.hItem, state);
+                        }
+
+                        None
+                    },
+                    _ => None
+                }
+            }
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+}
+
+impl PartialEq for TreeView {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for TreeView {}
+
 impl Drop for TreeView {
     fn drop(&mut self) {
+        self.disable_item_drag();
+
+        // Destroying the tree view deletes the items, so the handler must be unbound after
         self.handle.destroy();
+
+        let handler = self.handler0.borrow_mut().take();
+        if let Some(h) = handler {
+            drop(unbind_raw_event_handler(&h));
+        }
     }
 }
 
@@ -631,6 +1128,8 @@ pub struct TreeViewBuilder<'a> {
 
     #[cfg(feature="image-list")]
     image_list: Option<&'a ImageList>,
+
+    model: Option<Rc<dyn TreeModelLoader>>,
 }
 
 
@@ -677,9 +1176,20 @@ impl<'a> TreeViewBuilder<'a> {
         self
     }
 
+    pub fn model<M: TreeModel + 'static>(mut self, model: Rc<M>) -> TreeViewBuilder<'a> {
+        self.model = Some(Rc::new(TreeModelBinding { model }));
+        self
+    }
+
     pub fn build(self, out: &mut TreeView) -> Result<(), NwgError> {
+        use winapi::um::commctrl::{TVM_SETEXTENDEDSTYLE, TVS_EX_PARTIALCHECKBOXES};
+
         let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
 
+        // The checkboxes style must be set after the control is created
+        let checkboxes = flags & TVS_CHECKBOXES == TVS_CHECKBOXES;
+        let flags = flags & !TVS_CHECKBOXES;
+
         let parent = match self.parent {
             Some(p) => Ok(p),
             None => Err(NwgError::no_parent("TreeView"))
@@ -694,14 +1204,29 @@ impl<'a> TreeViewBuilder<'a> {
             .parent(Some(parent))
             .build()?;
 
+        if checkboxes {
+            let handle = out.handle.hwnd().unwrap();
+            wh::set_style(handle, wh::get_style(handle) | TVS_CHECKBOXES);
+
+            let ex_style = TVS_EX_PARTIALCHECKBOXES as usize;
+            wh::send_message(handle, TVM_SETEXTENDEDSTYLE, ex_style, ex_style as LPARAM);
+        }
+
         if self.font.is_some() {
             out.set_font(self.font);
         } else {
             out.set_font(Font::global_default().as_ref());
         }
 
+        out.bind_parent_handler();
+
         builder_set_image_list(&self, out);
 
+        if let Some(model) = self.model {
+            *out.model.borrow_mut() = Some(model);
+            out.reload_model();
+        }
+
         if self.focus {
             out.set_focus();
         }
@@ -723,6 +1248,309 @@ impl PartialEq for TreeItem {
 impl Eq for TreeItem {}
 
 
+/// Loads the items of a `TreeModel` in a tree view. Hides the `Node` type of the model.
+trait TreeModelLoader {
+    /// Inserts the children of `parent` in the tree view. If `parent` is null, the root items are inserted.
+    fn load_children(&self, tree: HWND, parent: HTREEITEM);
+
+    /// Updates the text, icon and children flag of an item from its node
+    fn update_item(&self, tree: HWND, item: HTREEITEM);
+}
+
+struct TreeModelBinding<M: TreeModel> {
+    model: Rc<M>
+}
+
+impl<M: TreeModel> TreeModelLoader for TreeModelBinding<M> {
+
+    fn load_children(&self, tree: HWND, parent: HTREEITEM) {
+        use winapi::um::commctrl::{TVM_INSERTITEMW, TVM_SETITEMW, TVINSERTSTRUCTW, TVINSERTSTRUCTW_u, TVI_LAST, TVIF_CHILDREN};
+
+        let node = match parent.is_null() {
+            true => None,
+            false => match unsafe { item_any_data(tree, parent).and_then(|d| d.downcast_ref::<M::Node>().cloned()) } {
+                Some(node) => Some(node),
+                None => { return; }
+            }
+        };
+
+        let children = self.model.children(node.as_ref());
+        if children.is_empty() && !parent.is_null() {
+            // Removes the expand button
+            let mut item = blank_item();
+            item.mask = TVIF_CHILDREN;
+            item.hItem = parent;
+            wh::send_message(tree, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+            return;
+        }
+
+        for child in children {
+            let mut text = to_utf16(&self.model.label(&child));
+            let mut item: TVINSERTSTRUCTW_u = unsafe { mem::zeroed() };
+            fill_model_item(unsafe { item.item_mut() }, &*self.model, &child, &mut text);
+            unsafe { item.item_mut().lParam = into_item_data(Box::new(child)); }
+
+            let insert = TVINSERTSTRUCTW {
+                hParent: parent,
+                hInsertAfter: TVI_LAST,
+                u: item
+            };
+
+            wh::send_message(tree, TVM_INSERTITEMW, 0, &insert as *const TVINSERTSTRUCTW as LPARAM);
+        }
+    }
+
+    fn update_item(&self, tree: HWND, item_handle: HTREEITEM) {
+        use winapi::um::commctrl::TVM_SETITEMW;
+
+        let node = match unsafe { item_any_data(tree, item_handle).and_then(|d| d.downcast_ref::<M::Node>().cloned()) } {
+            Some(node) => node,
+            None => { return; }
+        };
+
+        let mut text = to_utf16(&self.model.label(&node));
+        let mut item = blank_item();
+        fill_model_item(&mut item, &*self.model, &node, &mut text);
+        item.hItem = item_handle;
+
+        wh::send_message(tree, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+    }
+
+}
+
+/// Sets the text, image and children count of a tree item from a model node. `text` must outlive the item.
+fn fill_model_item<M: TreeModel>(item: &mut TVITEMW, model: &M, node: &M::Node, text: &mut Vec<u16>) {
+    use winapi::um::commctrl::{TVIF_TEXT, TVIF_CHILDREN, TVIF_IMAGE, TVIF_SELECTEDIMAGE};
+
+    item.mask |= TVIF_TEXT | TVIF_CHILDREN;
+    item.pszText = text.as_mut_ptr();
+    item.cChildren = model.has_children(node) as _;
+
+    if let Some(icon) = model.icon(node) {
+        item.mask |= TVIF_IMAGE | TVIF_SELECTEDIMAGE;
+        item.iImage = icon;
+        item.iSelectedImage = icon;
+    }
+}
+
+/// A copy of a tree item and its children. Owns the data of the items.
+struct ItemSnapshot {
+    text: Vec<u16>,
+    item: TVITEMW,
+    children: Vec<ItemSnapshot>,
+}
+
+impl Drop for ItemSnapshot {
+    fn drop(&mut self) {
+        unsafe { free_item_data(self.item.lParam); }
+    }
+}
+
+/// Copies an item and its children. The data of the items is moved into the snapshot, so the item should be deleted afterward.
+fn take_item_snapshot(tree: HWND, item_handle: HTREEITEM) -> ItemSnapshot {
+    use winapi::um::commctrl::{TVM_GETITEMW, TVM_SETITEMW, TVM_GETNEXTITEM, TVIF_TEXT, TVIF_IMAGE, TVIF_SELECTEDIMAGE, TVIF_STATE,
+        TVIF_CHILDREN, TVIF_PARAM, TVIF_HANDLE, TVGN_CHILD, TVGN_NEXT};
+
+    // The tree view does not give the text length, so the buffer grows until the text is not truncated
+    let mut size = 260;
+    let mut item = blank_item();
+    let text = loop {
+        let mut text: Vec<u16> = vec![0; size];
+        item.mask = TVIF_TEXT | TVIF_IMAGE | TVIF_SELECTEDIMAGE | TVIF_STATE | TVIF_CHILDREN | TVIF_PARAM | TVIF_HANDLE;
+        item.hItem = item_handle;
+        item.stateMask = !0;
+        item.pszText = text.as_mut_ptr();
+        item.cchTextMax = size as _;
+        wh::send_message(tree, TVM_GETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+
+        let len = text.iter().position(|&c| c == 0).unwrap_or(size);
+        if len + 1 < size {
+            break text;
+        }
+
+        size *= 2;
+    };
+
+    // The data now belongs to the snapshot
+    let mut old_item = blank_item();
+    old_item.mask = TVIF_PARAM | TVIF_HANDLE;
+    old_item.hItem = item_handle;
+    wh::send_message(tree, TVM_SETITEMW, 0, &mut old_item as *mut TVITEMW as LPARAM);
+
+    let mut children = Vec::new();
+    let mut child = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_CHILD, item_handle as LPARAM) as HTREEITEM;
+    while !child.is_null() {
+        children.push(take_item_snapshot(tree, child));
+        child = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_NEXT, child as LPARAM) as HTREEITEM;
+    }
+
+    ItemSnapshot { text, item, children }
+}
+
+/// Inserts the items of a snapshot under `parent`. The data of the items is moved back into the tree view. Returns the new item.
+fn restore_item_snapshot(tree: HWND, snapshot: &mut ItemSnapshot, parent: HTREEITEM, insert_after: HTREEITEM) -> HTREEITEM {
+    use winapi::um::commctrl::{TVM_INSERTITEMW, TVM_EXPAND, TVINSERTSTRUCTW, TVINSERTSTRUCTW_u, TVIF_HANDLE, TVIS_EXPANDEDONCE, TVE_EXPAND, TVI_LAST};
+
+    // The item can only be expanded once it has children
+    let expanded = snapshot.item.state & TVIS_EXPANDED == TVIS_EXPANDED;
+
+    let mut new_item: TVINSERTSTRUCTW_u = unsafe { mem::zeroed() };
+    unsafe {
+        let i = new_item.item_mut();
+        *i = snapshot.item;
+        i.mask &= !TVIF_HANDLE;
+        i.hItem = ptr::null_mut();
+        i.pszText = snapshot.text.as_mut_ptr();
+        i.state &= !(TVIS_EXPANDED | TVIS_EXPANDEDONCE);
+        i.stateMask = !(TVIS_EXPANDED | TVIS_EXPANDEDONCE);
+    }
+
+    let insert = TVINSERTSTRUCTW { hParent: parent, hInsertAfter: insert_after, u: new_item };
+    let new_handle = wh::send_message(tree, TVM_INSERTITEMW, 0, &insert as *const TVINSERTSTRUCTW as LPARAM) as HTREEITEM;
+    if new_handle.is_null() {
+        return new_handle;
+    }
+
+    snapshot.item.lParam = 0;
+
+    for child in snapshot.children.iter_mut() {
+        restore_item_snapshot(tree, child, new_handle, TVI_LAST);
+    }
+
+    if expanded {
+        wh::send_message(tree, TVM_EXPAND, TVE_EXPAND as WPARAM, new_handle as LPARAM);
+    }
+
+    new_handle
+}
+
+fn raw_check_state(tree: HWND, item_handle: HTREEITEM) -> Option<TreeCheckState> {
+    use winapi::um::commctrl::TVM_GETITEMSTATE;
+
+    let state = wh::send_message(tree, TVM_GETITEMSTATE, item_handle as WPARAM, TVIS_STATEIMAGEMASK as LPARAM) as u32;
+    TreeCheckState::from_raw(state)
+}
+
+fn set_raw_check_state(tree: HWND, item_handle: HTREEITEM, state: TreeCheckState) {
+    use winapi::um::commctrl::{TVM_SETITEMW, TVIF_STATE, TVIF_HANDLE};
+
+    let mut item = blank_item();
+    item.mask = TVIF_STATE | TVIF_HANDLE;
+    item.hItem = item_handle;
+    item.stateMask = TVIS_STATEIMAGEMASK;
+    item.state = state.to_raw();
+
+    wh::send_message(tree, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+}
+
+/// Sets the state of an item and its children, then updates the state of its parents
+fn propagate_check_state(tree: HWND, propagation: &CheckPropagation, item_handle: HTREEITEM, state: TreeCheckState) {
+    use winapi::um::commctrl::{TVM_GETNEXTITEM, TVGN_CHILD, TVGN_NEXT, TVGN_PARENT};
+
+    fn set_children(tree: HWND, item_handle: HTREEITEM, state: TreeCheckState) {
+        set_raw_check_state(tree, item_handle, state);
+
+        let mut child = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_CHILD, item_handle as LPARAM) as HTREEITEM;
+        while !child.is_null() {
+            set_children(tree, child, state);
+            child = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_NEXT, child as LPARAM) as HTREEITEM;
+        }
+    }
+
+    propagation.updating.set(true);
+
+    set_children(tree, item_handle, state);
+
+    let mut parent = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_PARENT, item_handle as LPARAM) as HTREEITEM;
+    while !parent.is_null() {
+        let (mut checked, mut unchecked) = (false, false);
+        let mut child = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_CHILD, parent as LPARAM) as HTREEITEM;
+        while !child.is_null() {
+            match raw_check_state(tree, child) {
+                Some(TreeCheckState::Checked) => { checked = true; },
+                Some(TreeCheckState::Unchecked) => { unchecked = true; },
+                _ => { checked = true; unchecked = true; }
+            }
+            child = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_NEXT, child as LPARAM) as HTREEITEM;
+        }
+
+        let parent_state = match (checked, unchecked) {
+            (true, false) => TreeCheckState::Checked,
+            (false, _) => TreeCheckState::Unchecked,
+            (true, true) => TreeCheckState::Partial,
+        };
+
+        set_raw_check_state(tree, parent, parent_state);
+        parent = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_PARENT, parent as LPARAM) as HTREEITEM;
+    }
+
+    propagation.updating.set(false);
+}
+
+/// Sets the data of an item and returns the previous data without freeing it
+unsafe fn replace_item_data(tree: HWND, item_handle: HTREEITEM, data: LPARAM) -> LPARAM {
+    use winapi::um::commctrl::{TVM_GETITEMW, TVM_SETITEMW, TVIF_PARAM, TVIF_HANDLE};
+
+    let mut item = blank_item();
+    item.mask = TVIF_PARAM | TVIF_HANDLE;
+    item.hItem = item_handle;
+    wh::send_message(tree, TVM_GETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+
+    let old_data = item.lParam;
+    item.lParam = data;
+    if wh::send_message(tree, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM) == 0 {
+        free_item_data(data);
+        return 0;
+    }
+
+    old_data
+}
+
+thread_local! {
+    /// The `TVITEMW::lParam` values created by `into_item_data`. The `lParam` of items inserted by another code is never read as item data.
+    static ITEM_DATA: RefCell<HashSet<LPARAM>> = RefCell::new(HashSet::new());
+}
+
+/// Converts the data of an item into a value that can be stored in `TVITEMW::lParam`. Freed with `free_item_data`.
+fn into_item_data(data: Box<dyn Any>) -> LPARAM {
+    let data = Rc::into_raw(Rc::new(data)) as LPARAM;
+    ITEM_DATA.with(|d| d.borrow_mut().insert(data));
+    data
+}
+
+/// Takes back the ownership of the data created by `into_item_data`. Returns `None` if `data` was not created by `into_item_data`.
+unsafe fn take_owned_item_data(data: LPARAM) -> Option<Rc<Box<dyn Any>>> {
+    match ITEM_DATA.with(|d| d.borrow_mut().remove(&data)) {
+        true => Some(Rc::from_raw(data as *const Box<dyn Any>)),
+        false => None
+    }
+}
+
+/// Frees the data created by `into_item_data`. Does nothing for any other value.
+unsafe fn free_item_data(data: LPARAM) {
+    drop(take_owned_item_data(data));
+}
+
+/// Returns the data of an item stored with `into_item_data`
+unsafe fn item_any_data(tree: HWND, item_handle: HTREEITEM) -> Option<Rc<Box<dyn Any>>> {
+    use winapi::um::commctrl::{TVM_GETITEMW, TVIF_PARAM, TVIF_HANDLE};
+
+    let mut item = blank_item();
+    item.mask = TVIF_PARAM | TVIF_HANDLE;
+    item.hItem = item_handle;
+
+    if wh::send_message(tree, TVM_GETITEMW, 0, &mut item as *mut TVITEMW as LPARAM) == 0 || item.lParam == 0 {
+        return None;
+    }
+
+    if !ITEM_DATA.with(|d| d.borrow().contains(&item.lParam)) {
+        return None;
+    }
+
+    let data = mem::ManuallyDrop::new(Rc::from_raw(item.lParam as *const Box<dyn Any>));
+    Some(Rc::clone(&data))
+}
+
 fn next_treeview_item(handle: &ControlHandle, action: usize, item: HTREEITEM) -> Option<TreeItem> {
     use winapi::um::commctrl::TVM_GETNEXTITEM;
 
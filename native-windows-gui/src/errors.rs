@@ -37,6 +37,18 @@ pub enum NwgError {
     /// Error raised by one of the locale functions
     #[cfg(feature = "winnls")]
     BadLocale(String),
+
+    /// Error raised when an event log could not be read or replayed
+    #[cfg(feature = "event-recorder")]
+    EventLogError(String),
+
+    /// Error raised when a RTF document could not be read or loaded in a rich text box
+    #[cfg(feature = "rich-textbox")]
+    RtfError(String),
+
+    /// Error raised when a date or a time is not valid or cannot be converted
+    #[cfg(any(feature = "datetime-picker", feature = "month-calendar"))]
+    DateTimeError(String),
 }
 
 impl NwgError {
@@ -75,6 +87,21 @@ impl NwgError {
         NwgError::BadLocale(e.into())
     }
 
+    #[cfg(feature = "event-recorder")]
+    pub fn event_log<S: Into<String>>(e: S) -> NwgError {
+        NwgError::EventLogError(e.into())
+    }
+
+    #[cfg(feature = "rich-textbox")]
+    pub fn rtf<S: Into<String>>(e: S) -> NwgError {
+        NwgError::RtfError(e.into())
+    }
+
+    #[cfg(any(feature = "datetime-picker", feature = "month-calendar"))]
+    pub fn date_time<S: Into<String>>(e: S) -> NwgError {
+        NwgError::DateTimeError(e.into())
+    }
+
     #[cfg(feature = "image-decoder")]
     pub fn image_decoder<S: Into<String>>(code: i32, e: S) -> NwgError {
         NwgError::ImageDecoderError(code, e.into())
@@ -111,6 +138,15 @@ impl fmt::Display for NwgError {
 
             #[cfg(feature = "winnls")]
             BadLocale(reason) => write!(f, "Windows locale functions failed: {:?}", reason),
+
+            #[cfg(feature = "event-recorder")]
+            EventLogError(reason) => write!(f, "Event log error: {:?}", reason),
+
+            #[cfg(feature = "rich-textbox")]
+            RtfError(reason) => write!(f, "RTF error: {:?}", reason),
+
+            #[cfg(any(feature = "datetime-picker", feature = "month-calendar"))]
+            DateTimeError(reason) => write!(f, "Date time error: {:?}", reason),
         }
         
     }
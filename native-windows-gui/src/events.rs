@@ -10,215 +10,326 @@ pub enum MousePressEvent {
     MousePressRightDown
 }
 
-/// Events are identifier that are sent by controls on user interaction
-/// Some events also have data that can be further processed by the event loop. See `EventData`
+/// The user action that moved a scroll bar or a trackbar. See `EventData::OnScroll`.
+/// The up actions also move horizontal bars to the left, and the down actions to the right.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[repr(usize)]
-pub enum Event {
-    /// Undefined / not implemented event. This can be dispatched by the bigger controls such as ListView and TreeView
+pub enum ScrollAction {
+    /// One step up (arrow button or arrow key)
+    LineUp,
+
+    /// One step down (arrow button or arrow key)
+    LineDown,
+
+    /// One page up (click in the channel or page key)
+    PageUp,
+
+    /// One page down (click in the channel or page key)
+    PageDown,
+
+    /// The thumb is being dragged. Sent for each move of the mouse.
+    ThumbTrack,
+
+    /// The thumb was released after being dragged
+    ThumbPosition,
+
+    /// Moved to the minimum (home key)
+    Top,
+
+    /// Moved to the maximum (end key)
+    Bottom,
+
+    /// The user released the mouse button or the key. Sent after the other actions.
+    EndScroll,
+
+    /// An action not defined by the system
     Unknown,
+}
+
+/// Declares the `Event` enum and `Event::from_name`, so that the events without a value can be found from their name
+macro_rules! events {
+    ($(#[$attr:meta])* pub enum Event { $($(#[$variant_attr:meta])* $variant:ident $(($value:ty))?,)* }) => {
+        $(#[$attr])*
+        pub enum Event {
+            $($(#[$variant_attr])* $variant $(($value))?,)*
+        }
+
+        impl Event {
+            /// Returns the event without a value named `name`. Ex: `OnButtonClick`
+            #[cfg(feature="event-recorder")]
+            pub(crate) fn from_name(name: &str) -> Option<Event> {
+                $(events!(@from_name name, $variant $(, $value)?);)*
+                None
+            }
+        }
+    };
+    (@from_name $name:ident, $variant:ident) => {
+        if $name == stringify!($variant) { return Some(Event::$variant); }
+    };
+    (@from_name $name:ident, $variant:ident, $value:ty) => {};
+}
+
+events! {
+    /// Events are identifier that are sent by controls on user interaction
+    /// Some events also have data that can be further processed by the event loop. See `EventData`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[repr(usize)]
+    pub enum Event {
+        /// Undefined / not implemented event. This can be dispatched by the bigger controls such as ListView and TreeView
+        Unknown,
+
+        /// Generic mouse press events that can be generated by most window controls
+        OnMousePress(MousePressEvent),
+
+        /// Generic mouse move event that can be generated by most window controls
+        OnMouseMove,
+
+        /// Generic mouse wheel event that be generated by most window controls
+        /// Read the delta value with `EventData::OnMouseWheel` to check which key.
+        OnMouseWheel,
+
+        /// Generic window event when the user right click a window
+        OnContextMenu,
+
+        /// When a top level window control is created.
+        OnInit,
+
+        /// When a control needs to be redrawn
+        OnPaint,
 
-    /// Generic mouse press events that can be generated by most window controls
-    OnMousePress(MousePressEvent),
+        /// When a key is pressed on a keyboard. Unlike OnKeyDown, this returns a char (ex: 'c') in a EventData::OnChar.
+        OnChar,
 
-    /// Generic mouse move event that can be generated by most window controls
-    OnMouseMove,
+        /// When a key is pressed on a keyboard. Use `EventData::OnKey` to check which key.
+        OnKeyPress,
 
-    /// Generic mouse wheel event that be generated by most window controls
-    /// Read the delta value with `EventData::OnMouseWheel` to check which key.
-    OnMouseWheel,
+        /// When a key is pressed on a keyboard.Use EventData::OnKey to check which key.
+        OnKeyRelease,
 
-    /// Generic window event when the user right click a window
-    OnContextMenu,
+        /// When a control is resized by the user. 
+        /// This is typically applied to top level windows but it also applies to children when layouts are used.
+        OnResize,
 
-    /// When a top level window control is created.
-    OnInit,
+        /// When a control is about to be resized by the user. 
+        /// This does not triggers on maximize
+        OnResizeBegin,
 
-    /// When a control needs to be redrawn
-    OnPaint,
+        /// When a control stops being resized
+        /// This does not triggers on maximize
+        OnResizeEnd,
 
-    /// When a key is pressed on a keyboard. Unlike OnKeyDown, this returns a char (ex: 'c') in a EventData::OnChar.
-    OnChar,
+        // When a window control is maximized
+        OnWindowMaximize,
 
-    /// When a key is pressed on a keyboard. Use `EventData::OnKey` to check which key.
-    OnKeyPress,
+        // When a window control is minimized
+        OnWindowMinimize,
 
-    /// When a key is pressed on a keyboard.Use EventData::OnKey to check which key.
-    OnKeyRelease,
-    
-    /// When a control is resized by the user. 
-    /// This is typically applied to top level windows but it also applies to children when layouts are used.
-    OnResize,
+        /// When a control is moved by the user. This is typically applied to top level windows.
+        /// This is typically applied to top level windows but it also applies to children when layouts are used.
+        OnMove,
 
-    /// When a control is about to be resized by the user. 
-    /// This does not triggers on maximize
-    OnResizeBegin,
+        /// When a bar like control value is changed, including while the thumb is dragged.
+        /// Generates a `EventData::OnScroll`.
+        OnVerticalScroll,
 
-    /// When a control stops being resized
-    /// This does not triggers on maximize
-    OnResizeEnd,
+        /// When a bar like control value is changed, including while the thumb is dragged.
+        /// Generates a `EventData::OnScroll`.
+        OnHorizontalScroll,
 
-    // When a window control is maximized
-    OnWindowMaximize,
+        /// When a file is dropped into a a control
+        OnFileDrop,
 
-    // When a window control is minimized
-    OnWindowMinimize,
+        /// When a button is clicked. Similar to a MouseUp event, but only for button control
+        OnButtonClick,
 
-    /// When a control is moved by the user. This is typically applied to top level windows.
-    /// This is typically applied to top level windows but it also applies to children when layouts are used.
-    OnMove,
+        /// When a button is clicked twice rapidly
+        OnButtonDoubleClick,
 
-    /// When a bar like control value is changed.
-    OnVerticalScroll,
+        /// When a label is clicked
+        OnLabelClick,
 
-    /// When a bar like control value is changed.
-    OnHorizontalScroll,
+        /// When a label is clicked twice rapidly
+        OnLabelDoubleClick,
 
-    /// When a file is dropped into a a control
-    OnFileDrop,
+        /// When a ImageFrame is clicked
+        OnImageFrameClick,
 
-    /// When a button is clicked. Similar to a MouseUp event, but only for button control
-    OnButtonClick,
+        /// When a ImageFrame is clicked twice rapidly
+        OnImageFrameDoubleClick,
 
-    /// When a button is clicked twice rapidly
-    OnButtonDoubleClick,
+        /// When a TextInput value is changed
+        OnTextInput,
 
-    /// When a label is clicked
-    OnLabelClick,
+        /// When the text of a TextInput with a validator becomes valid or invalid. See `TextInput::is_valid`.
+        /// Generates a `EventData::OnValidationChanged`.
+        OnValidationChanged,
 
-    /// When a label is clicked twice rapidly
-    OnLabelDoubleClick,
+        /// When the user changes the value of a NumberSelect with the buttons, the keyboard, the mouse wheel or by typing a new value.
+        /// Generates a `EventData::OnNumberSelectChanged`.
+        OnNumberSelectChanged,
 
-    /// When a ImageFrame is clicked
-    OnImageFrameClick,
+        /// When the list of a combobox is closed
+        OnComboBoxClosed,
 
-    /// When a ImageFrame is clicked twice rapidly
-    OnImageFrameDoubleClick,
+        /// When the list of a combobox is about to be visible
+        OnComboBoxDropdown,
 
-    /// When a TextInput value is changed
-    OnTextInput,
+        /// When the current selection of the combobox was changed
+        OnComboxBoxSelection,
 
-    /// When the list of a combobox is closed
-    OnComboBoxClosed,
+        /// When the user changed the text of an editable combobox
+        OnComboBoxTextChanged,
 
-    /// When the list of a combobox is about to be visible
-    OnComboBoxDropdown,
+        /// When the date select dropdown is expanded
+        OnDatePickerDropdown,
 
-    /// When the current selection of the combobox was changed
-    OnComboxBoxSelection,
+        /// When the date select dropdown is closed
+        OnDatePickerClosed,
 
-    /// When the date select dropdown is expanded
-    OnDatePickerDropdown,
+        /// When the value of the date select is changed
+        OnDatePickerChanged,
 
-    /// When the date select dropdown is closed
-    OnDatePickerClosed,
+        /// When the user selects a day (or a range of days) in a month calendar
+        OnCalendarSelect,
 
-    /// When the value of the date select is changed
-    OnDatePickerChanged,
+        /// When a month calendar switches between the month, year, decade and century views
+        OnCalendarViewChange,
 
-    /// When an item on a list box is clicked twice
-    OnListBoxDoubleClick,
+        /// When an item on a list box is clicked twice
+        OnListBoxDoubleClick,
 
-    /// When an item on a list box is selected
-    OnListBoxSelect,
+        /// When an item on a list box is selected
+        OnListBoxSelect,
 
-    /// The select tab of a TabsContainer changed
-    TabsContainerChanged,
+        /// The select tab of a TabsContainer changed
+        TabsContainerChanged,
 
-    /// The select tab of a TabsContainer is about to be changed
-    TabsContainerChanging,
+        /// The select tab of a TabsContainer is about to be changed
+        TabsContainerChanging,
 
-    /// When the trackbar thumb is released by the user
-    TrackBarUpdated,
+        /// When the trackbar thumb is released by the user
+        TrackBarUpdated,
 
-    /// When a menu control is opened
-    OnMenuOpen,
+        /// When the position of a trackbar changes, including while the thumb is dragged.
+        /// Generates a `EventData::OnScroll`.
+        TrackBarChanged,
 
-    /// When a menu is hovered (either through mouse or keyboard)
-    OnMenuHover,
+        /// When a menu control is opened
+        OnMenuOpen,
 
-    /// When the user selects on a menu item
-    OnMenuItemSelected,
+        /// When a menu is hovered (either through mouse or keyboard)
+        OnMenuHover,
 
-    /// When the user hovers over a callback tooltip
-    /// The callback will also receive a `EventData::OnTooltipText`
-    OnTooltipText,
+        /// When the user selects on a menu item
+        OnMenuItemSelected,
 
-    /// When the user has clicked the left mouse button within the control.
-    OnTreeViewClick,
+        /// When the user hovers over a callback tooltip
+        /// The callback will also receive a `EventData::OnTooltipText`
+        OnTooltipText,
 
-    /// When the user has clicked the left mouse button within the control twice rapidly.
-    OnTreeViewDoubleClick,
+        /// When the user has clicked the left mouse button within the control.
+        OnTreeViewClick,
 
-    /// When the user has clicked the right mouse button within the control.
-    OnTreeViewRightClick,
+        /// When the user has clicked the left mouse button within the control twice rapidly.
+        OnTreeViewDoubleClick,
 
-    /// When the control has lost the input focus
-    OnTreeFocusLost,
+        /// When the user has clicked the right mouse button within the control.
+        OnTreeViewRightClick,
 
-    /// When the control has acquired the input focus
-    OnTreeFocus,
+        /// When the control has lost the input focus
+        OnTreeFocusLost,
 
-    /// When an item is removed from the treeview. The item being deleted is passed in `EventData::OnTreeItemDelete`
-    OnTreeItemDelete,
+        /// When the control has acquired the input focus
+        OnTreeFocus,
 
-    /// When an item is expanded. Generates a `EventData::OnTreeItemDelete`
-    OnTreeItemExpanded,
+        /// When an item is removed from the treeview. The item being deleted is passed in `EventData::OnTreeItemDelete`
+        OnTreeItemDelete,
 
-    /// When the state of a tree item is changed.
-    OnTreeItemChanged,
+        /// When an item is expanded. Generates a `EventData::OnTreeItemDelete`
+        OnTreeItemExpanded,
 
-    /// When the selected tree item is changed.
-    OnTreeItemSelectionChanged,
+        /// When the state of a tree item is changed.
+        OnTreeItemChanged,
 
-    /// When all the items in a list view are destroyed
-    /// Do not add, delete, or rearrange items in the list view while processing this notification code.
-    OnListViewClear,
+        /// When the selected tree item is changed.
+        OnTreeItemSelectionChanged,
 
-    /// When an item is about to be removed from the list view
-    /// Do not add, delete, or rearrange items in the list view while processing this notification code.
-    /// Generates a `EventData::ListViewItemIndex`
-    OnListViewItemRemoved,
+        /// When the user is done editing the text of a tree item (see `TreeViewFlags::EDIT_LABELS`).
+        /// Generates a `EventData::OnTreeItemRename`. Use `TreeItemRenameData::accept(false)` to reject the new text.
+        OnTreeItemRenamed,
 
-    /// When a new item is inserted in the list view
-    /// This is only triggered when an ietm is added to a new ROW
-    OnListViewItemInsert,
+        /// When all the items in a list view are destroyed
+        /// Do not add, delete, or rearrange items in the list view while processing this notification code.
+        OnListViewClear,
 
-    /// When an item in the list view is activated by the user
-    /// An item is activated when the user click it twice
-    /// Generates a `EventData::ListViewItemIndex`
-    OnListViewItemActivated,
+        /// When an item is about to be removed from the list view
+        /// Do not add, delete, or rearrange items in the list view while processing this notification code.
+        /// Generates a `EventData::ListViewItemIndex`
+        OnListViewItemRemoved,
 
-    /// When an item is selected/unselected in the listview
-    /// See `EventData::OnListViewItemChanged` to differentiate the two
-    OnListViewItemChanged,
+        /// When a new item is inserted in the list view
+        /// This is only triggered when an ietm is added to a new ROW
+        OnListViewItemInsert,
 
-    /// When the control has acquired the input focus
-    OnListViewFocus,
+        /// When an item in the list view is activated by the user
+        /// An item is activated when the user click it twice
+        /// Generates a `EventData::ListViewItemIndex`
+        OnListViewItemActivated,
 
-    /// When the control has lost the input focus
-    OnListViewFocusLost,
+        /// When an item is selected/unselected in the listview
+        /// See `EventData::OnListViewItemChanged` to differentiate the two
+        OnListViewItemChanged,
 
-    /// When a TrayNotification info popup (not the tooltip) is shown 
-    OnTrayNotificationShow,
+        /// When the checkbox of a list view item is checked/unchecked. See `ListViewExFlags::CHECKBOXES`
+        /// Generates a `EventData::OnListViewItemChecked`
+        OnListViewItemChecked,
 
-    /// When a TrayNotification info popup (not the tooltip) is hidden 
-    OnTrayNotificationHide,
+        /// When the control has acquired the input focus
+        OnListViewFocus,
 
-    /// When a TrayNotification is closed due to a timeout
-    OnTrayNotificationTimeout,
+        /// When the control has lost the input focus
+        OnListViewFocusLost,
 
-    /// When a TrayNotification is closed due to a user click
-    OnTrayNotificationUserClose,
+        /// When the user clicks on the header of a list view column. The header is hidden by default (see `ListViewFlags::NO_HEADER`)
+        /// Generates a `EventData::OnListViewItemIndex` where `column_index` is the index of the column. `row_index` is always 0.
+        OnListViewColumnClick,
 
-    /// When a timer delay is elapsed
-    OnTimerTick,
+        /// When the user starts to edit a list view item (see `ListViewFlags::EDIT_LABELS`) or when `ListView::edit_item` is called.
+        /// Generates a `EventData::OnListViewEdit`. Use `ListViewEditData::accept(false)` to prevent the edition.
+        OnListViewBeginEdit,
 
-    /// When a notice is... noticed
-    OnNotice,
+        /// When the user is done editing a list view item.
+        /// Generates a `EventData::OnListViewEdit`. Use `ListViewEditData::accept(false)` to reject the new text.
+        OnListViewEndEdit,
 
-    /// When a user click on the X button of a window
-    OnWindowClose,
+        /// When the user drops an item of a tree view or a list view with `enable_item_drag`. Raised on the control where the drag started.
+        /// Generates a `EventData::OnItemDrop`. The item is not moved, use `TreeView::move_item` or `ListView::move_row`.
+        OnItemDrop,
+
+        /// When a TrayNotification info popup (not the tooltip) is shown 
+        OnTrayNotificationShow,
+
+        /// When a TrayNotification info popup (not the tooltip) is hidden 
+        OnTrayNotificationHide,
+
+        /// When a TrayNotification is closed due to a timeout
+        OnTrayNotificationTimeout,
+
+        /// When a TrayNotification is closed due to a user click
+        OnTrayNotificationUserClose,
+
+        /// When a timer delay is elapsed
+        OnTimerTick,
+
+        /// When a notice is... noticed
+        OnNotice,
+
+        /// When a user click on the X button of a window
+        OnWindowClose,
+
+        /// An application defined event raised with `raise_event`. The value is the id passed to `raise_event`.
+        /// Use `EventData::on_custom` to read the payload.
+        Custom(u32),
+    }
 }
 
 
@@ -251,6 +362,17 @@ pub enum EventData {
     /// The path to one or more files that were dropped in the application
     OnFileDrop(DropFiles),
 
+    /// `true` if the text of the TextInput is now valid
+    OnValidationChanged(bool),
+
+    /// The new position of a scroll bar or a trackbar and the action that moved it.
+    /// For the scroll bars of a window, the handle sent with the event is `ControlHandle::Hwnd(null)`.
+    OnScroll { position: i32, action: ScrollAction },
+
+    /// The new value of a NumberSelect
+    #[cfg(feature="number-select")]
+    OnNumberSelectChanged(crate::NumberSelectValue),
+
     /// The handle to the item being deleted. The item is still valid.
     #[cfg(feature="tree-view")]
     OnTreeItemDelete(crate::TreeItem),
@@ -263,6 +385,10 @@ pub enum EventData {
     #[cfg(feature="tree-view")]
     OnTreeItemSelectionChanged{ old: crate::TreeItem, new: crate::TreeItem },
 
+    /// The renamed tree item and its new text
+    #[cfg(feature="tree-view")]
+    OnTreeItemRename(TreeItemRenameData),
+
     /// Row index and column index of the list view item that raised the event
     #[cfg(feature="list-view")]
     OnListViewItemIndex { row_index: usize, column_index: usize },
@@ -270,6 +396,21 @@ pub enum EventData {
     /// Row index, column index, and selected state of the list view item that raised the event
     #[cfg(feature="list-view")]
     OnListViewItemChanged { row_index: usize, column_index: usize, selected: bool },
+
+    /// Row index and new checked state of the list view item that raised the event
+    #[cfg(feature="list-view")]
+    OnListViewItemChecked { row_index: usize, checked: bool },
+
+    /// The item being edited and the new text of the item
+    #[cfg(feature="list-view")]
+    OnListViewEdit(ListViewEditData),
+
+    /// The dragged item and where it was dropped
+    #[cfg(any(feature="tree-view", feature="list-view"))]
+    OnItemDrop(crate::ItemDrag),
+
+    /// The payload of an application defined event raised with `raise_event`
+    Custom(CustomEventData),
 }
 
 impl EventData {
@@ -359,6 +500,75 @@ impl EventData {
         }
     }
 
+    /// unwraps event data into the row index and the checked state of a list view item (row_index, checked)
+    #[cfg(feature="list-view")]
+    pub fn on_list_view_item_checked(&self) -> (usize, bool) {
+        match self {
+            &EventData::OnListViewItemChecked { row_index, checked } => (row_index, checked),
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// unwraps event data into the new position and the action of a scroll event
+    pub fn on_scroll(&self) -> (i32, ScrollAction) {
+        match self {
+            &EventData::OnScroll { position, action } => (position, action),
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// unwraps event data into the new valid state of a text input
+    pub fn on_validation_changed(&self) -> bool {
+        match self {
+            &EventData::OnValidationChanged(v) => v,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// unwraps event data into the new value of a number select
+    #[cfg(feature="number-select")]
+    pub fn on_number_select_changed(&self) -> crate::NumberSelectValue {
+        match self {
+            &EventData::OnNumberSelectChanged(v) => v,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// unwraps event data into the renamed tree item
+    #[cfg(feature="tree-view")]
+    pub fn on_tree_item_rename(&self) -> &TreeItemRenameData {
+        match self {
+            EventData::OnTreeItemRename(d) => d,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// unwraps event data into the edited list view item
+    #[cfg(feature="list-view")]
+    pub fn on_list_view_edit(&self) -> &ListViewEditData {
+        match self {
+            EventData::OnListViewEdit(d) => d,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// unwraps event data into a dropped item
+    #[cfg(any(feature="tree-view", feature="list-view"))]
+    pub fn on_item_drop(&self) -> &crate::ItemDrag {
+        match self {
+            EventData::OnItemDrop(d) => d,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// unwraps event data into the payload of a custom event. Panics if it's not the right type.
+    pub fn on_custom(&self) -> &CustomEventData {
+        match self {
+            EventData::Custom(d) => d,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
 }
 
 //
@@ -369,7 +579,10 @@ use winapi::um::commctrl::NMTTDISPINFOW;
 use winapi::um::winuser::{PAINTSTRUCT, BeginPaint, EndPaint};
 use winapi::um::shellapi::{HDROP, DragFinish};
 use winapi::shared::windef::HWND;
+use std::any::Any;
 use std::fmt;
+#[cfg(feature = "mock-events")]
+use std::{marker::PhantomData, ops::Deref};
 
 /// A wrapper structure that set the tooltip text on a `OnTooltipText` callback
 pub struct ToolTipTextData {
@@ -454,6 +667,121 @@ impl fmt::Debug for WindowCloseData {
 }
 
 
+/// Opaque type over a list view item edition. Sets if the edition should be accepted.
+#[cfg(feature="list-view")]
+pub struct ListViewEditData {
+    pub(crate) row_index: usize,
+    pub(crate) column_index: usize,
+    pub(crate) text: Option<String>,
+    pub(crate) accept: *mut bool,
+}
+
+#[cfg(feature="list-view")]
+impl ListViewEditData {
+
+    /// Returns the row index of the edited item
+    pub fn row_index(&self) -> usize {
+        self.row_index
+    }
+
+    /// Returns the column index of the edited item
+    pub fn column_index(&self) -> usize {
+        self.column_index
+    }
+
+    /// Returns the new text of the item in a `OnListViewEndEdit` event.
+    /// Returns `None` in a `OnListViewBeginEdit` event or if the user cancelled the edition.
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_ref().map(|t| t as &str)
+    }
+
+    /// In a `OnListViewBeginEdit` event, sets if the item can be edited.
+    /// In a `OnListViewEndEdit` event, sets if the new text should be saved in the item. Defaults to `true`.
+    pub fn accept(&self, value: bool) {
+        unsafe{ *self.accept = value; }
+    }
+
+    /// Returns true if the edition will be accepted
+    pub fn accepted(&self) -> bool {
+        unsafe{ *self.accept }
+    }
+}
+
+#[cfg(feature="list-view")]
+impl fmt::Debug for ListViewEditData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ListViewEditData {{ row_index: {}, column_index: {}, text: {:?}, accepted: {} }}", self.row_index, self.column_index, self.text, self.accepted())
+    }
+}
+
+
+/// Opaque type over a tree item rename. Sets if the new text should be accepted.
+#[cfg(feature="tree-view")]
+pub struct TreeItemRenameData {
+    pub(crate) item: crate::TreeItem,
+    pub(crate) text: String,
+    pub(crate) accept: *mut bool,
+}
+
+#[cfg(feature="tree-view")]
+impl TreeItemRenameData {
+
+    /// Returns the renamed item
+    pub fn item(&self) -> &crate::TreeItem {
+        &self.item
+    }
+
+    /// Returns the new text of the item
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Sets if the new text should be saved in the item. Defaults to `true`.
+    pub fn accept(&self, value: bool) {
+        unsafe{ *self.accept = value; }
+    }
+
+    /// Returns true if the new text will be accepted
+    pub fn accepted(&self) -> bool {
+        unsafe{ *self.accept }
+    }
+}
+
+#[cfg(feature="tree-view")]
+impl fmt::Debug for TreeItemRenameData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TreeItemRenameData {{ item: {:?}, text: {:?}, accepted: {} }}", self.item, self.text, self.accepted())
+    }
+}
+
+
+/// Opaque type over the payload of a custom event. The payload is owned by NWG and is freed
+/// once every event handler bound to the window received the event.
+pub struct CustomEventData {
+    pub(crate) payload: *const dyn Any
+}
+
+impl CustomEventData {
+
+    /// Returns a reference to the payload if it is of type `T`, or `None` otherwise.
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        unsafe { (&*self.payload).downcast_ref::<T>() }
+    }
+
+    /// Returns the payload as a `&dyn Any`
+    pub fn payload_any(&self) -> &dyn Any {
+        unsafe { &*self.payload }
+    }
+
+}
+
+impl fmt::Debug for CustomEventData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CustomEventData")
+    }
+}
+
+
 /// Opaque type over a paint event data
 #[derive(Debug)]
 pub struct PaintData {
@@ -553,3 +881,161 @@ impl Drop for DropFiles {
     }
 
 }
+
+
+/**
+    Owns the storage of synthetic event data. Used to test event handlers without creating any control.
+
+    The window close and tooltip event data point to the storage of the mock. They are returned in a `MockEventDataRef`
+    that borrows the mock, so the mock cannot be read, reset or dropped while a handler can still write to it.
+    Event data that does not wrap a system resource, such as `EventData::OnKey` or `EventData::OnChar`, can be created directly.
+
+    Requires the `mock-events` feature.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn on_tooltip(data: &nwg::EventData) {
+        data.on_tooltip_text().set_text("Hello");
+    }
+
+    let mut mock = nwg::MockEventData::new();
+    on_tooltip(&mock.tooltip_text());
+    assert_eq!(mock.tooltip_value(), "Hello");
+
+    {
+        let data = mock.window_close();
+        if let nwg::EventData::OnWindowClose(close) = &*data {
+            close.close(false);
+        }
+    }
+    assert!(!mock.closing());
+
+    let drop = mock.file_drop(&["C:\\test.txt"], [10, 20]);
+    assert_eq!(drop.on_file_drop().files(), vec!["C:\\test.txt".to_string()]);
+    ```
+*/
+#[cfg(feature = "mock-events")]
+pub struct MockEventData {
+    closing: Box<bool>,
+    tooltip: Box<NMTTDISPINFOW>,
+}
+
+#[cfg(feature = "mock-events")]
+impl MockEventData {
+
+    pub fn new() -> MockEventData {
+        MockEventData {
+            closing: Box::new(true),
+            tooltip: Box::new(unsafe { ::std::mem::zeroed() }),
+        }
+    }
+
+    /// Returns a `OnWindowClose` event data. Use `closing` to check the value set by the handler once the data is dropped.
+    pub fn window_close(&mut self) -> MockEventDataRef {
+        *self.closing = true;
+        let data = EventData::OnWindowClose(WindowCloseData { data: &mut *self.closing as *mut bool });
+        MockEventDataRef { data, mock: PhantomData }
+    }
+
+    /// Returns the value set by the handler with `WindowCloseData::close`
+    pub fn closing(&self) -> bool {
+        *self.closing
+    }
+
+    /// Returns a `OnTooltipText` event data with an empty text buffer. Use `tooltip_value` to read the text set by the handler once the data is dropped.
+    pub fn tooltip_text(&mut self) -> MockEventDataRef {
+        *self.tooltip = unsafe { ::std::mem::zeroed() };
+        let data = EventData::OnTooltipText(ToolTipTextData { data: &mut *self.tooltip as *mut NMTTDISPINFOW });
+        MockEventDataRef { data, mock: PhantomData }
+    }
+
+    /// Returns the text set by the handler with `ToolTipTextData::set_text`
+    pub fn tooltip_value(&self) -> String {
+        let text = &self.tooltip.szText;
+        let len = text.iter().position(|&c| c == 0).unwrap_or(text.len());
+        String::from_utf16_lossy(&text[..len])
+    }
+
+    /// Returns the value set by the handler with `ToolTipTextData::keep`
+    pub fn tooltip_kept(&self) -> bool {
+        use winapi::um::commctrl::TTF_DI_SETITEM;
+        self.tooltip.uFlags & TTF_DI_SETITEM == TTF_DI_SETITEM
+    }
+
+    /// Returns a `OnPaint` event data that is not associated with any window.
+    /// The window handle is null, so `BeginPaint` fails and `PaintData::begin_paint` returns a zeroed `PAINTSTRUCT`
+    /// with a null `hdc`. The handler must not draw with it.
+    pub fn paint(&self) -> EventData {
+        EventData::OnPaint(PaintData { hwnd: ::std::ptr::null_mut() })
+    }
+
+    /// Returns a `OnFileDrop` event data with the files paths and the drop point.
+    /// The files data is allocated in the same format as a drag and drop operation and freed when the event data is dropped.
+    pub fn file_drop<S: AsRef<str>>(&self, files: &[S], point: [i32; 2]) -> EventData {
+        use winapi::um::winbase::{GlobalAlloc, GlobalLock, GlobalUnlock, GHND};
+        use winapi::shared::minwindef::{BOOL, DWORD};
+        use winapi::shared::windef::POINT;
+        use std::{mem, ptr};
+
+        /// The `DROPFILES` structure, missing from winapi
+        #[repr(C)]
+        struct DropFilesHeader {
+            files_offset: DWORD,
+            pt: POINT,
+            non_client: BOOL,
+            wide: BOOL,
+        }
+
+        let mut paths: Vec<u16> = Vec::new();
+        for f in files.iter() {
+            paths.extend(f.as_ref().encode_utf16());
+            paths.push(0);
+        }
+        paths.push(0);
+
+        let header_size = mem::size_of::<DropFilesHeader>();
+        let alloc_size = header_size + paths.len() * mem::size_of::<u16>();
+
+        unsafe {
+            let alloc = GlobalAlloc(GHND, alloc_size as _);
+            let locked_ptr = GlobalLock(alloc) as *mut u8;
+            assert!(!locked_ptr.is_null());
+
+            let header = DropFilesHeader {
+                files_offset: header_size as DWORD,
+                pt: POINT { x: point[0], y: point[1] },
+                non_client: 0,
+                wide: 1,
+            };
+
+            ptr::write(locked_ptr as *mut DropFilesHeader, header);
+            ptr::copy_nonoverlapping(paths.as_ptr(), locked_ptr.add(header_size) as *mut u16, paths.len());
+            GlobalUnlock(alloc);
+
+            EventData::OnFileDrop(DropFiles { drop: alloc as HDROP })
+        }
+    }
+
+}
+
+/**
+    A mock event data that writes into the storage of a `MockEventData`.
+    Dereferences to `EventData`. The mock stays borrowed until this value is dropped.
+
+    Requires the `mock-events` feature.
+*/
+#[cfg(feature = "mock-events")]
+pub struct MockEventDataRef<'a> {
+    data: EventData,
+    mock: PhantomData<&'a mut MockEventData>,
+}
+
+#[cfg(feature = "mock-events")]
+impl<'a> Deref for MockEventDataRef<'a> {
+    type Target = EventData;
+
+    fn deref(&self) -> &EventData {
+        &self.data
+    }
+}
@@ -24,11 +24,12 @@ pub use common_types::*;
 
 pub(crate) mod win32;
 pub use win32::{
- dispatch_thread_events, dispatch_thread_events_with_callback, stop_thread_dispatch, enable_visual_styles, init_common_controls, 
+ dispatch_thread_events, dispatch_thread_events_with_callback, dispatch_thread_events_with_pacing, DispatchPacing, request_frame, stop_thread_dispatch, enable_visual_styles, init_common_controls, 
  window::{
      EventHandler, RawEventHandler,
      full_bind_event_handler, bind_event_handler, unbind_event_handler,
-     bind_raw_event_handler, has_raw_handler, unbind_raw_event_handler
+     bind_raw_event_handler, has_raw_handler, unbind_raw_event_handler,
+     raise_event
  },
  message_box::{MessageButtons, MessageIcons, MessageChoice, MessageParams, message, fatal_message, error_message, simple_message}};
 
@@ -43,6 +44,18 @@ pub use win32::cursor::GlobalCursor;
 #[cfg(feature="clipboard")]
 pub use win32::clipboard::{Clipboard, ClipboardFormat, ClipboardData};
 
+#[cfg(feature="modal-dialog")]
+pub use win32::dialog::{Dialog, DialogUi};
+
+#[cfg(feature="event-recorder")]
+pub use win32::event_recorder::{EventRecorder, EventReplay, EventLog, EventRecord, RecordedEventData};
+
+#[cfg(any(feature="tree-view", feature="list-view"))]
+pub use win32::item_drag::{ItemDrag, DragItem};
+
+#[cfg(any(feature="listbox", feature="combobox"))]
+pub use win32::owner_draw::{OwnerDrawItem, ItemRenderer};
+
 mod resources;
 pub use resources::*;
 
@@ -58,6 +71,12 @@ mod winnls;
 #[cfg(feature = "winnls")]
 pub use winnls::*;
 
+#[cfg(feature = "rich-textbox")]
+mod rtf;
+
+#[cfg(feature = "rich-textbox")]
+pub use rtf::{RtfToken, RtfWriter, tokenize_rtf, rtf_to_text};
+
 /**
     A structure that implements this trait is considered a GUI structure. The structure will hold GUI components and possibly user data.
 
@@ -117,6 +136,19 @@ pub trait NativeUi<UI> {
 }
 
 
+/**
+    A structure that implements this trait can list its controls by name. Used to register the controls of a UI in a `EventRecorder` or a `EventReplay`.
+
+    Native-windows-derive implements this trait on the structures deriving `NwgUi` (and on the generated UI structure) using the field names
+    of the controls. The controls of the partials are not included.
+*/
+pub trait NamedControls {
+
+    /// Should return the name and the handle of the controls
+    fn named_controls(&self) -> Vec<(&'static str, ControlHandle)>;
+}
+
+
 /// Initialize some application wide GUI settings.
 /// This includes default styling and common controls resources.
 pub fn init() -> std::result::Result<(), errors::NwgError> {
@@ -0,0 +1,460 @@
+/*!
+    A small reader and writer for the Rich Text Format used by `RichTextBox::rtf` and `RichTextBox::set_rtf`.
+    It does not use winapi, so RTF documents can be generated and inspected without a rich text box.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let mut writer = nwg::RtfWriter::new();
+    writer.bold(true).text("Title").bold(false).paragraph().text("Hello world");
+
+    let rtf = writer.finish();
+    assert_eq!(nwg::rtf_to_text(&rtf).unwrap(), "Title\nHello world");
+    ```
+*/
+use crate::{NwgError, ParaAlignment};
+use std::fmt::Write;
+
+/// Groups that do not contain text of the document
+const SKIPPED_DESTINATIONS: &'static [&'static str] = &[
+    "fonttbl", "colortbl", "stylesheet", "info", "pict", "header", "footer", "listtable",
+    "listoverridetable", "rsidtbl", "xmlnstbl", "themedata", "colorschememapping", "latentstyles", "datastore",
+];
+
+/// Characters 0x80 to 0x9F of the Windows-1252 code page. The other bytes are the same as Latin-1.
+const CP1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+
+/// A token of a RTF document. See `tokenize_rtf`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RtfToken {
+    /// `{`
+    GroupStart,
+
+    /// `}`
+    GroupEnd,
+
+    /// A control word and its numeric parameter (ex: `\b0` or `\par`)
+    ControlWord { name: String, param: Option<i32> },
+
+    /// A control symbol (ex: `\~` or `\*`). Escaped braces and backslashes are returned as `Text`.
+    ControlSymbol(char),
+
+    /// A byte written in hexadecimal (ex: `\'e9`)
+    Hex(u8),
+
+    /// Text of the document. Line breaks are ignored by RTF and are not included.
+    Text(String),
+}
+
+impl RtfToken {
+
+    /// Returns `true` if the token is the control word `name`
+    pub fn is_control_word(&self, name: &str) -> bool {
+        match self {
+            RtfToken::ControlWord { name: n, .. } => n == name,
+            _ => false
+        }
+    }
+
+}
+
+/**
+    Splits a RTF document into tokens. Consecutive text is merged into a single `RtfToken::Text`.
+
+    Returns an error if the groups are not balanced or if a control word or a hexadecimal value is malformed.
+*/
+pub fn tokenize_rtf(rtf: &str) -> Result<Vec<RtfToken>, NwgError> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut depth = 0usize;
+    let mut chars = rtf.char_indices().peekable();
+
+    fn push(tokens: &mut Vec<RtfToken>, text: &mut String, token: RtfToken) {
+        if !text.is_empty() {
+            tokens.push(RtfToken::Text(text.split_off(0)));
+        }
+
+        tokens.push(token);
+    }
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '{' => {
+                depth += 1;
+                push(&mut tokens, &mut text, RtfToken::GroupStart);
+            },
+            '}' => {
+                if depth == 0 {
+                    return Err(NwgError::rtf(format!("Unexpected '}}' at {}", index)));
+                }
+
+                depth -= 1;
+                push(&mut tokens, &mut text, RtfToken::GroupEnd);
+            },
+            '\r' | '\n' => {},
+            '\\' => match chars.next() {
+                Some((_, c)) if c.is_ascii_alphabetic() => {
+                    let mut name = c.to_string();
+                    while let Some(&(_, c)) = chars.peek() {
+                        if !c.is_ascii_alphabetic() { break; }
+                        name.push(c);
+                        chars.next();
+                    }
+
+                    let mut param = String::new();
+                    if let Some(&(_, '-')) = chars.peek() {
+                        param.push('-');
+                        chars.next();
+                    }
+
+                    while let Some(&(_, c)) = chars.peek() {
+                        if !c.is_ascii_digit() { break; }
+                        param.push(c);
+                        chars.next();
+                    }
+
+                    // A space after a control word is a delimiter, not text
+                    if let Some(&(_, ' ')) = chars.peek() {
+                        chars.next();
+                    }
+
+                    let param = match param.is_empty() {
+                        true => None,
+                        false => match param.parse() {
+                            Ok(p) => Some(p),
+                            Err(_) => { return Err(NwgError::rtf(format!("Bad parameter for \\{} at {}", name, index))); }
+                        }
+                    };
+
+                    push(&mut tokens, &mut text, RtfToken::ControlWord { name, param });
+                },
+                Some((_, '\'')) => {
+                    let hex: String = chars.by_ref().take(2).map(|(_, c)| c).collect();
+                    match u8::from_str_radix(&hex, 16) {
+                        Ok(byte) if hex.len() == 2 => push(&mut tokens, &mut text, RtfToken::Hex(byte)),
+                        _ => { return Err(NwgError::rtf(format!("Bad hexadecimal value at {}", index))); }
+                    }
+                },
+                Some((_, c @ '{')) | Some((_, c @ '}')) | Some((_, c @ '\\')) => {
+                    text.push(c);
+                },
+                Some((_, '\r')) | Some((_, '\n')) => {
+                    // An escaped line break is the same as `\par`
+                    push(&mut tokens, &mut text, RtfToken::ControlWord { name: "par".to_string(), param: None });
+                },
+                Some((_, c)) => {
+                    push(&mut tokens, &mut text, RtfToken::ControlSymbol(c));
+                },
+                None => {
+                    return Err(NwgError::rtf("Unexpected end of document after '\\'"));
+                }
+            },
+            c => text.push(c),
+        }
+    }
+
+    if depth != 0 {
+        return Err(NwgError::rtf(format!("{} group(s) are not closed", depth)));
+    }
+
+    if !text.is_empty() {
+        tokens.push(RtfToken::Text(text));
+    }
+
+    Ok(tokens)
+}
+
+/**
+    Returns the text of a RTF document without its formatting. Paragraphs and line breaks are separated by `\n`.
+    The font table, the color table, the stylesheet and the other groups that are not text are skipped.
+*/
+pub fn rtf_to_text(rtf: &str) -> Result<String, NwgError> {
+    #[derive(Copy, Clone)]
+    struct Group {
+        skip: bool,
+        /// The number of fallback characters written after a `\u` character (`\ucN`)
+        unicode_skip: usize,
+    }
+
+    let tokens = tokenize_rtf(rtf)?;
+
+    let mut groups = vec![Group { skip: false, unicode_skip: 1 }];
+    let mut group_start = false;
+    let mut fallback = 0usize;
+    let mut high_surrogate: Option<u16> = None;
+    let mut text = String::new();
+
+    for token in tokens.iter() {
+        let group = *groups.last().unwrap();
+        let first = group_start;
+        group_start = false;
+
+        match token {
+            RtfToken::GroupStart => {
+                groups.push(group);
+                group_start = true;
+                fallback = 0;
+                continue;
+            },
+            RtfToken::GroupEnd => {
+                groups.pop();
+                fallback = 0;
+                continue;
+            },
+            RtfToken::ControlSymbol('*') if first => {
+                groups.last_mut().unwrap().skip = true;
+                continue;
+            },
+            RtfToken::ControlWord { name, .. } if first && SKIPPED_DESTINATIONS.contains(&name.as_str()) => {
+                groups.last_mut().unwrap().skip = true;
+                continue;
+            },
+            _ if group.skip => {
+                continue;
+            },
+            _ => {}
+        }
+
+        match token {
+            RtfToken::Text(t) => {
+                let skipped = fallback.min(t.chars().count());
+                fallback -= skipped;
+                text.extend(t.chars().skip(skipped));
+            },
+            RtfToken::Hex(byte) => match fallback {
+                0 => text.push(match *byte {
+                    0x80..=0x9F => CP1252_HIGH[(*byte - 0x80) as usize],
+                    b => b as char
+                }),
+                _ => { fallback -= 1; }
+            },
+            RtfToken::ControlWord { name, param } => {
+                fallback = 0;
+
+                match (name.as_str(), *param) {
+                    ("uc", Some(count)) => { groups.last_mut().unwrap().unicode_skip = count.max(0) as usize; },
+                    ("u", Some(code)) => {
+                        // Characters outside of the BMP are written as two surrogates
+                        let unit = code as u16;
+                        let units = match high_surrogate.take() {
+                            Some(high) => vec![high, unit],
+                            None if (0xD800..0xDC00).contains(&unit) => { high_surrogate = Some(unit); vec![] },
+                            None => vec![unit],
+                        };
+
+                        text.extend(std::char::decode_utf16(units).map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER)));
+                        fallback = group.unicode_skip;
+                    },
+                    ("par", _) | ("line", _) => text.push('\n'),
+                    ("tab", _) => text.push('\t'),
+                    ("emdash", _) => text.push('—'),
+                    ("endash", _) => text.push('–'),
+                    ("bullet", _) => text.push('•'),
+                    ("lquote", _) => text.push('‘'),
+                    ("rquote", _) => text.push('’'),
+                    ("ldblquote", _) => text.push('“'),
+                    ("rdblquote", _) => text.push('”'),
+                    _ => {}
+                }
+            },
+            RtfToken::ControlSymbol('~') => text.push('\u{A0}'),
+            RtfToken::ControlSymbol('_') => text.push('\u{2011}'),
+            _ => {}
+        }
+    }
+
+    Ok(text)
+}
+
+
+/**
+    Writes a RTF document. The formatting methods apply to the text written after them.
+    Use `begin_group` and `end_group` to limit the formatting to a part of the document.
+
+    The fonts and the colors used in the document are collected in the font table and the color table by `finish`.
+*/
+#[derive(Clone, Debug, Default)]
+pub struct RtfWriter {
+    fonts: Vec<String>,
+    colors: Vec<[u8; 3]>,
+    body: String,
+}
+
+impl RtfWriter {
+
+    pub fn new() -> RtfWriter {
+        RtfWriter::default()
+    }
+
+    /// Writes text. `\n` starts a new paragraph and `\t` is written as a tab.
+    pub fn text(&mut self, text: &str) -> &mut RtfWriter {
+        escape_text(&mut self.body, text);
+        self
+    }
+
+    /// Starts a new paragraph
+    pub fn paragraph(&mut self) -> &mut RtfWriter {
+        self.control("par", None);
+        self.body.push('\n');
+        self
+    }
+
+    pub fn bold(&mut self, bold: bool) -> &mut RtfWriter {
+        self.toggle("b", bold)
+    }
+
+    pub fn italic(&mut self, italic: bool) -> &mut RtfWriter {
+        self.toggle("i", italic)
+    }
+
+    pub fn underline(&mut self, underline: bool) -> &mut RtfWriter {
+        match underline {
+            true => self.control("ul", None),
+            false => self.control("ulnone", None),
+        }
+    }
+
+    pub fn strikeout(&mut self, strikeout: bool) -> &mut RtfWriter {
+        self.toggle("strike", strikeout)
+    }
+
+    /// Sets the font family (ex: "Segoe UI")
+    pub fn font(&mut self, face: &str) -> &mut RtfWriter {
+        let index = match self.fonts.iter().position(|f| f == face) {
+            Some(index) => index,
+            None => {
+                self.fonts.push(face.to_string());
+                self.fonts.len() - 1
+            }
+        };
+
+        self.control("f", Some(index as i32))
+    }
+
+    /// Sets the size of the font in points
+    pub fn font_size(&mut self, size: f32) -> &mut RtfWriter {
+        self.control("fs", Some((size * 2.0).round() as i32))
+    }
+
+    pub fn text_color(&mut self, color: [u8; 3]) -> &mut RtfWriter {
+        let index = self.color_index(color);
+        self.control("cf", Some(index))
+    }
+
+    pub fn background_color(&mut self, color: [u8; 3]) -> &mut RtfWriter {
+        let index = self.color_index(color);
+        self.control("highlight", Some(index))
+    }
+
+    /// Sets the alignment of the current paragraph
+    pub fn align(&mut self, alignment: ParaAlignment) -> &mut RtfWriter {
+        let word = match alignment {
+            ParaAlignment::Left => "ql",
+            ParaAlignment::Center => "qc",
+            ParaAlignment::Right => "qr",
+            ParaAlignment::Justify => "qj",
+        };
+
+        self.control(word, None)
+    }
+
+    /// Starts a group. The formatting set in the group is reverted by `end_group`.
+    pub fn begin_group(&mut self) -> &mut RtfWriter {
+        self.body.push('{');
+        self
+    }
+
+    pub fn end_group(&mut self) -> &mut RtfWriter {
+        self.body.push('}');
+        self
+    }
+
+    /// Writes a control word that does not have a method in the writer (ex: `("sub", None)`)
+    pub fn control(&mut self, name: &str, param: Option<i32>) -> &mut RtfWriter {
+        self.body.push('\\');
+        self.body.push_str(name);
+        if let Some(param) = param {
+            write!(self.body, "{}", param).unwrap();
+        }
+
+        // Always ends the control word with a delimiter, so it cannot be merged with the following text
+        self.body.push(' ');
+        self
+    }
+
+    /// Returns the RTF document
+    pub fn finish(&self) -> String {
+        let mut rtf = String::from("{\\rtf1\\ansi\\ansicpg1252\\deff0\\uc1");
+
+        if !self.fonts.is_empty() {
+            rtf.push_str("{\\fonttbl");
+            for (index, font) in self.fonts.iter().enumerate() {
+                write!(rtf, "{{\\f{}\\fnil ", index).unwrap();
+                escape_text(&mut rtf, font);
+                rtf.push_str(";}");
+            }
+            rtf.push('}');
+        }
+
+        if !self.colors.is_empty() {
+            // The first color of the table is the default color
+            rtf.push_str("{\\colortbl ;");
+            for [r, g, b] in self.colors.iter() {
+                write!(rtf, "\\red{}\\green{}\\blue{};", r, g, b).unwrap();
+            }
+            rtf.push('}');
+        }
+
+        rtf.push('\n');
+        rtf.push_str(&self.body);
+        rtf.push('}');
+        rtf
+    }
+
+    fn toggle(&mut self, name: &str, value: bool) -> &mut RtfWriter {
+        match value {
+            true => self.control(name, None),
+            false => self.control(name, Some(0)),
+        }
+    }
+
+    fn color_index(&mut self, color: [u8; 3]) -> i32 {
+        let index = match self.colors.iter().position(|c| *c == color) {
+            Some(index) => index,
+            None => {
+                self.colors.push(color);
+                self.colors.len() - 1
+            }
+        };
+
+        // Index 0 is the default color
+        (index + 1) as i32
+    }
+
+}
+
+fn escape_text(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\\' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            },
+            '\r' => {},
+            '\n' => out.push_str("\\par\n"),
+            '\t' => out.push_str("\\tab "),
+            c if c.is_ascii() => out.push(c),
+            c => {
+                // `\u` takes a signed 16 bits value, followed by a fallback character for the readers that do not support unicode
+                let mut buffer = [0u16; 2];
+                for unit in c.encode_utf16(&mut buffer).iter() {
+                    write!(out, "\\u{}?", *unit as i16).unwrap();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::*;
+
+const ITEMS: &'static [&'static str] = &["Banana", "Apple", "Pineapple", "apple pie", "Grape", "Native Windows GUI"];
+
+#[test]
+fn prefix_matcher() {
+    assert_eq!(rank_matches(&PrefixMatcher, "app", ITEMS), vec![1, 3]);
+    assert_eq!(rank_matches(&PrefixMatcher, "APPLE P", ITEMS), vec![3]);
+    assert_eq!(rank_matches(&PrefixMatcher, "", ITEMS), vec![0, 1, 2, 3, 4, 5]);
+    assert!(rank_matches(&PrefixMatcher, "kiwi", ITEMS).is_empty());
+}
+
+#[test]
+fn substring_matcher() {
+    // The items where the text appears first are ranked first
+    assert_eq!(rank_matches(&SubstringMatcher, "apple", ITEMS), vec![1, 3, 2]);
+    assert_eq!(rank_matches(&SubstringMatcher, "ap", ITEMS), vec![1, 3, 4, 2]);
+    assert_eq!(SubstringMatcher.score("an", "Banana"), Some(-1));
+    assert_eq!(SubstringMatcher.score("é", "CAFÉ"), Some(-3));
+}
+
+#[test]
+fn fuzzy_matcher() {
+    assert_eq!(rank_matches(&FuzzyMatcher, "nwg", ITEMS), vec![5]);
+    assert!(FuzzyMatcher.score("gwn", "Native Windows GUI").is_none());
+    assert!(FuzzyMatcher.score("apples", "Apple").is_none());
+
+    // Consecutive characters and word starts rank higher than scattered characters
+    let exact = FuzzyMatcher.score("apple", "Apple").unwrap();
+    let inner = FuzzyMatcher.score("apple", "Pineapple").unwrap();
+    let scattered = FuzzyMatcher.score("apple", "A purple lime").unwrap();
+    assert!(exact > inner);
+    assert!(inner > scattered);
+
+    // Camel case counts as a word start
+    assert!(FuzzyMatcher.score("lv", "ListView").unwrap() > FuzzyMatcher.score("lv", "Lava").unwrap());
+}
+
+#[test]
+fn custom_matcher() {
+    struct LengthMatcher;
+
+    impl ComboBoxMatcher for LengthMatcher {
+        fn score(&self, text: &str, item: &str) -> Option<i32> {
+            match item.len() >= text.len() {
+                true => Some(-(item.len() as i32)),
+                false => None
+            }
+        }
+    }
+
+    // Items with the same score keep their order
+    assert_eq!(rank_matches(&LengthMatcher, "abcdefg", ITEMS), vec![2, 3, 5]);
+    assert_eq!(rank_matches(&LengthMatcher, "abcde", ITEMS), vec![1, 4, 0, 2, 3, 5]);
+}
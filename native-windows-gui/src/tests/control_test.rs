@@ -452,6 +452,8 @@ mod partial_controls_test_ui {
                 .flags(TrackBarFlags::VISIBLE | TrackBarFlags::RANGE | TrackBarFlags::VERTICAL | TrackBarFlags::AUTO_TICK)
                 .position((290, 220))
                 .size((40, 110))
+                .tick_frequency(Some(2))
+                .tooltip_side(Some(TrackBarTooltipSide::Right))
                 .background_color(Some([255, 255, 255]))
                 .parent(&data.basics_control_tab)
                 .build(&mut data.test_track2)?;
@@ -512,6 +514,7 @@ mod partial_controls_test_ui {
                 .size((20, 200))
                 .parent(&data.basics_control_tab2)
                 .range(Some(0..100))
+                .page_size(Some(10))
                 .pos(Some(30))
                 .build(&mut data.test_scroll_v)?;
 
@@ -1137,29 +1140,34 @@ fn run_combo_tests(app: &ControlsTest, _evt: Event) {
 fn run_date_tests(app: &ControlsTest, _evt: Event) {
     if !app.runs.borrow().date {
         
-        let v = DatePickerValue { year: 2000, month: 10, day: 5 };
-        app.test_date.set_value(Some(v));
+        let v = DatePickerValue::new(2000, 10, 5).with_time(13, 37, 42);
+        app.test_date.set_value(Some(v)).unwrap();
         assert_eq!(app.test_date.value(), Some(v));
         assert_eq!(app.test_date.checked(), true);
 
-        app.test_date.set_value(None);
+        app.test_date.set_value(None).unwrap();
         assert_eq!(app.test_date.value(), None);
         assert_eq!(app.test_date.checked(), false);
 
+        assert!(app.test_date.set_value(Some(DatePickerValue::new(2001, 2, 29))).is_err());
+
         app.test_date.set_format(Some("'YEAR: 'yyyy"));
 
-        let up = DatePickerValue { year: 2000, month: 1, day: 1 };
-        let down = DatePickerValue { year: 2001, month: 1, day: 1 };
-        app.test_date.set_range(&[up, down]);
+        let up = DatePickerValue::new(2000, 1, 1);
+        let down = DatePickerValue::new(2001, 1, 1);
+        app.test_date.set_range(&[up, down]).unwrap();
         assert_eq!(app.test_date.range(), [up, down]);
 
+        assert!(app.test_date.set_range(&[down, up]).is_err());
+        assert!(app.test_date.set_value(Some(DatePickerValue::new(2005, 1, 1))).is_err());
+
         app.runs.borrow_mut().date = true;
     } else {
         app.test_date.set_format(None);
 
-        let up = DatePickerValue { year: 1950, month: 1, day: 1 };
-        let down = DatePickerValue { year: 2020, month: 12, day: 30 };
-        app.test_date.set_range(&[up, down]);
+        let up = DatePickerValue::new(1950, 1, 1);
+        let down = DatePickerValue::new(2020, 12, 30);
+        app.test_date.set_range(&[up, down]).unwrap();
         app.runs.borrow_mut().date = false;
     }
 }
@@ -1411,6 +1419,14 @@ fn run_track_tests(app: &ControlsTest, _evt: Event) {
         app.test_track2.set_selection_range_pos(0..3);
         assert_eq!(app.test_track2.selection_range_pos(), 0..3);
 
+        assert!(app.test_track2.tooltips());
+        assert!(!app.test_track1.tooltips());
+
+        app.test_track1.set_line_size(2);
+        assert_eq!(app.test_track1.line_size(), 2);
+        app.test_track1.set_page_size(5);
+        assert_eq!(app.test_track1.page_size(), 5);
+
         app.runs.borrow_mut().track = true;
     } else {
         app.runs.borrow_mut().track = false;
@@ -0,0 +1,82 @@
+use crate::*;
+use std::time::{Duration, UNIX_EPOCH};
+
+fn date(year: u16, month: u16, day: u16) -> DatePickerValue {
+    DatePickerValue::new(year, month, day)
+}
+
+#[test]
+fn validate() {
+    assert!(date(2020, 2, 29).validate().is_ok());
+    assert!(date(2000, 2, 29).validate().is_ok());
+    assert!(date(1900, 2, 29).validate().is_err());
+    assert!(date(2021, 2, 29).validate().is_err());
+    assert!(date(2021, 4, 31).validate().is_err());
+    assert!(date(2021, 0, 1).validate().is_err());
+    assert!(date(2021, 13, 1).validate().is_err());
+    assert!(date(2021, 1, 0).validate().is_err());
+    assert!(date(1600, 12, 31).validate().is_err());
+    assert!(date(30827, 12, 31).with_time(23, 59, 59).validate().is_ok());
+    assert!(date(2021, 1, 1).with_time(24, 0, 0).validate().is_err());
+    assert!(date(2021, 1, 1).with_time(0, 60, 0).validate().is_err());
+}
+
+#[test]
+fn ordering() {
+    assert!(date(2020, 1, 1).with_time(23, 0, 0) < date(2020, 1, 2));
+    assert!(date(2019, 12, 31) < date(2020, 1, 1));
+    assert!(date(2020, 1, 1).with_time(0, 0, 1) > date(2020, 1, 1));
+}
+
+#[test]
+fn system_time() {
+    assert_eq!(DatePickerValue::from_system_time(UNIX_EPOCH).unwrap(), date(1970, 1, 1));
+
+    let time = UNIX_EPOCH + Duration::from_secs(951_831_296);
+    let value = date(2000, 2, 29).with_time(13, 34, 56);
+    assert_eq!(DatePickerValue::from_system_time(time).unwrap(), value);
+    assert_eq!(value.to_system_time().unwrap(), time);
+
+    // Before the epoch, subseconds round toward the past
+    let time = UNIX_EPOCH - Duration::from_millis(500);
+    assert_eq!(DatePickerValue::from_system_time(time).unwrap(), date(1969, 12, 31).with_time(23, 59, 59));
+
+    let old = date(1601, 1, 1);
+    assert_eq!(DatePickerValue::from_system_time(old.to_system_time().unwrap()).unwrap(), old);
+
+    assert!(date(2021, 2, 29).to_system_time().is_err());
+}
+
+#[test]
+fn local_system_time() {
+    // Noon UTC is never in a daylight saving time transition
+    let time = UNIX_EPOCH + Duration::from_secs(1_592_222_400);
+    let local = DatePickerValue::from_system_time_local(time).unwrap();
+    assert_eq!(local.to_system_time_local().unwrap(), time);
+
+    assert!(date(2021, 2, 29).to_system_time_local().is_err());
+}
+
+#[test]
+fn iso8601() {
+    let value = date(2020, 2, 29).with_time(13, 37, 5);
+    assert_eq!(value.to_iso8601(), "2020-02-29T13:37:05");
+    assert_eq!(date(1601, 1, 1).to_iso8601(), "1601-01-01T00:00:00");
+
+    assert_eq!(DatePickerValue::from_iso8601("2020-02-29").unwrap(), date(2020, 2, 29));
+    assert_eq!(DatePickerValue::from_iso8601("2020-02-29T13:37:05").unwrap(), value);
+    assert_eq!(DatePickerValue::from_iso8601(" 2020-02-29 13:37:05 ").unwrap(), value);
+    assert_eq!(DatePickerValue::from_iso8601("2020-02-29T13:37").unwrap(), value.with_time(13, 37, 0));
+    assert_eq!(DatePickerValue::from_iso8601("2020-02-29T13:37:05.999Z").unwrap(), value);
+
+    // Offsets are converted to UTC
+    assert_eq!(DatePickerValue::from_iso8601("2020-02-29T13:37:05+02:00").unwrap(), value.with_time(11, 37, 5));
+    assert_eq!(DatePickerValue::from_iso8601("2020-02-29T23:00:00-0130").unwrap(), date(2020, 3, 1).with_time(0, 30, 0));
+
+    for bad in ["", "2020", "2020-2-29", "2020-02-30", "20-02-29", "2020-02-29T", "2020-02-29T25:00",
+                "2020-02-29T13:37:05+2", "2020-02-29T13:37:05.x", "2020-02-29T13:37:05:01", "2020-02-29x"].iter() {
+        assert!(DatePickerValue::from_iso8601(bad).is_err(), "{:?}", bad);
+    }
+
+    assert_eq!(DatePickerValue::from_iso8601(&value.to_iso8601()).unwrap(), value);
+}
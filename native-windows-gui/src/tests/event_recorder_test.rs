@@ -0,0 +1,164 @@
+use crate::*;
+use std::ptr;
+
+fn test_handles() -> (ControlHandle, ControlHandle) {
+    (ControlHandle::Notice(ptr::null_mut(), 1), ControlHandle::Timer(ptr::null_mut(), 2))
+}
+
+fn test_log() -> EventLog {
+    let records = vec![
+        EventRecord { control: "window".into(), event: Event::OnInit, data: RecordedEventData::NoData },
+        EventRecord { control: "button".into(), event: Event::OnMousePress(MousePressEvent::MousePressLeftUp), data: RecordedEventData::NoData },
+        EventRecord { control: "button".into(), event: Event::OnChar, data: RecordedEventData::Char('\t') },
+        EventRecord { control: "button".into(), event: Event::Custom(42), data: RecordedEventData::Opaque },
+        EventRecord { control: "window".into(), event: Event::OnListViewItemChanged, data: RecordedEventData::ListViewItemChanged { row_index: 3, column_index: 1, selected: true } },
+        EventRecord { control: "window".into(), event: Event::OnWindowClose, data: RecordedEventData::WindowClose },
+    ];
+
+    EventLog { records }
+}
+
+#[test]
+fn event_log_round_trip() {
+    let log = test_log();
+    let text = log.to_string();
+    assert_eq!(text.lines().count(), log.records.len());
+    assert_eq!(EventLog::parse(&text).unwrap(), log);
+}
+
+#[test]
+fn event_log_validation_round_trip() {
+    let log = EventLog { records: vec![
+        EventRecord { control: "edit".into(), event: Event::OnValidationChanged, data: RecordedEventData::ValidationChanged(true) },
+        EventRecord { control: "edit".into(), event: Event::OnValidationChanged, data: RecordedEventData::ValidationChanged(false) },
+    ]};
+
+    let text = log.to_string();
+    assert_eq!(EventLog::parse(&text).unwrap(), log);
+}
+
+#[test]
+fn event_log_number_select_round_trip() {
+    let log = EventLog { records: vec![
+        EventRecord { control: "amount".into(), event: Event::OnNumberSelectChanged, data: RecordedEventData::NumberSelectChanged(NumberSelectValue::Float(-12.5)) },
+        EventRecord { control: "count".into(), event: Event::OnNumberSelectChanged, data: RecordedEventData::NumberSelectChanged(NumberSelectValue::Int(7)) },
+    ]};
+
+    let text = log.to_string();
+    assert_eq!(EventLog::parse(&text).unwrap(), log);
+}
+
+#[test]
+fn event_log_scroll_round_trip() {
+    let log = EventLog { records: vec![
+        EventRecord { control: "volume".into(), event: Event::TrackBarChanged, data: RecordedEventData::Scroll { position: 42, action: ScrollAction::ThumbTrack } },
+        EventRecord { control: "volume".into(), event: Event::OnHorizontalScroll, data: RecordedEventData::Scroll { position: -3, action: ScrollAction::EndScroll } },
+    ]};
+
+    let text = log.to_string();
+    assert_eq!(EventLog::parse(&text).unwrap(), log);
+}
+
+#[test]
+fn event_log_parse() {
+    let text = "# comment\r\n\nwindow\tOnInit\t-\r\nedit\tOnKeyPress\tkey:13\nedit\tOnMouseWheel\twheel:-120\n";
+    let log = EventLog::parse(text).unwrap();
+    assert_eq!(log.records.len(), 3);
+    assert_eq!(log.records[1].data, RecordedEventData::Key(13));
+    assert_eq!(log.records[2].event, Event::OnMouseWheel);
+    assert_eq!(log.records[2].data, RecordedEventData::MouseWheel(-120));
+
+    assert!(EventLog::parse("window\tOnNothing\t-").is_err());
+    assert!(EventLog::parse("window\tOnInit\tchar:abc").is_err());
+    assert!(EventLog::parse("window\tOnInit").is_err());
+    assert!(EventLog::parse("window\tOnInit\t-\textra").is_err());
+}
+
+#[test]
+fn event_replay_dispatch() {
+    let (window, button) = test_handles();
+    let mut replay = EventReplay::new();
+    replay.register("window", &window);
+    replay.register("button", &button);
+
+    let mut received = Vec::new();
+    replay.replay(&test_log(), |evt, evt_data, handle| {
+        if let EventData::OnWindowClose(close) = &evt_data {
+            assert!(close.closing());
+        }
+        received.push((evt, RecordedEventData::from_event_data(&evt_data), handle));
+    }).unwrap();
+
+    assert_eq!(received.len(), 6);
+    assert_eq!(received[0].2, window);
+    assert_eq!(received[2], (Event::OnChar, RecordedEventData::Char('\t'), button));
+    assert_eq!(received[3].1, RecordedEventData::NoData);
+}
+
+#[test]
+fn event_replay_unknown_control() {
+    let (window, _) = test_handles();
+    let mut replay = EventReplay::new();
+    replay.register("window", &window);
+
+    let mut count = 0;
+    assert!(replay.replay(&test_log(), |_, _, _| count += 1).is_err());
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn event_recorder_record() {
+    let (window, button) = test_handles();
+    let recorder = EventRecorder::new();
+    recorder.register("window", &window);
+    recorder.register("button", &button);
+
+    recorder.record(Event::OnInit, &EventData::NoData, window);
+    recorder.record(Event::OnMouseMove, &EventData::NoData, button);
+    recorder.record(Event::OnButtonClick, &EventData::NoData, ControlHandle::NoHandle);
+    recorder.record(Event::OnKeyPress, &EventData::OnKey(65), button);
+
+    recorder.set_recording(false);
+    recorder.record(Event::OnButtonClick, &EventData::NoData, button);
+
+    let log = recorder.take_log();
+    assert_eq!(log.records.len(), 2);
+    assert_eq!(log.records[1], EventRecord { control: "button".into(), event: Event::OnKeyPress, data: RecordedEventData::Key(65) });
+    assert!(recorder.log().records.is_empty());
+}
+
+struct TestUi {
+    window: ControlHandle,
+    button: ControlHandle,
+}
+
+impl NamedControls for TestUi {
+    fn named_controls(&self) -> Vec<(&'static str, ControlHandle)> {
+        vec![("window", self.window), ("button", self.button)]
+    }
+}
+
+#[test]
+fn event_register_all() {
+    let (window, button) = test_handles();
+    let ui = TestUi { window, button };
+
+    let mut replay = EventReplay::new();
+    replay.register_all(&ui);
+    assert_eq!(replay.handle("button"), Some(button));
+    assert!(replay.replay(&test_log(), |_, _, _| {}).is_ok());
+
+    let recorder = EventRecorder::new();
+    recorder.register_all(&ui);
+    recorder.record(Event::OnButtonClick, &EventData::NoData, button);
+    assert_eq!(recorder.log().records[0].control, "button");
+}
+
+#[test]
+fn event_from_name() {
+    assert_eq!(Event::from_name("OnButtonClick"), Some(Event::OnButtonClick));
+    assert_eq!(Event::from_name("OnWindowClose"), Some(Event::OnWindowClose));
+    assert_eq!(Event::from_name("OnMousePress"), None);
+    assert_eq!(Event::from_name("Custom"), None);
+    assert_eq!(Event::from_name("OnNothing"), None);
+}
@@ -0,0 +1,137 @@
+use crate::*;
+use std::ops::Range;
+
+const RED: HighlightStyle = HighlightStyle { text_color: Some([255, 0, 0]), background_color: None, bold: false, italic: false, underline: false };
+const BOLD: HighlightStyle = HighlightStyle { text_color: None, background_color: None, bold: true, italic: false, underline: false };
+
+/// Highlights the words "red" and "bold", and the whole line after a line ending with "!"
+struct TestHighlighter;
+
+impl Highlighter for TestHighlighter {
+    fn highlight(&self, lines: &[&str], range: Range<usize>) -> Vec<HighlightSpan> {
+        let mut spans = Vec::new();
+        for index in range {
+            let line = lines[index];
+            if index > 0 && lines[index - 1].ends_with('!') {
+                spans.push(HighlightSpan { line: index, range: 0..line.len(), style: BOLD });
+            }
+
+            for (start, word) in line.match_indices("red") {
+                spans.push(HighlightSpan { line: index, range: start..(start + word.len()), style: RED });
+            }
+        }
+
+        spans
+    }
+
+    fn invalidated(&self, lines: &[&str], changed: Range<usize>) -> Range<usize> {
+        changed.start..(changed.end + 1).min(lines.len())
+    }
+}
+
+fn run(range: Range<u32>, style: Option<HighlightStyle>) -> StyleRun {
+    StyleRun { range, style }
+}
+
+#[test]
+fn highlight_cache_first_update() {
+    let mut cache = HighlightCache::new();
+    let runs = cache.update("a red\rb", &TestHighlighter);
+    assert_eq!(runs, vec![run(0..2, None), run(2..5, Some(RED)), run(6..7, None)]);
+
+    // Nothing changed
+    assert!(cache.update("a red\rb", &TestHighlighter).is_empty());
+}
+
+#[test]
+fn highlight_cache_incremental_update() {
+    let mut cache = HighlightCache::new();
+    cache.update("one\rtwo red\rthree\rfour", &TestHighlighter);
+
+    // Only the edited line is formatted, and the next line is highlighted again without changes
+    let runs = cache.update("one\rtwo reds\rthree\rfour", &TestHighlighter);
+    assert_eq!(runs, vec![run(4..8, None), run(8..11, Some(RED)), run(11..12, None)]);
+
+    // The line after the edit changes style, so it is formatted too
+    let runs = cache.update("one\rtwo reds!\rthree\rfour", &TestHighlighter);
+    assert_eq!(runs, vec![run(4..8, None), run(8..11, Some(RED)), run(11..13, None), run(14..19, Some(BOLD))]);
+
+    // Removing a line only formats the line that follows it
+    let runs = cache.update("one\rthree\rfour", &TestHighlighter);
+    assert_eq!(runs, vec![run(4..9, None)]);
+}
+
+#[test]
+fn highlight_cache_line_breaks() {
+    let mut cache = HighlightCache::new();
+    let runs = cache.update("red\r\nred\nred", &TestHighlighter);
+    assert_eq!(runs, vec![run(0..3, Some(RED)), run(5..8, Some(RED)), run(9..12, Some(RED))]);
+
+    let runs = cache.update("red\r\n\r\nred\nred", &TestHighlighter);
+    assert!(runs.is_empty());
+}
+
+#[test]
+fn highlight_cache_utf16_offsets() {
+    let mut cache = HighlightCache::new();
+    let runs = cache.update("😀 red\ré red", &TestHighlighter);
+    assert_eq!(runs, vec![run(0..3, None), run(3..6, Some(RED)), run(7..9, None), run(9..12, Some(RED))]);
+
+    // Spans that are not on a char boundary are ignored
+    struct Bad;
+    impl Highlighter for Bad {
+        fn highlight(&self, _lines: &[&str], _range: Range<usize>) -> Vec<HighlightSpan> {
+            vec![HighlightSpan { line: 0, range: 1..3, style: RED }, HighlightSpan { line: 5, range: 0..1, style: RED }]
+        }
+    }
+
+    let mut cache = HighlightCache::new();
+    assert_eq!(cache.update("😀", &Bad), vec![run(0..2, None)]);
+}
+
+#[test]
+fn highlight_cache_clear() {
+    let mut cache = HighlightCache::new();
+    cache.update("red", &TestHighlighter);
+    cache.clear();
+    assert_eq!(cache.update("red", &TestHighlighter), vec![run(0..3, Some(RED))]);
+}
+
+#[test]
+fn keyword_highlighter() {
+    let highlighter = KeywordHighlighter::new()
+        .keywords(&["let", "if"], BOLD)
+        .strings(RED)
+        .line_comment("//", HighlightStyle::color([0, 128, 0]));
+
+    let line = r#"let iffy = "if \"let\"" // let"#;
+    let spans = highlighter.highlight(&[line], 0..1);
+    let ranges: Vec<(&str, HighlightStyle)> = spans.iter().map(|s| (&line[s.range.clone()], s.style)).collect();
+
+    assert_eq!(ranges, vec![
+        (r#""if \"let\"""#, RED),
+        ("// let", HighlightStyle::color([0, 128, 0])),
+        ("let", BOLD),
+    ]);
+
+    // An unterminated string ends with the line
+    let spans = highlighter.highlight(&["if \"let"], 0..1);
+    assert_eq!(spans, vec![
+        HighlightSpan { line: 0, range: 3..7, style: RED },
+        HighlightSpan { line: 0, range: 0..2, style: BOLD },
+    ]);
+}
+
+#[cfg(feature = "regex-highlighter")]
+#[test]
+fn keyword_highlighter_pattern() {
+    let highlighter = KeywordHighlighter::new()
+        .strings(RED)
+        .pattern(r"\d+", BOLD).unwrap();
+
+    let spans = highlighter.highlight(&["x = 42 \"7\""], 0..1);
+    assert_eq!(spans, vec![
+        HighlightSpan { line: 0, range: 7..10, style: RED },
+        HighlightSpan { line: 0, range: 4..6, style: BOLD },
+    ]);
+}
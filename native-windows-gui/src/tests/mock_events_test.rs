@@ -0,0 +1,42 @@
+use crate::*;
+
+#[test]
+fn mock_window_close() {
+    let mut mock = MockEventData::new();
+    {
+        let data = mock.window_close();
+        match &*data {
+            EventData::OnWindowClose(close) => close.close(false),
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    assert!(!mock.closing());
+}
+
+#[test]
+fn mock_tooltip_text() {
+    let mut mock = MockEventData::new();
+    {
+        let data = mock.tooltip_text();
+        data.on_tooltip_text().set_text("Tooltip");
+        data.on_tooltip_text().keep(true);
+    }
+
+    assert_eq!(mock.tooltip_value(), "Tooltip");
+    assert!(mock.tooltip_kept());
+
+    mock.tooltip_text();
+    assert_eq!(mock.tooltip_value(), "");
+}
+
+#[test]
+fn mock_file_drop() {
+    let mock = MockEventData::new();
+    let data = mock.file_drop(&["C:\\a.txt", "C:\\dossier\\é.png"], [12, -4]);
+    let drop = data.on_file_drop();
+
+    assert_eq!(drop.len(), 2);
+    assert_eq!(drop.point(), [12, -4]);
+    assert_eq!(drop.files(), vec!["C:\\a.txt".to_string(), "C:\\dossier\\é.png".to_string()]);
+}
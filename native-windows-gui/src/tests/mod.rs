@@ -12,6 +12,26 @@ use freeing_test::*;
 
 mod other;
 
+mod event_recorder_test;
+
+mod mock_events_test;
+
+mod combo_box_matcher_test;
+
+mod text_validator_test;
+
+mod rtf_test;
+
+mod text_search_test;
+
+mod highlighter_test;
+
+mod number_format_test;
+
+mod date_picker_value_test;
+
+mod month_calendar_test;
+
 
 #[derive(Default)]
 pub struct TestControlPanel {
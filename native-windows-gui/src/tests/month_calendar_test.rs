@@ -0,0 +1,52 @@
+use crate::*;
+use crate::controls::BoldDays;
+use winapi::um::minwinbase::SYSTEMTIME;
+use std::mem;
+
+fn start(year: u16, month: u16) -> SYSTEMTIME {
+    let mut time: SYSTEMTIME = unsafe { mem::zeroed() };
+    time.wYear = year;
+    time.wMonth = month;
+    time.wDay = 1;
+    time
+}
+
+fn bold_days<F: Fn(&DatePickerValue) -> bool + 'static>(callback: F) -> BoldDays {
+    let days = BoldDays::default();
+    *days.callback.borrow_mut() = Some(Box::new(callback));
+    days
+}
+
+#[test]
+fn bold_days_month_rollover() {
+    let days = bold_days(|d| d.day == 1 || d.day == 31);
+    days.compute(&start(2020, 11), 4);
+
+    let states = days.states.borrow();
+    assert_eq!(states.len(), 4);
+    assert_eq!(states[0], 1);                      // November 2020 has 30 days
+    assert_eq!(states[1], 1 | (1 << 30));          // December 2020
+    assert_eq!(states[2], 1 | (1 << 30));          // January 2021
+    assert_eq!(states[3], 1);                      // February 2021
+}
+
+#[test]
+fn bold_days_leap_february() {
+    let days = bold_days(|d| d.month == 2 && d.day >= 28);
+
+    days.compute(&start(2020, 2), 1);
+    assert_eq!(days.states.borrow()[0], (1 << 27) | (1 << 28));
+
+    days.compute(&start(2021, 2), 1);
+    assert_eq!(days.states.borrow()[0], 1 << 27);
+
+    days.compute(&start(1900, 2), 1);
+    assert_eq!(days.states.borrow()[0], 1 << 27);
+}
+
+#[test]
+fn bold_days_without_callback() {
+    let days = BoldDays::default();
+    days.compute(&start(2020, 1), 3);
+    assert_eq!(*days.states.borrow(), vec![0, 0, 0]);
+}
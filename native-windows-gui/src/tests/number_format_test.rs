@@ -0,0 +1,96 @@
+use crate::*;
+
+fn format(decimal: &str, thousand: &str, grouping: &str) -> NumberFormat {
+    NumberFormat {
+        decimal_separator: decimal.to_string(),
+        thousand_separator: thousand.to_string(),
+        grouping: grouping.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn format_default() {
+    let default = NumberFormat::default();
+    assert_eq!(default.format_int(1234567), "1234567");
+    assert_eq!(default.format_int(-42), "-42");
+    assert_eq!(default.format_int(i64::min_value()), "-9223372036854775808");
+    assert_eq!(default.format_float(3.14159, 2), "3.14");
+    assert_eq!(default.format_float(2.5, 0), "2");
+    assert_eq!(default.format_float(-0.001, 2), "0.00");
+    assert_eq!(default.format_float(-1.5, 1), "-1.5");
+}
+
+#[test]
+fn format_grouping() {
+    let en = format(".", ",", "3;0");
+    assert_eq!(en.format_int(0), "0");
+    assert_eq!(en.format_int(999), "999");
+    assert_eq!(en.format_int(1000), "1,000");
+    assert_eq!(en.format_int(-1234567), "-1,234,567");
+    assert_eq!(en.format_float(1234567.891, 2), "1,234,567.89");
+
+    let fr = format(",", "\u{a0}", "3;0");
+    assert_eq!(fr.format_float(-12345.5, 1), "-12\u{a0}345,5");
+
+    // Indian grouping: 3 digits, then groups of 2
+    let hi = format(".", ",", "3;2;0");
+    assert_eq!(hi.format_int(1234567890), "1,23,45,67,890");
+
+    // Without the trailing 0, only the last group is separated
+    let last = format(".", ",", "3");
+    assert_eq!(last.format_int(1234567), "1234,567");
+
+    let none = format(".", ",", "");
+    assert_eq!(none.format_int(1234567), "1234567");
+}
+
+#[test]
+fn format_prefix_suffix() {
+    let dollars = NumberFormat { prefix: "$".to_string(), ..format(".", ",", "3;0") };
+    assert_eq!(dollars.format_float(-1234.5, 2), "$-1,234.50");
+
+    let weight = NumberFormat { suffix: " kg".to_string(), ..Default::default() };
+    assert_eq!(weight.format_int(80), "80 kg");
+}
+
+#[test]
+fn parse() {
+    let en = NumberFormat { prefix: "$".to_string(), suffix: " USD".to_string(), ..format(".", ",", "3;0") };
+    assert_eq!(en.parse_int("$1,234 USD"), Some(1234));
+    assert_eq!(en.parse_int("  -1,234,567 "), Some(-1234567));
+    assert_eq!(en.parse_int("12.5"), None);
+    assert_eq!(en.parse_float("$ 1,234.5"), Some(1234.5));
+    assert_eq!(en.parse_float("1.2.3"), None);
+    assert_eq!(en.parse_float("12abc"), None);
+    assert_eq!(en.parse_float(""), None);
+    assert_eq!(en.parse_float("$"), None);
+
+    // The decimal separator of the format is used, and `.` is also accepted if it is not the thousand separator
+    let fr = format(",", "\u{a0}", "3;0");
+    assert_eq!(fr.parse_float("1\u{a0}234,5"), Some(1234.5));
+    assert_eq!(fr.parse_float("1 234.5"), Some(1234.5));
+
+    let de = format(",", ".", "3;0");
+    assert_eq!(de.parse_float("1.234,5"), Some(1234.5));
+    assert_eq!(de.parse_int("1.234.567"), Some(1234567));
+}
+
+#[test]
+fn parse_formatted() {
+    let formats = [
+        NumberFormat::default(),
+        format(",", "\u{a0}", "3;0"),
+        NumberFormat { prefix: "€ ".to_string(), suffix: " TTC".to_string(), ..format(",", ".", "3;2;0") },
+    ];
+
+    for f in formats.iter() {
+        for &value in [0.0, 1.5, -1234567.25, 999.99].iter() {
+            assert_eq!(f.parse_float(&f.format_float(value, 2)), Some(value));
+        }
+
+        for &value in [0, 7, -1000, i64::max_value()].iter() {
+            assert_eq!(f.parse_int(&f.format_int(value)), Some(value));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::*;
+
+fn word(name: &str, param: Option<i32>) -> RtfToken {
+    RtfToken::ControlWord { name: name.to_string(), param }
+}
+
+fn text(text: &str) -> RtfToken {
+    RtfToken::Text(text.to_string())
+}
+
+#[test]
+fn rtf_tokenize() {
+    use RtfToken::{GroupStart, GroupEnd, Hex};
+
+    let tokens = tokenize_rtf(r"{\rtf1\ansi Hello {\b bold\b0} \'e9\u233?}").unwrap();
+    assert_eq!(tokens, vec![
+        GroupStart, word("rtf", Some(1)), word("ansi", None), text("Hello "),
+        GroupStart, word("b", None), text("bold"), word("b", Some(0)), GroupEnd,
+        text(" "), Hex(0xE9), word("u", Some(233)), text("?"), GroupEnd
+    ]);
+
+    // Escaped characters are text, line breaks are ignored
+    let tokens = tokenize_rtf("{a\\{b\\}\\\\c\\~d}").unwrap();
+    assert_eq!(tokens, vec![GroupStart, text("a{b}\\c"), RtfToken::ControlSymbol('~'), text("d"), GroupEnd]);
+
+    let tokens = tokenize_rtf("\\fs-24 x\\par\r\ny\\\nz").unwrap();
+    assert_eq!(tokens, vec![word("fs", Some(-24)), text("x"), word("par", None), text("y"), word("par", None), text("z")]);
+}
+
+#[test]
+fn rtf_tokenize_errors() {
+    assert!(tokenize_rtf("}").is_err());
+    assert!(tokenize_rtf("{{}").is_err());
+    assert!(tokenize_rtf(r"\'zz").is_err());
+    assert!(tokenize_rtf(r"\'e").is_err());
+    assert!(tokenize_rtf("\\").is_err());
+    assert!(tokenize_rtf(r"\b99999999999").is_err());
+}
+
+#[test]
+fn rtf_plain_text() {
+    let rtf = r"{\rtf1\ansi{\fonttbl{\f0 Arial;}}{\colortbl ;\red255\green0\blue0;}{\*\generator Riched20;}\f0 Hello\par It\'92s \u8364? \tab x}";
+    assert_eq!(rtf_to_text(rtf).unwrap(), "Hello\nIt’s € \tx");
+
+    // Characters outside of the BMP and the number of fallback characters
+    assert_eq!(rtf_to_text(r"{\uc0\u-10179\u-8704}").unwrap(), "😀");
+    assert_eq!(rtf_to_text(r"{\uc2\u233 ab c}").unwrap(), "é c");
+}
+
+#[test]
+fn rtf_writer() {
+    let mut writer = RtfWriter::new();
+    writer.font("Segoe UI").font_size(11.0).text_color([255, 0, 0])
+        .bold(true).text("Hi {there}").bold(false).paragraph()
+        .begin_group().italic(true).text("é😀\tC:\\").end_group();
+
+    let rtf = writer.finish();
+    assert!(rtf.starts_with("{\\rtf1"));
+    assert!(rtf.contains("{\\fonttbl{\\f0\\fnil Segoe UI;}}"));
+    assert!(rtf.contains("{\\colortbl ;\\red255\\green0\\blue0;}"));
+    assert!(rtf.contains("\\fs22 \\cf1 "));
+    assert_eq!(rtf_to_text(&rtf).unwrap(), "Hi {there}\né😀\tC:\\");
+
+    let tokens = tokenize_rtf(&rtf).unwrap();
+    assert_eq!(tokens.iter().filter(|t| t.is_control_word("b")).count(), 2);
+
+    let rtf = RtfWriter::new().align(ParaAlignment::Center).text("x").finish();
+    assert!(rtf.ends_with("\\qc x}"));
+}
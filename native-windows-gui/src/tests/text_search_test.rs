@@ -0,0 +1,80 @@
+use crate::*;
+
+// "é" is 2 bytes and 1 UTF-16 unit, "😀" is 4 bytes and 2 UTF-16 units (a surrogate pair)
+const TEXT: &'static str = "aé😀b";
+
+#[test]
+fn utf16_offsets() {
+    assert_eq!(utf16_offset_to_index(TEXT, 0), Some(0));
+    assert_eq!(utf16_offset_to_index(TEXT, 1), Some(1));
+    assert_eq!(utf16_offset_to_index(TEXT, 2), Some(3));
+    assert_eq!(utf16_offset_to_index(TEXT, 3), None);
+    assert_eq!(utf16_offset_to_index(TEXT, 4), Some(7));
+    assert_eq!(utf16_offset_to_index(TEXT, 5), Some(8));
+    assert_eq!(utf16_offset_to_index(TEXT, 6), None);
+    assert_eq!(utf16_offset_to_index("", 0), Some(0));
+    assert_eq!(utf16_offset_to_index("", 1), None);
+
+    assert_eq!(index_to_utf16_offset(TEXT, 0), 0);
+    assert_eq!(index_to_utf16_offset(TEXT, 3), 2);
+    assert_eq!(index_to_utf16_offset(TEXT, 7), 4);
+    assert_eq!(index_to_utf16_offset(TEXT, 8), 5);
+}
+
+#[test]
+fn utf16_offsets_round_trip() {
+    let text = "Ünïcödé\r\n𝄞 music\r\n漢字";
+    for (index, _) in text.char_indices() {
+        let offset = index_to_utf16_offset(text, index);
+        assert_eq!(utf16_offset_to_index(text, offset), Some(index));
+    }
+
+    let utf16: Vec<u16> = text.encode_utf16().collect();
+    assert_eq!(index_to_utf16_offset(text, text.len()), utf16.len() as u32);
+}
+
+#[test]
+#[should_panic]
+fn utf16_offset_not_on_char_boundary() {
+    index_to_utf16_offset(TEXT, 2);
+}
+
+#[test]
+fn find() {
+    let text = "Hello world, hello WORLD";
+    let default = FindOptions::default();
+    assert_eq!(find_text(text, "hello", default), Some(0..5));
+    assert_eq!(find_text(text, "world", FindOptions { start: 7, ..default }), Some(19..24));
+    assert_eq!(find_text(text, "hello", FindOptions { match_case: true, ..default }), Some(13..18));
+    assert_eq!(find_text(text, "Hello", FindOptions { match_case: true, start: 1, ..default }), None);
+    assert_eq!(find_text(text, "kiwi", default), None);
+    assert_eq!(find_text(text, "", default), None);
+    assert_eq!(find_text(text, "hello", FindOptions { start: 100, ..default }), None);
+}
+
+#[test]
+fn find_whole_word() {
+    let text = "cat concat cat_food cat";
+    let whole_word = FindOptions { whole_word: true, ..Default::default() };
+    assert_eq!(find_text(text, "cat", whole_word), Some(0..3));
+    assert_eq!(find_text(text, "cat", FindOptions { start: 1, ..whole_word }), Some(20..23));
+    assert_eq!(find_text(text, "con", whole_word), None);
+
+    // The character before the start offset is used to check the word boundary
+    assert_eq!(find_text("concat", "cat", FindOptions { start: 3, ..whole_word }), None);
+}
+
+#[test]
+fn find_utf16_ranges() {
+    let text = "😀 Café, CAFÉ";
+    let default = FindOptions::default();
+    assert_eq!(find_text(text, "café", default), Some(3..7));
+    assert_eq!(find_text(text, "café", FindOptions { start: 4, ..default }), Some(9..13));
+    assert_eq!(find_text(text, "😀", default), Some(0..2));
+
+    // The result can be used to slice the text
+    let range = find_text(text, "CAFÉ", FindOptions { match_case: true, ..default }).unwrap();
+    let start = utf16_offset_to_index(text, range.start).unwrap();
+    let end = utf16_offset_to_index(text, range.end).unwrap();
+    assert_eq!(&text[start..end], "CAFÉ");
+}
@@ -0,0 +1,108 @@
+use crate::*;
+
+#[test]
+fn integer_validator() {
+    let any = IntegerValidator::default();
+    assert_eq!(any.filter("-").as_deref(), Some("-"));
+    assert_eq!(any.filter("-12").as_deref(), Some("-12"));
+    assert_eq!(any.filter("").as_deref(), Some(""));
+    assert!(any.filter("1-2").is_none());
+    assert!(any.filter("12a").is_none());
+    assert!(any.filter("99999999999999999999").is_none());
+    assert!(any.is_valid("42"));
+    assert!(!any.is_valid("-"));
+    assert!(!any.is_valid(""));
+
+    // Digits that can never bring the value back in the range are rejected while typing
+    let byte = IntegerValidator::range(0, 255);
+    assert!(byte.filter("-1").is_none());
+    assert!(byte.filter("256").is_none());
+    assert_eq!(byte.filter("25").as_deref(), Some("25"));
+    assert!(byte.is_valid("255"));
+    assert!(!byte.is_valid("256"));
+
+    let signed = IntegerValidator::range(-50, 50);
+    assert!(signed.filter("-51").is_none());
+    assert!(signed.filter("51").is_none());
+    assert_eq!(signed.filter("-5").as_deref(), Some("-5"));
+
+    // A partial value can be out of range
+    let two_digits = IntegerValidator::range(10, 99);
+    assert_eq!(two_digits.filter("1").as_deref(), Some("1"));
+    assert!(!two_digits.is_valid("1"));
+}
+
+#[test]
+fn float_validator() {
+    let any = FloatValidator::default();
+    assert_eq!(any.filter("-").as_deref(), Some("-"));
+    assert_eq!(any.filter(".").as_deref(), Some("."));
+    assert_eq!(any.filter("-.5").as_deref(), Some("-.5"));
+    assert!(any.filter("1.2.3").is_none());
+    assert!(any.filter("1e5").is_none());
+    assert!(any.is_valid("1."));
+    assert!(any.is_valid("-.5"));
+    assert!(!any.is_valid("."));
+    assert!(!any.is_valid("-"));
+
+    let money = FloatValidator { decimals: Some(2), ..Default::default() };
+    assert_eq!(money.filter("1.23").as_deref(), Some("1.23"));
+    assert!(money.filter("1.234").is_none());
+
+    let integer = FloatValidator { decimals: Some(0), ..Default::default() };
+    assert!(integer.filter("1.").is_none());
+
+    let unit = FloatValidator::range(0.0, 1.0);
+    assert!(unit.filter("-0.5").is_none());
+    assert!(unit.filter("2").is_none());
+    assert_eq!(unit.filter("1.5").as_deref(), Some("1.5"));
+    assert!(unit.is_valid("0.5"));
+    assert!(!unit.is_valid("1.5"));
+}
+
+#[test]
+fn input_mask_apply() {
+    let phone = InputMask::new("(000) 000-0000");
+    assert_eq!(phone.len(), 14);
+    assert_eq!(phone.apply("").as_deref(), Some(""));
+    assert_eq!(phone.apply("5551234").as_deref(), Some("(555) 123-4"));
+    assert_eq!(phone.apply("(555) 123-4567").as_deref(), Some("(555) 123-4567"));
+    assert!(phone.apply("555a").is_none());
+    assert!(phone.apply("55512345678").is_none());
+
+    // Literals that are no longer at the right position after an edit are ignored
+    assert_eq!(phone.apply("(1923) 456").as_deref(), Some("(192) 345-6"));
+
+    let date = InputMask::new("00/00/0000");
+    assert_eq!(date.apply("12/").as_deref(), Some("12/"));
+    assert_eq!(date.apply("12//").as_deref(), Some("12/"));
+    assert_eq!(date.apply("12312020").as_deref(), Some("12/31/2020"));
+
+    let plate = InputMask::new("aa-00");
+    assert_eq!(plate.apply("AB12").as_deref(), Some("AB-12"));
+    assert!(plate.apply("1").is_none());
+
+    let code = InputMask::new("***");
+    assert_eq!(code.apply("a1B").as_deref(), Some("a1B"));
+    assert!(code.apply("a1_").is_none());
+}
+
+#[test]
+fn input_mask_escape() {
+    let mask = InputMask::new(r"\0a-00");
+    assert_eq!(mask.len(), 5);
+    assert_eq!(mask.apply("b12").as_deref(), Some("0b-12"));
+    assert_eq!(mask.apply("0b-12").as_deref(), Some("0b-12"));
+    assert!(mask.is_complete("0b-12"));
+}
+
+#[test]
+fn input_mask_complete() {
+    let phone = InputMask::new("(000) 000-0000");
+    assert!(phone.is_complete("(555) 123-4567"));
+    assert!(phone.is_valid("(555) 123-4567"));
+    assert!(!phone.is_complete("(555) 123-456"));
+    assert!(!phone.is_complete("5551234567"));
+    assert!(!phone.is_complete(""));
+    assert_eq!(phone.filter("5551234567").as_deref(), Some("(555) 123-4567"));
+}
@@ -45,7 +45,7 @@ pub fn from_utf16(s: &[u16]) -> String {
 /**
     Read a string from a wide char pointer. Undefined behaviour if [ptr] is not null terminated.
 */
-#[cfg(any(feature = "file-dialog", feature = "winnls"))]
+#[cfg(any(feature = "file-dialog", feature = "winnls", feature = "list-view", feature = "tree-view"))]
 pub unsafe fn from_wide_ptr(ptr: *mut u16, length: Option<usize>) -> String {
     use std::slice::from_raw_parts;
 
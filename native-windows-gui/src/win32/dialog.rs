@@ -0,0 +1,167 @@
+/*!
+    Run a `NativeUi` structure as a modal dialog. The owner window is disabled and a nested events loop
+    is executed until the dialog window is closed.
+*/
+use winapi::shared::windef::HWND;
+use crate::controls::ControlHandle;
+use crate::{NativeUi, NwgError, bind_raw_event_handler_inner, unbind_raw_event_handler};
+use std::ops::Deref;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::{mem, ptr};
+
+/// Raw handler id used to watch the dialog window. Ids <= 0xFFFF are reserved by NWG.
+const DIALOG_HANDLER_ID: usize = 0x4546;
+
+
+/**
+    A GUI structure that can be executed as a modal dialog with `Dialog::run`.
+
+    ```rust
+    use native_windows_gui as nwg;
+    use std::cell::Cell;
+
+    #[derive(Default)]
+    pub struct ConfirmDialog {
+        window: nwg::Window,
+        accepted: Cell<bool>,
+    }
+
+    impl nwg::DialogUi for ConfirmDialog {
+        type Output = bool;
+
+        fn dialog_handle(&self) -> &nwg::ControlHandle {
+            &self.window.handle
+        }
+
+        fn dialog_output(&self) -> bool {
+            self.accepted.get()
+        }
+    }
+    ```
+*/
+pub trait DialogUi {
+    /// The value returned by `Dialog::run` once the dialog is closed
+    type Output;
+
+    /// Should return the handle of the top level window of the dialog.
+    fn dialog_handle(&self) -> &ControlHandle;
+
+    /// Should return the result of the dialog. Called once, after the dialog window was closed and before the dialog is freed.
+    fn dialog_output(&self) -> Self::Output;
+}
+
+
+/**
+    Executes a `NativeUi` structure as a modal dialog.
+
+    The dialog window is closed when it is hidden. This happens when the user clicks on the X button
+    (unless `OnWindowClose` cancels it), when `Window::close` is called or when `Window::set_visible(false)` is called.
+    There's no need to call `stop_thread_dispatch` from the dialog. Doing so will close the dialog AND stop the events loop of the owner.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn ask_confirmation<D, UI>(window: &nwg::Window, dialog: D) -> bool
+        where D: nwg::NativeUi<UI> + nwg::DialogUi<Output=bool>,
+              UI: std::ops::Deref<Target=D>
+    {
+        nwg::Dialog::run(Some(window), dialog).unwrap_or(false)
+    }
+    ```
+*/
+pub struct Dialog;
+
+impl Dialog {
+
+    /**
+        Builds the dialog from `initial_state`, disables the `owner` window and dispatch the events of the thread
+        until the dialog window is closed. Returns the value of `DialogUi::dialog_output`.
+
+        The dialog ui is freed before this function returns.
+
+        Errors:
+          - If `build_ui` returns an error, it is forwarded to the caller
+          - If the dialog handle is not a window
+    */
+    pub fn run<C, D, UI>(owner: Option<C>, initial_state: D) -> Result<D::Output, NwgError>
+        where C: Into<ControlHandle>,
+              D: NativeUi<UI> + DialogUi,
+              UI: Deref<Target=D>
+    {
+        use winapi::um::winuser::{WM_SHOWWINDOW, WM_DESTROY, GWLP_HWNDPARENT, SW_SHOW};
+        use winapi::um::winuser::{IsWindowEnabled, EnableWindow, SetActiveWindow, ShowWindow};
+        use crate::win32::window_helper as wh;
+
+        let owner: Option<HWND> = owner.and_then(|o| o.into().hwnd());
+
+        let ui = D::build_ui(initial_state)?;
+        let dialog_handle = ui.dialog_handle().clone();
+        let dialog_hwnd = match dialog_handle.hwnd() {
+            Some(h) => h,
+            None => { return Err(NwgError::control_create("Dialog handle must be a window")); }
+        };
+
+        let closed = Rc::new(Cell::new(false));
+        let closed_handler = closed.clone();
+        let handler = bind_raw_event_handler_inner(&dialog_handle, DIALOG_HANDLER_ID, move |_hwnd, msg, w, l| {
+            match msg {
+                // `l` is 0 when the window is hidden by `ShowWindow`, and not because the owner was minimized
+                WM_SHOWWINDOW if w == 0 && l == 0 => closed_handler.set(true),
+                WM_DESTROY => closed_handler.set(true),
+                _ => {}
+            }
+            None
+        })?;
+
+        let owner_was_enabled = owner.map(|o| unsafe { IsWindowEnabled(o) != 0 }).unwrap_or(false);
+        if let Some(owner) = owner {
+            wh::set_window_long(dialog_hwnd, GWLP_HWNDPARENT, owner as usize);
+            unsafe { EnableWindow(owner, 0); }
+        }
+
+        unsafe { ShowWindow(dialog_hwnd, SW_SHOW); }
+
+        run_modal_loop(&closed);
+
+        // The owner must be enabled before the dialog is freed, otherwise windows will activate another application
+        if let Some(owner) = owner {
+            unsafe {
+                if owner_was_enabled {
+                    EnableWindow(owner, 1);
+                }
+                SetActiveWindow(owner);
+            }
+        }
+
+        drop(unbind_raw_event_handler(&handler));
+
+        let output = ui.dialog_output();
+        mem::drop(ui);
+
+        Ok(output)
+    }
+
+}
+
+/// A copy of `dispatch_thread_events` that stops when the dialog is closed.
+/// If a `WM_QUIT` message is received, it is posted again so that the outer loop can also exit.
+fn run_modal_loop(closed: &Cell<bool>) {
+    use winapi::um::winuser::{MSG, GA_ROOT};
+    use winapi::um::winuser::{GetMessageW, PostQuitMessage, IsDialogMessageW, GetAncestor, TranslateMessage, DispatchMessageW};
+
+    unsafe {
+        let mut msg: MSG = mem::zeroed();
+        while !closed.get() {
+            if GetMessageW(&mut msg, ptr::null_mut(), 0, 0) == 0 {
+                PostQuitMessage(msg.wParam as i32);
+                break;
+            }
+
+            if IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+/*!
+    Line oriented messages shared by the multi line edit controls (`TextBox` and `RichTextBox`).
+    All the character offsets are in UTF-16 code units.
+*/
+use winapi::shared::windef::HWND;
+use winapi::shared::minwindef::{WPARAM, LPARAM};
+use super::base_helper::to_utf16;
+use super::window_helper as wh;
+
+
+pub fn line_count(handle: HWND) -> u32 {
+    use winapi::um::winuser::EM_GETLINECOUNT;
+    wh::send_message(handle, EM_GETLINECOUNT as u32, 0, 0) as u32
+}
+
+/// Returns the offset of the first character of `line`, or `None` if the line does not exist
+pub fn char_from_line(handle: HWND, line: u32) -> Option<u32> {
+    use winapi::um::winuser::EM_LINEINDEX;
+
+    match wh::send_message(handle, EM_LINEINDEX as u32, line as WPARAM, 0) {
+        -1 => None,
+        index => Some(index as u32)
+    }
+}
+
+pub fn line_from_char(handle: HWND, offset: u32) -> u32 {
+    use winapi::um::winuser::EM_LINEFROMCHAR;
+    wh::send_message(handle, EM_LINEFROMCHAR as u32, offset as WPARAM, 0) as u32
+}
+
+/// Returns the text of `line` without the line break
+pub fn line(handle: HWND, line: u32) -> Option<String> {
+    use winapi::um::winuser::{EM_LINELENGTH, EM_GETLINE};
+
+    let index = char_from_line(handle, line)?;
+    let length = wh::send_message(handle, EM_LINELENGTH as u32, index as WPARAM, 0) as usize;
+
+    // The first word of the buffer is its size. The copied text is not null terminated.
+    let mut buffer: Vec<u16> = vec![0; length.max(1)];
+    buffer[0] = buffer.len().min(u16::max_value() as usize) as u16;
+    let copied = wh::send_message(handle, EM_GETLINE as u32, line as WPARAM, buffer.as_mut_ptr() as LPARAM) as usize;
+
+    let text = String::from_utf16_lossy(&buffer[..copied.min(length)]);
+    Some(text.trim_end_matches(|c| c == '\r' || c == '\n').to_string())
+}
+
+/// Scrolls vertically so that `line` is the first visible line
+pub fn scroll_to_line(handle: HWND, line: u32) {
+    use winapi::um::winuser::{EM_GETFIRSTVISIBLELINE, EM_LINESCROLL};
+
+    let first = wh::send_message(handle, EM_GETFIRSTVISIBLELINE as u32, 0, 0);
+    wh::send_message(handle, EM_LINESCROLL as u32, 0, line as LPARAM - first);
+}
+
+/// Replaces the selection with `text`. The change can be undone.
+pub fn replace_selection(handle: HWND, text: &str) {
+    use winapi::um::winuser::EM_REPLACESEL;
+
+    let text = to_utf16(text);
+    wh::send_message(handle, EM_REPLACESEL as u32, 1, text.as_ptr() as LPARAM);
+}
@@ -0,0 +1,538 @@
+/*!
+    Record the events dispatched by NWG into a text log and replay them later. The log identifies controls by name
+    (ex: the field names of a derived ui) so that a recording can be replayed against a new instance of the same UI.
+
+    The event data that cannot be serialized (ex: `PaintData`, `DropFiles` or tree items) is recorded as `Opaque`
+    and replayed as `EventData::NoData`.
+*/
+use winapi::shared::minwindef::LPARAM;
+use crate::controls::ControlHandle;
+use crate::win32::window_helper::NWG_REPLAY_EVENT;
+use crate::win32::window::{EventHandler, full_bind_event_handler};
+use crate::{Event, EventData, MousePressEvent, ScrollAction, WindowCloseData, NamedControls, NwgError};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::fmt;
+
+
+const MOUSE_PRESS_EVENTS: &'static [MousePressEvent] = &[
+    MousePressEvent::MousePressLeftUp, MousePressEvent::MousePressLeftDown,
+    MousePressEvent::MousePressRightUp, MousePressEvent::MousePressRightDown
+];
+
+/// The scroll actions and their name in the text format
+const SCROLL_ACTIONS: &'static [(ScrollAction, &'static str)] = &[
+    (ScrollAction::LineUp, "line_up"), (ScrollAction::LineDown, "line_down"), (ScrollAction::PageUp, "page_up"),
+    (ScrollAction::PageDown, "page_down"), (ScrollAction::ThumbTrack, "thumb_track"), (ScrollAction::ThumbPosition, "thumb_position"),
+    (ScrollAction::Top, "top"), (ScrollAction::Bottom, "bottom"), (ScrollAction::EndScroll, "end"), (ScrollAction::Unknown, "unknown"),
+];
+
+
+/**
+    A serializable copy of `EventData`.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecordedEventData {
+    /// The event had no data
+    NoData,
+
+    /// The event data could not be recorded. Replayed as `EventData::NoData`
+    Opaque,
+
+    /// A `OnWindowClose` event. Replayed with `WindowCloseData::closing` set to true.
+    WindowClose,
+
+    /// The character of a `OnChar` event
+    Char(char),
+
+    /// The virtual key code of a `OnKeyPress` or `OnKeyRelease` event
+    Key(u32),
+
+    /// The delta of a `OnMouseWheel` event
+    MouseWheel(i32),
+
+    /// The valid state of a `OnValidationChanged` event
+    ValidationChanged(bool),
+
+    /// The position and the action of a scroll event
+    Scroll { position: i32, action: ScrollAction },
+
+    /// The new value of a `OnNumberSelectChanged` event
+    #[cfg(feature="number-select")]
+    NumberSelectChanged(crate::NumberSelectValue),
+
+    /// Row index and column index of a list view item
+    #[cfg(feature="list-view")]
+    ListViewItemIndex { row_index: usize, column_index: usize },
+
+    /// Row index, column index and selected state of a list view item
+    #[cfg(feature="list-view")]
+    ListViewItemChanged { row_index: usize, column_index: usize, selected: bool },
+
+    /// Row index and checked state of a list view item
+    #[cfg(feature="list-view")]
+    ListViewItemChecked { row_index: usize, checked: bool },
+}
+
+impl RecordedEventData {
+
+    /// Creates a serializable copy of `data`
+    pub fn from_event_data(data: &EventData) -> RecordedEventData {
+        match data {
+            EventData::NoData => RecordedEventData::NoData,
+            EventData::OnWindowClose(_) => RecordedEventData::WindowClose,
+            &EventData::OnChar(c) => RecordedEventData::Char(c),
+            &EventData::OnKey(k) => RecordedEventData::Key(k),
+            &EventData::OnMouseWheel(d) => RecordedEventData::MouseWheel(d),
+            &EventData::OnValidationChanged(v) => RecordedEventData::ValidationChanged(v),
+            &EventData::OnScroll { position, action } => RecordedEventData::Scroll { position, action },
+
+            #[cfg(feature="number-select")]
+            &EventData::OnNumberSelectChanged(v) => RecordedEventData::NumberSelectChanged(v),
+
+            #[cfg(feature="list-view")]
+            &EventData::OnListViewItemIndex { row_index, column_index } => RecordedEventData::ListViewItemIndex { row_index, column_index },
+
+            #[cfg(feature="list-view")]
+            &EventData::OnListViewItemChanged { row_index, column_index, selected } => RecordedEventData::ListViewItemChanged { row_index, column_index, selected },
+
+            #[cfg(feature="list-view")]
+            &EventData::OnListViewItemChecked { row_index, checked } => RecordedEventData::ListViewItemChecked { row_index, checked },
+
+            _ => RecordedEventData::Opaque
+        }
+    }
+
+    /// Rebuilds the event data. `closing` is used as the storage of `WindowCloseData`
+    pub(crate) fn to_event_data(&self, closing: *mut bool) -> EventData {
+        match self {
+            RecordedEventData::NoData | RecordedEventData::Opaque => EventData::NoData,
+            RecordedEventData::WindowClose => EventData::OnWindowClose(WindowCloseData { data: closing }),
+            &RecordedEventData::Char(c) => EventData::OnChar(c),
+            &RecordedEventData::Key(k) => EventData::OnKey(k),
+            &RecordedEventData::MouseWheel(d) => EventData::OnMouseWheel(d),
+            &RecordedEventData::ValidationChanged(v) => EventData::OnValidationChanged(v),
+            &RecordedEventData::Scroll { position, action } => EventData::OnScroll { position, action },
+
+            #[cfg(feature="number-select")]
+            &RecordedEventData::NumberSelectChanged(v) => EventData::OnNumberSelectChanged(v),
+
+            #[cfg(feature="list-view")]
+            &RecordedEventData::ListViewItemIndex { row_index, column_index } => EventData::OnListViewItemIndex { row_index, column_index },
+
+            #[cfg(feature="list-view")]
+            &RecordedEventData::ListViewItemChanged { row_index, column_index, selected } => EventData::OnListViewItemChanged { row_index, column_index, selected },
+
+            #[cfg(feature="list-view")]
+            &RecordedEventData::ListViewItemChecked { row_index, checked } => EventData::OnListViewItemChecked { row_index, checked },
+        }
+    }
+
+    fn parse(value: &str) -> Option<RecordedEventData> {
+        let (kind, args) = match value.find(':') {
+            Some(i) => (&value[..i], &value[i+1..]),
+            None => (value, "")
+        };
+
+        let args: Vec<&str> = args.split(',').collect();
+        let data = match kind {
+            "-" => RecordedEventData::NoData,
+            "opaque" => RecordedEventData::Opaque,
+            "close" => RecordedEventData::WindowClose,
+            "char" => RecordedEventData::Char(std::char::from_u32(args[0].parse().ok()?)?),
+            "key" => RecordedEventData::Key(args[0].parse().ok()?),
+            "wheel" => RecordedEventData::MouseWheel(args[0].parse().ok()?),
+            "valid" => RecordedEventData::ValidationChanged(args[0] == "1"),
+            "scroll" if args.len() == 2 => RecordedEventData::Scroll {
+                position: args[0].parse().ok()?,
+                action: SCROLL_ACTIONS.iter().find(|(_, name)| *name == args[1])?.0,
+            },
+
+            #[cfg(feature="number-select")]
+            "number_int" => RecordedEventData::NumberSelectChanged(crate::NumberSelectValue::Int(args[0].parse().ok()?)),
+
+            #[cfg(feature="number-select")]
+            "number_float" => RecordedEventData::NumberSelectChanged(crate::NumberSelectValue::Float(args[0].parse().ok()?)),
+
+            #[cfg(feature="list-view")]
+            "lv_index" if args.len() == 2 => RecordedEventData::ListViewItemIndex {
+                row_index: args[0].parse().ok()?,
+                column_index: args[1].parse().ok()?,
+            },
+
+            #[cfg(feature="list-view")]
+            "lv_changed" if args.len() == 3 => RecordedEventData::ListViewItemChanged {
+                row_index: args[0].parse().ok()?,
+                column_index: args[1].parse().ok()?,
+                selected: args[2] == "1",
+            },
+
+            #[cfg(feature="list-view")]
+            "lv_checked" if args.len() == 2 => RecordedEventData::ListViewItemChecked {
+                row_index: args[0].parse().ok()?,
+                checked: args[1] == "1",
+            },
+
+            _ => { return None; }
+        };
+
+        Some(data)
+    }
+
+}
+
+impl fmt::Display for RecordedEventData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordedEventData::NoData => write!(f, "-"),
+            RecordedEventData::Opaque => write!(f, "opaque"),
+            RecordedEventData::WindowClose => write!(f, "close"),
+            RecordedEventData::Char(c) => write!(f, "char:{}", *c as u32),
+            RecordedEventData::Key(k) => write!(f, "key:{}", k),
+            RecordedEventData::MouseWheel(d) => write!(f, "wheel:{}", d),
+            RecordedEventData::ValidationChanged(v) => write!(f, "valid:{}", *v as u8),
+            RecordedEventData::Scroll { position, action } => {
+                let name = SCROLL_ACTIONS.iter().find(|(a, _)| a == action).map(|(_, name)| *name).unwrap_or("unknown");
+                write!(f, "scroll:{},{}", position, name)
+            },
+
+            #[cfg(feature="number-select")]
+            RecordedEventData::NumberSelectChanged(crate::NumberSelectValue::Int(v)) => write!(f, "number_int:{}", v),
+
+            #[cfg(feature="number-select")]
+            RecordedEventData::NumberSelectChanged(crate::NumberSelectValue::Float(v)) => write!(f, "number_float:{}", v),
+
+            #[cfg(feature="list-view")]
+            RecordedEventData::ListViewItemIndex { row_index, column_index } => write!(f, "lv_index:{},{}", row_index, column_index),
+
+            #[cfg(feature="list-view")]
+            RecordedEventData::ListViewItemChanged { row_index, column_index, selected } => write!(f, "lv_changed:{},{},{}", row_index, column_index, *selected as u8),
+
+            #[cfg(feature="list-view")]
+            RecordedEventData::ListViewItemChecked { row_index, checked } => write!(f, "lv_checked:{},{}", row_index, *checked as u8),
+        }
+    }
+}
+
+
+/**
+    A single recorded event
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventRecord {
+    /// The name of the control that raised the event
+    pub control: String,
+
+    /// The event raised
+    pub event: Event,
+
+    /// The data of the event
+    pub data: RecordedEventData,
+}
+
+
+/**
+    A list of recorded events. The log can be converted to text using `to_string` and read back with `EventLog::parse`.
+
+    The text format has one event per line: `CONTROL_NAME<TAB>EVENT<TAB>DATA`. Empty lines and lines starting with `#` are ignored.
+
+    ```text
+    # A comment
+    window	OnInit	-
+    name_edit	OnChar	char:97
+    button	OnButtonClick	-
+    window	OnWindowClose	close
+    ```
+*/
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventLog {
+    pub records: Vec<EventRecord>
+}
+
+impl EventLog {
+
+    pub fn new() -> EventLog {
+        EventLog { records: Vec::new() }
+    }
+
+    /// Parse a log previously written with `to_string`
+    pub fn parse(text: &str) -> Result<EventLog, NwgError> {
+        let mut records = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let bad_line = || NwgError::event_log(format!("Invalid event record on line {}: {:?}", i+1, line));
+
+            let mut parts = line.split('\t');
+            let control = parts.next().ok_or_else(bad_line)?;
+            let event = parts.next().and_then(parse_event).ok_or_else(bad_line)?;
+            let data = parts.next().and_then(RecordedEventData::parse).ok_or_else(bad_line)?;
+            if parts.next().is_some() {
+                return Err(bad_line());
+            }
+
+            records.push(EventRecord { control: control.to_string(), event, data });
+        }
+
+        Ok(EventLog { records })
+    }
+
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in self.records.iter() {
+            writeln!(f, "{}\t{:?}\t{}", r.control, r.event, r.data)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_event(value: &str) -> Option<Event> {
+    if value.starts_with("Custom(") && value.ends_with(')') {
+        let id = &value[7..value.len()-1];
+        return id.parse().ok().map(Event::Custom);
+    }
+
+    if value.starts_with("OnMousePress(") && value.ends_with(')') {
+        let press = &value[13..value.len()-1];
+        return MOUSE_PRESS_EVENTS.iter()
+            .find(|p| format!("{:?}", p) == press)
+            .map(|p| Event::OnMousePress(*p));
+    }
+
+    Event::from_name(value)
+}
+
+/// Panics if the control name cannot be written in a log
+fn check_control_name(name: &str) {
+    if name.is_empty() || name.starts_with('#') || name.contains(|c| c == '\t' || c == '\n' || c == '\r') {
+        panic!("Invalid control name for an event log: {:?}", name);
+    }
+}
+
+
+struct EventRecorderInner {
+    controls: Vec<(ControlHandle, String)>,
+    ignored: Vec<Event>,
+    recording: bool,
+    log: EventLog,
+}
+
+/**
+    Records the events raised by named controls into an `EventLog`.
+
+    Events raised by controls that were not registered are not recorded. By default, `OnPaint`, `OnMouseMove` and `OnTooltipText` are ignored.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn start_recording(window: &nwg::Window, button: &nwg::Button) -> (nwg::EventRecorder, nwg::EventHandler) {
+        let recorder = nwg::EventRecorder::new();
+        recorder.register("window", &window.handle);
+        recorder.register("button", &button.handle);
+
+        let handler = recorder.bind(&window.handle);
+        (recorder, handler)
+    }
+    ```
+*/
+#[derive(Clone)]
+pub struct EventRecorder {
+    inner: Rc<RefCell<EventRecorderInner>>
+}
+
+impl EventRecorder {
+
+    pub fn new() -> EventRecorder {
+        let inner = EventRecorderInner {
+            controls: Vec::new(),
+            ignored: vec![Event::OnPaint, Event::OnMouseMove, Event::OnTooltipText],
+            recording: true,
+            log: EventLog::new(),
+        };
+
+        EventRecorder { inner: Rc::new(RefCell::new(inner)) }
+    }
+
+    /// Associates a name to a control. Panics if the name is empty, starts with `#` or contains tabs or new lines.
+    pub fn register<S: Into<String>>(&self, name: S, handle: &ControlHandle) {
+        let name = name.into();
+        check_control_name(&name);
+
+        let mut inner = self.inner.borrow_mut();
+        inner.controls.retain(|(h, _)| h != handle);
+        inner.controls.push((*handle, name));
+    }
+
+    /// Registers every control of `ui` with its name. When `ui` is derived with `NwgUi`, the names are the field names of the controls.
+    pub fn register_all(&self, ui: &dyn NamedControls) {
+        for (name, handle) in ui.named_controls() {
+            self.register(name, &handle);
+        }
+    }
+
+    /// Stops recording `evt`
+    pub fn ignore(&self, evt: Event) {
+        self.inner.borrow_mut().ignored.push(evt);
+    }
+
+    /// Pause or resume the recording
+    pub fn set_recording(&self, recording: bool) {
+        self.inner.borrow_mut().recording = recording;
+    }
+
+    /// Returns true if the recorder is recording
+    pub fn recording(&self) -> bool {
+        self.inner.borrow().recording
+    }
+
+    /// Records an event. Can be called from an existing event handler if `bind` is not used.
+    pub fn record(&self, evt: Event, evt_data: &EventData, handle: ControlHandle) {
+        let mut inner = self.inner.borrow_mut();
+        if !inner.recording || inner.ignored.contains(&evt) {
+            return;
+        }
+
+        let control = match inner.controls.iter().find(|(h, _)| *h == handle) {
+            Some((_, name)) => name.clone(),
+            None => { return; }
+        };
+
+        let data = RecordedEventData::from_event_data(evt_data);
+        inner.log.records.push(EventRecord { control, event: evt, data });
+    }
+
+    /// Binds an event handler that records the events of `window` and its children. See `full_bind_event_handler`.
+    /// The handler must be unbound with `unbind_event_handler`.
+    pub fn bind(&self, window: &ControlHandle) -> EventHandler {
+        let recorder = self.clone();
+        full_bind_event_handler(window, move |evt, evt_data, handle| {
+            recorder.record(evt, &evt_data, handle);
+        })
+    }
+
+    /// Returns a copy of the recorded events
+    pub fn log(&self) -> EventLog {
+        self.inner.borrow().log.clone()
+    }
+
+    /// Returns the recorded events and clears the recorder log
+    pub fn take_log(&self) -> EventLog {
+        std::mem::replace(&mut self.inner.borrow_mut().log, EventLog::new())
+    }
+
+}
+
+
+/// The event sent by `EventReplay::send`
+pub(crate) struct ReplayedEvent {
+    pub(crate) event: Event,
+    pub(crate) data: RecordedEventData,
+    pub(crate) handle: ControlHandle,
+    pub(crate) closing: Cell<bool>,
+}
+
+impl ReplayedEvent {
+
+    pub(crate) fn event_data(&self) -> EventData {
+        self.data.to_event_data(self.closing.as_ptr())
+    }
+
+}
+
+/**
+    Replays an `EventLog`. Each control name in the log must be registered in the replay with the handle of the new control.
+
+    `replay` calls a callback directly and does not need any window. It can be used to test the event processing
+    function of a ui without a display. `send` dispatches the events through the event handlers bound to a window.
+*/
+#[derive(Default)]
+pub struct EventReplay {
+    controls: Vec<(String, ControlHandle)>
+}
+
+impl EventReplay {
+
+    pub fn new() -> EventReplay {
+        EventReplay { controls: Vec::new() }
+    }
+
+    /// Associates a name of the log to a control
+    pub fn register<S: Into<String>>(&mut self, name: S, handle: &ControlHandle) {
+        let name = name.into();
+        self.controls.retain(|(n, _)| n != &name);
+        self.controls.push((name, *handle));
+    }
+
+    /// Associates the name of every control of `ui` to the control. When `ui` is derived with `NwgUi`, the names are the field names of the controls.
+    pub fn register_all(&mut self, ui: &dyn NamedControls) {
+        for (name, handle) in ui.named_controls() {
+            self.register(name, &handle);
+        }
+    }
+
+    /// Returns the handle associated with a control name
+    pub fn handle(&self, name: &str) -> Option<ControlHandle> {
+        self.controls.iter().find(|(n, _)| n == name).map(|(_, h)| *h)
+    }
+
+    /**
+        Calls `callback` for every event of the log, in order.
+
+        Returns an error without calling the callback if a control name of the log was not registered.
+    */
+    pub fn replay<F>(&self, log: &EventLog, mut callback: F) -> Result<(), NwgError>
+        where F: FnMut(Event, EventData, ControlHandle) -> ()
+    {
+        let handles = self.resolve(log)?;
+
+        for (record, handle) in log.records.iter().zip(handles) {
+            let mut closing = true;
+            callback(record.event, record.data.to_event_data(&mut closing), handle);
+        }
+
+        Ok(())
+    }
+
+    /**
+        Sends every event of the log to the event handlers bound to `window`, in order. The events are dispatched
+        synchronously and `send` returns once the last event was processed.
+
+        Only the event handlers are called. For example, replaying a `OnWindowClose` event won't close the window.
+
+        Errors:
+          - If `window` is not a window handle
+          - If a control name of the log was not registered
+    */
+    pub fn send(&self, log: &EventLog, window: &ControlHandle) -> Result<(), NwgError> {
+        use winapi::um::winuser::SendMessageW;
+
+        let hwnd = window.hwnd().ok_or_else(|| NwgError::event_log("Events can only be sent to a window control"))?;
+        let handles = self.resolve(log)?;
+
+        for (record, handle) in log.records.iter().zip(handles) {
+            let replayed = ReplayedEvent {
+                event: record.event,
+                data: record.data.clone(),
+                handle,
+                closing: Cell::new(true)
+            };
+
+            unsafe { SendMessageW(hwnd, NWG_REPLAY_EVENT, 0, &replayed as *const ReplayedEvent as LPARAM); }
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&self, log: &EventLog) -> Result<Vec<ControlHandle>, NwgError> {
+        log.records.iter()
+            .map(|r| self.handle(&r.control).ok_or_else(|| NwgError::event_log(format!("Control {:?} is not registered", r.control))))
+            .collect()
+    }
+
+}
@@ -0,0 +1,351 @@
+/*!
+    Drag and drop of the items of a tree view or a list view with the mouse.
+    The drop target can be any tree view or list view of the same thread.
+*/
+use winapi::shared::windef::{HWND, POINT};
+use winapi::shared::minwindef::LPARAM;
+use winapi::shared::basetsd::UINT_PTR;
+use crate::controls::ControlHandle;
+use crate::win32::window_helper as wh;
+use crate::{RawEventHandler, bind_raw_event_handler_inner, unbind_raw_event_handler};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::{mem, ptr};
+
+/// Raw handler id used to track the mouse on the source control. Ids <= 0xFFFF are reserved by NWG.
+const DRAG_HANDLER_ID: UINT_PTR = 0x4547;
+
+
+/// An item that can be dragged with `enable_item_drag`
+#[derive(Debug)]
+pub enum DragItem {
+    /// An item of a tree view
+    #[cfg(feature="tree-view")]
+    TreeItem(crate::TreeItem),
+
+    /// A row of a list view
+    #[cfg(feature="list-view")]
+    ListRow(usize),
+}
+
+impl Clone for DragItem {
+    fn clone(&self) -> Self {
+        match self {
+            #[cfg(feature="tree-view")]
+            DragItem::TreeItem(item) => DragItem::TreeItem(crate::TreeItem { handle: item.handle }),
+
+            #[cfg(feature="list-view")]
+            &DragItem::ListRow(row) => DragItem::ListRow(row),
+        }
+    }
+}
+
+impl PartialEq for DragItem {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature="tree-view")]
+            (DragItem::TreeItem(a), DragItem::TreeItem(b)) => a == b,
+
+            #[cfg(feature="list-view")]
+            (DragItem::ListRow(a), DragItem::ListRow(b)) => a == b,
+
+            #[allow(unreachable_patterns)]
+            _ => false
+        }
+    }
+}
+
+
+/**
+    A drag and drop operation of an item. Passed to the validation callback of `enable_item_drag`
+    while the user moves the item, and sent with `Event::OnItemDrop` when the item is dropped.
+*/
+#[derive(Clone, Debug)]
+pub struct ItemDrag {
+    /// The control where the drag started
+    pub source: ControlHandle,
+
+    /// The dragged item
+    pub item: DragItem,
+
+    /// The tree view or list view under the cursor
+    pub target: ControlHandle,
+
+    /// The item under the cursor. `None` if the cursor is over an empty part of `target`.
+    pub target_item: Option<DragItem>,
+}
+
+
+/// The handlers bound by `bind_item_drag`
+pub(crate) struct ItemDragHandler {
+    parent: RawEventHandler,
+    source: RawEventHandler,
+}
+
+impl ItemDragHandler {
+
+    pub(crate) fn unbind(self) {
+        drop(unbind_raw_event_handler(&self.parent));
+        drop(unbind_raw_event_handler(&self.source));
+    }
+
+}
+
+struct DragContext {
+    validator: Box<dyn Fn(&ItemDrag) -> bool>,
+
+    /// The current drag operation and whether it can be dropped on the current target
+    current: RefCell<Option<(ItemDrag, bool)>>,
+}
+
+
+/**
+    Starts a drag operation when `source` sends `TVN_BEGINDRAGW` or `LVN_BEGINDRAG` to its parent.
+    The cursor is captured by `source` until the mouse button is released.
+*/
+pub(crate) fn bind_item_drag(source: HWND, validator: Box<dyn Fn(&ItemDrag) -> bool>) -> ItemDragHandler {
+    use winapi::um::winuser::{WM_NOTIFY, NMHDR, WM_MOUSEMOVE, WM_LBUTTONUP, WM_CAPTURECHANGED, WM_KEYDOWN, VK_ESCAPE, SetCapture, ReleaseCapture};
+
+    let context = Rc::new(DragContext { validator, current: RefCell::new(None) });
+
+    // The address of the context is used as id because the control handle may already be used by another handler on the parent
+    let parent_id = Rc::as_ptr(&context) as UINT_PTR;
+    let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(source));
+    let parent_context = context.clone();
+    let parent = bind_raw_event_handler_inner(&parent_handle, parent_id, move |_hwnd, msg, _w, l| {
+        if msg != WM_NOTIFY {
+            return None;
+        }
+
+        unsafe {
+            let nmhdr = &*(l as *const NMHDR);
+            if nmhdr.hwndFrom != source {
+                return None;
+            }
+
+            if let Some(item) = begin_drag_item(nmhdr.code, l) {
+                let drag = ItemDrag {
+                    source: ControlHandle::Hwnd(source),
+                    item,
+                    target: ControlHandle::Hwnd(source),
+                    target_item: None,
+                };
+
+                *parent_context.current.borrow_mut() = Some((drag, false));
+                SetCapture(source);
+            }
+        }
+
+        None
+    });
+
+    let source_context = context;
+    let source_handler = bind_raw_event_handler_inner(&ControlHandle::Hwnd(source), DRAG_HANDLER_ID, move |_hwnd, msg, w, _l| {
+        if source_context.current.borrow().is_none() {
+            return None;
+        }
+
+        match msg {
+            WM_MOUSEMOVE => {
+                update_drag(&source_context);
+                Some(0)
+            },
+            WM_LBUTTONUP => {
+                let current = source_context.current.borrow_mut().take();
+                if let Some((drag, valid)) = current {
+                    set_drop_highlight(&drag.target, None);
+                    unsafe { ReleaseCapture(); }
+
+                    if valid {
+                        let data = &drag as *const ItemDrag;
+                        wh::send_message(source, wh::NWG_ITEM_DROP, 0, data as LPARAM);
+                    }
+                }
+                Some(0)
+            },
+            WM_KEYDOWN if w as i32 == VK_ESCAPE => {
+                unsafe { ReleaseCapture(); }
+                Some(0)
+            },
+            WM_CAPTURECHANGED => {
+                // Another window took the capture or the drag was cancelled
+                let current = source_context.current.borrow_mut().take();
+                if let Some((drag, _)) = current {
+                    set_drop_highlight(&drag.target, None);
+                }
+                None
+            },
+            _ => None
+        }
+    });
+
+    ItemDragHandler {
+        parent: parent.unwrap(),
+        source: source_handler.unwrap(),
+    }
+}
+
+/// Returns the item that the user started to drag, if `code` is a begin drag notification
+unsafe fn begin_drag_item(code: u32, l: LPARAM) -> Option<DragItem> {
+    match code {
+        #[cfg(feature="tree-view")]
+        winapi::um::commctrl::TVN_BEGINDRAGW => {
+            let data = &*(l as *const winapi::um::commctrl::NMTREEVIEWW);
+            Some(DragItem::TreeItem(crate::TreeItem { handle: data.itemNew.hItem }))
+        },
+
+        #[cfg(feature="list-view")]
+        winapi::um::commctrl::LVN_BEGINDRAG => {
+            let data = &*(l as *const winapi::um::commctrl::NMLISTVIEW);
+            Some(DragItem::ListRow(data.iItem as usize))
+        },
+
+        _ => None
+    }
+}
+
+/// Finds the control and the item under the cursor, validates the drop and updates the highlight and the cursor
+fn update_drag(context: &DragContext) {
+    use winapi::um::winuser::{GetCursorPos, WindowFromPoint, SetCursor, LoadCursorW, IDC_ARROW, IDC_NO};
+
+    let mut current = context.current.borrow_mut();
+    let (drag, valid) = match current.as_mut() {
+        Some(c) => c,
+        None => { return; }
+    };
+
+    let mut point: POINT = unsafe { mem::zeroed() };
+    unsafe { GetCursorPos(&mut point); }
+
+    let target = unsafe { WindowFromPoint(point) };
+    let target_item = hit_test(target, point);
+
+    set_drop_highlight(&drag.target, None);
+
+    *valid = match target_item {
+        Some(target_item) => {
+            drag.target = ControlHandle::Hwnd(target);
+            drag.target_item = target_item;
+            (context.validator)(drag)
+        },
+        None => false
+    };
+
+    if *valid {
+        set_drop_highlight(&drag.target, drag.target_item.as_ref());
+    }
+
+    unsafe {
+        let cursor = match *valid {
+            true => IDC_ARROW,
+            false => IDC_NO
+        };
+        SetCursor(LoadCursorW(ptr::null_mut(), cursor));
+    }
+}
+
+/// Returns the item of the tree view or list view `target` at the screen position `point`.
+/// Returns `None` if `target` is not a tree view or a list view.
+fn hit_test(target: HWND, point: POINT) -> Option<Option<DragItem>> {
+    use winapi::um::winuser::ScreenToClient;
+
+    let class_name = local_class_name(target)?;
+
+    let mut point = point;
+    unsafe { ScreenToClient(target, &mut point); }
+
+    match &class_name as &str {
+        #[cfg(feature="tree-view")]
+        winapi::um::commctrl::WC_TREEVIEW => {
+            use winapi::um::commctrl::{TVHITTESTINFO, TVM_HITTEST};
+
+            let mut info: TVHITTESTINFO = unsafe { mem::zeroed() };
+            info.pt = point;
+            wh::send_message(target, TVM_HITTEST, 0, &mut info as *mut TVHITTESTINFO as LPARAM);
+
+            match info.hItem.is_null() {
+                true => Some(None),
+                false => Some(Some(DragItem::TreeItem(crate::TreeItem { handle: info.hItem })))
+            }
+        },
+
+        #[cfg(feature="list-view")]
+        winapi::um::commctrl::WC_LISTVIEW => {
+            use winapi::um::commctrl::{LVHITTESTINFO, LVM_HITTEST};
+
+            let mut info: LVHITTESTINFO = unsafe { mem::zeroed() };
+            info.pt = point;
+            wh::send_message(target, LVM_HITTEST, 0, &mut info as *mut LVHITTESTINFO as LPARAM);
+
+            match info.iItem < 0 {
+                true => Some(None),
+                false => Some(Some(DragItem::ListRow(info.iItem as usize)))
+            }
+        },
+
+        _ => None
+    }
+}
+
+/// Highlights `item` as the drop target in `target`. If `item` is `None`, the highlight is removed.
+fn set_drop_highlight(target: &ControlHandle, item: Option<&DragItem>) {
+    let target = match target.hwnd() {
+        Some(h) => h,
+        None => { return; }
+    };
+
+    let class_name = match local_class_name(target) {
+        Some(c) => c,
+        None => { return; }
+    };
+
+    match &class_name as &str {
+        #[cfg(feature="tree-view")]
+        winapi::um::commctrl::WC_TREEVIEW => {
+            use winapi::um::commctrl::{TVM_SELECTITEM, TVGN_DROPHILITE};
+
+            let item = match item {
+                Some(DragItem::TreeItem(i)) => i.handle,
+                _ => ptr::null_mut()
+            };
+
+            wh::send_message(target, TVM_SELECTITEM, TVGN_DROPHILITE, item as LPARAM);
+        },
+
+        #[cfg(feature="list-view")]
+        winapi::um::commctrl::WC_LISTVIEW => {
+            use winapi::um::commctrl::{LVITEMW, LVM_SETITEMSTATE, LVIS_DROPHILITED};
+
+            let mut state: LVITEMW = unsafe { mem::zeroed() };
+            state.stateMask = LVIS_DROPHILITED;
+
+            // -1 removes the state from every item
+            wh::send_message(target, LVM_SETITEMSTATE, -1isize as _, &mut state as *mut LVITEMW as LPARAM);
+
+            if let Some(&DragItem::ListRow(row)) = item {
+                state.state = LVIS_DROPHILITED;
+                wh::send_message(target, LVM_SETITEMSTATE, row as _, &mut state as *mut LVITEMW as LPARAM);
+            }
+        },
+
+        _ => {}
+    }
+}
+
+/// Returns the class name of `handle` if the window belongs to the current thread.
+/// The drag messages carry pointers to this process memory, so they must never be sent to the windows of another process.
+fn local_class_name(handle: HWND) -> Option<String> {
+    use winapi::um::winuser::GetWindowThreadProcessId;
+    use winapi::um::processthreadsapi::GetCurrentThreadId;
+
+    let local = !handle.is_null() && unsafe { GetWindowThreadProcessId(handle, ptr::null_mut()) == GetCurrentThreadId() };
+    match local {
+        true => Some(unsafe { wh::get_window_class_name(handle) }),
+        false => None
+    }
+}
+
+/// Returns the data of a `NWG_ITEM_DROP` message
+pub(crate) unsafe fn dropped_item(l: LPARAM) -> ItemDrag {
+    (&*(l as *const ItemDrag)).clone()
+}
@@ -23,6 +23,24 @@ pub(crate) mod extern_canvas;
 #[cfg(feature = "image-decoder")]
 pub(crate) mod image_decoder;
 
+#[cfg(feature = "modal-dialog")]
+pub(crate) mod dialog;
+
+#[cfg(feature = "event-recorder")]
+pub(crate) mod event_recorder;
+
+#[cfg(any(feature = "tree-view", feature = "list-view"))]
+pub(crate) mod item_drag;
+
+#[cfg(any(feature = "listbox", feature = "combobox"))]
+pub(crate) mod owner_draw;
+
+#[cfg(feature = "rich-textbox")]
+pub(crate) mod richedit;
+
+#[cfg(any(feature = "textbox", feature = "rich-textbox"))]
+pub(crate) mod edit_helper;
+
 use std::{mem, ptr};
 use crate::errors::NwgError;
 
@@ -74,6 +92,95 @@ pub fn dispatch_thread_events_with_callback<F>(mut cb: F)
     }
 }
 
+
+/**
+    How `dispatch_thread_events_with_pacing` schedules its callback.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DispatchPacing {
+    /// The callback is executed each time the event queue becomes empty or when a frame is requested with `request_frame`.
+    OnIdle,
+
+    /// Same as `OnIdle`, but the callback is executed at most `n` times per second.
+    FrameRate(u32),
+}
+
+thread_local! {
+    static FRAME_REQUESTED: std::cell::Cell<bool> = std::cell::Cell::new(false);
+}
+
+/**
+    Ask `dispatch_thread_events_with_pacing` to execute its callback again, even if there are no new events to process.
+    Calling this function from inside the callback will produce a continuous animation at the selected `DispatchPacing`.
+
+    This function only affects the events loop running on the current thread.
+*/
+pub fn request_frame() {
+    FRAME_REQUESTED.with(|r| r.set(true));
+}
+
+/**
+    Dispatch system events in the current thread AND execute a callback when the events queue is idle.
+    Unlike `dispatch_thread_events_with_callback`, this method will pause the thread (using `MsgWaitForMultipleObjectsEx`)
+    if there are no events to process and no frame was requested with `request_frame`.
+
+    With `DispatchPacing::FrameRate`, the thread will also sleep until the next frame is due.
+*/
+pub fn dispatch_thread_events_with_pacing<F>(pacing: DispatchPacing, mut cb: F)
+    where F: FnMut() -> ()
+{
+    use winapi::um::winuser::MSG;
+    use winapi::um::winuser::{PeekMessageW, MsgWaitForMultipleObjectsEx, PM_REMOVE, WM_QUIT, QS_ALLINPUT, MWMO_INPUTAVAILABLE};
+    use winapi::um::winbase::INFINITE;
+    use std::time::{Duration, Instant};
+
+    let frame_time = match pacing {
+        DispatchPacing::OnIdle => Duration::from_millis(0),
+        DispatchPacing::FrameRate(fps) => Duration::from_secs(1) / fps.max(1),
+    };
+
+    let mut last_frame: Option<Instant> = None;
+    let mut dirty = true;
+
+    unsafe {
+        let mut msg: MSG = mem::zeroed();
+        loop {
+            while PeekMessageW(&mut msg, ptr::null_mut(), 0, 0, PM_REMOVE) != 0 {
+                if msg.message == WM_QUIT {
+                    return;
+                }
+
+                if IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
+                    TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+
+                dirty = true;
+            }
+
+            if FRAME_REQUESTED.with(|r| r.replace(false)) {
+                dirty = true;
+            }
+
+            let mut timeout = INFINITE;
+            if dirty {
+                let elapsed = last_frame.map(|f| f.elapsed()).unwrap_or(frame_time);
+                if elapsed >= frame_time {
+                    dirty = false;
+                    last_frame = Some(Instant::now());
+                    cb();
+                    continue;
+                }
+
+                // Round up so that the thread never wakes up before the frame is due
+                timeout = ((frame_time - elapsed).as_micros() as u32 + 999) / 1000;
+            }
+
+            MsgWaitForMultipleObjectsEx(0, ptr::null(), timeout, QS_ALLINPUT, MWMO_INPUTAVAILABLE);
+        }
+    }
+}
+
 /**
     Break the events loop running on the current thread
 */
@@ -140,7 +247,7 @@ pub fn init_common_controls() -> Result<(), NwgError> {
     unsafe {
         let mut classes = ICC_BAR_CLASSES | ICC_STANDARD_CLASSES;
 
-        if cfg!(feature = "datetime-picker") {
+        if cfg!(any(feature = "datetime-picker", feature = "month-calendar")) {
             classes |= ICC_DATE_CLASSES;
         }
 
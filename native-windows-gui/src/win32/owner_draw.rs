@@ -0,0 +1,301 @@
+/*!
+    Owner drawn items of the `ListBox` and the `ComboBox`. The items are rendered by an `ItemRenderer` when the collection
+    of the control changes, and drawn by NWG when the control sends `WM_DRAWITEM` to its parent.
+*/
+use winapi::shared::windef::{HWND, HFONT, HDC, RECT};
+use winapi::shared::minwindef::DWORD;
+use winapi::shared::basetsd::UINT_PTR;
+use winapi::um::commctrl::HIMAGELIST;
+use crate::controls::ControlHandle;
+use crate::win32::window_helper as wh;
+use crate::win32::base_helper::to_utf16;
+use crate::{Font, RawEventHandler, bind_raw_event_handler_inner};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::{mem, ptr};
+
+/// Space in pixels around the image and the text of an item
+const PADDING: i32 = 2;
+
+
+/**
+    How an item of an owner drawn `ListBox` or `ComboBox` is displayed. Returned by an `ItemRenderer`.
+
+    ```rust
+    use native_windows_gui as nwg;
+    let item = nwg::OwnerDrawItem {
+        image: Some(0),
+        secondary_text: Some("Administrator".to_string()),
+        ..nwg::OwnerDrawItem::new("Jane Doe")
+    };
+    ```
+*/
+#[derive(Debug, Default)]
+pub struct OwnerDrawItem {
+    /// The index of the image in the image list of the control. See `ListBox::set_image_list`
+    pub image: Option<i32>,
+
+    /// The first line of the item
+    pub text: String,
+
+    /// A second line displayed in gray below `text`. It is not displayed in the selection field of a combobox.
+    pub secondary_text: Option<String>,
+
+    /// The color of `text`. Ignored when the item is selected.
+    pub text_color: Option<[u8; 3]>,
+
+    /// The color of the background. Ignored when the item is selected.
+    pub background_color: Option<[u8; 3]>,
+
+    /// The font of `text`. Uses the font of the control if `None`.
+    /// The item only keeps the handle of the font, so the font must stay alive as long as the item is displayed.
+    pub font: Option<Font>,
+}
+
+impl Clone for OwnerDrawItem {
+    fn clone(&self) -> Self {
+        OwnerDrawItem {
+            image: self.image,
+            text: self.text.clone(),
+            secondary_text: self.secondary_text.clone(),
+            text_color: self.text_color,
+            background_color: self.background_color,
+            font: self.font.as_ref().map(|f| Font { handle: f.handle }),
+        }
+    }
+}
+
+impl OwnerDrawItem {
+
+    /// Creates an item that only displays `text`
+    pub fn new<S: Into<String>>(text: S) -> OwnerDrawItem {
+        OwnerDrawItem {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+}
+
+
+/**
+    Renders the items of the collection of an owner drawn `ListBox` or `ComboBox`. See the `renderer` builder parameter.
+    The items are rendered each time the collection of the control is updated, not each time they are painted.
+
+    Closures that take an item of the collection and return an `OwnerDrawItem` implement this trait.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    struct User { name: String, admin: bool }
+
+    fn user_renderer(user: &User) -> nwg::OwnerDrawItem {
+        nwg::OwnerDrawItem {
+            image: Some(if user.admin { 1 } else { 0 }),
+            text_color: if user.admin { Some([200, 0, 0]) } else { None },
+            ..nwg::OwnerDrawItem::new(user.name.as_str())
+        }
+    }
+    ```
+*/
+pub trait ItemRenderer<D> {
+    /// Returns how `item` is displayed
+    fn render(&self, item: &D) -> OwnerDrawItem;
+}
+
+impl<D, F> ItemRenderer<D> for F where F: Fn(&D) -> OwnerDrawItem {
+    fn render(&self, item: &D) -> OwnerDrawItem {
+        self(item)
+    }
+}
+
+
+/// The rendered items of a control, shared with the `WM_DRAWITEM` handler
+pub(crate) struct OwnerDraw {
+    pub(crate) items: RefCell<Vec<OwnerDrawItem>>,
+    pub(crate) image_list: Cell<HIMAGELIST>,
+}
+
+impl Default for OwnerDraw {
+    fn default() -> OwnerDraw {
+        OwnerDraw {
+            items: RefCell::new(Vec::new()),
+            image_list: Cell::new(ptr::null_mut()),
+        }
+    }
+}
+
+
+/**
+    Draws the items of `control` when it sends `WM_DRAWITEM` to its parent.
+    `item_index` maps the index of a displayed item to the index of its rendered item.
+*/
+pub(crate) fn bind_owner_draw<F>(control: HWND, state: Rc<OwnerDraw>, item_index: F) -> RawEventHandler
+    where F: Fn(usize) -> Option<usize> + 'static
+{
+    use winapi::um::winuser::{WM_DRAWITEM, DRAWITEMSTRUCT};
+
+    // The address of the state is used as id because the control handle may already be used by another handler on the parent
+    let id = Rc::as_ptr(&state) as UINT_PTR;
+    let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(control));
+
+    let handler = bind_raw_event_handler_inner(&parent_handle, id, move |_hwnd, msg, _w, l| {
+        if msg != WM_DRAWITEM {
+            return None;
+        }
+
+        let data = unsafe { &*(l as *const DRAWITEMSTRUCT) };
+        if data.hwndItem != control {
+            return None;
+        }
+
+        // The item id is -1 if the list is empty
+        let index = match data.itemID {
+            std::u32::MAX => None,
+            id => item_index(id as usize)
+        };
+
+        let items = state.items.borrow();
+        let item = index.and_then(|i| items.get(i));
+        unsafe { draw_item(control, &state, data, item); }
+
+        Some(1)
+    });
+
+    handler.unwrap()
+}
+
+unsafe fn draw_item(control: HWND, state: &OwnerDraw, data: &winapi::um::winuser::DRAWITEMSTRUCT, item: Option<&OwnerDrawItem>) {
+    use winapi::um::winuser::{FillRect, DrawFocusRect, GetSysColor, GetSysColorBrush, ODS_SELECTED, ODS_FOCUS, ODS_NOFOCUSRECT, ODS_COMBOBOXEDIT,
+        COLOR_HIGHLIGHT, COLOR_HIGHLIGHTTEXT, COLOR_WINDOW, COLOR_WINDOWTEXT, COLOR_GRAYTEXT, DT_VCENTER, DT_TOP, DT_BOTTOM};
+    use winapi::um::wingdi::{CreateSolidBrush, DeleteObject, SetBkMode, RGB, TRANSPARENT};
+    use winapi::um::commctrl::{ImageList_Draw, ILD_NORMAL, ILD_SELECTED};
+
+    let hdc = data.hDC;
+    let selected = data.itemState & ODS_SELECTED == ODS_SELECTED;
+
+    match (selected, item.and_then(|i| i.background_color)) {
+        (false, Some([r, g, b])) => {
+            let brush = CreateSolidBrush(RGB(r, g, b));
+            FillRect(hdc, &data.rcItem, brush);
+            DeleteObject(brush as _);
+        },
+        (true, _) => { FillRect(hdc, &data.rcItem, GetSysColorBrush(COLOR_HIGHLIGHT)); },
+        (false, None) => { FillRect(hdc, &data.rcItem, GetSysColorBrush(COLOR_WINDOW)); },
+    }
+
+    if let Some(item) = item {
+        let mut rect = data.rcItem;
+        rect.left += PADDING;
+
+        let image_list = state.image_list.get();
+        if let (Some(image), false) = (item.image, image_list.is_null()) {
+            let (width, height) = image_size(image_list);
+            let y = rect.top + ((rect.bottom - rect.top) - height) / 2;
+            let style = match selected {
+                true => ILD_SELECTED,
+                false => ILD_NORMAL
+            };
+
+            ImageList_Draw(image_list, image, hdc, rect.left, y, style);
+            rect.left += width + PADDING;
+        }
+
+        let text_color = match (selected, item.text_color) {
+            (true, _) => GetSysColor(COLOR_HIGHLIGHTTEXT),
+            (false, Some([r, g, b])) => RGB(r, g, b),
+            (false, None) => GetSysColor(COLOR_WINDOWTEXT),
+        };
+
+        let font = wh::get_window_font(control);
+        let text_font = item.font.as_ref().map(|f| f.handle).unwrap_or(font);
+        SetBkMode(hdc, TRANSPARENT as _);
+
+        // The selection field of a combobox is too small for two lines
+        let secondary_text = match data.itemState & ODS_COMBOBOXEDIT == ODS_COMBOBOXEDIT {
+            true => None,
+            false => item.secondary_text.as_ref()
+        };
+
+        match secondary_text {
+            Some(secondary_text) => {
+                let middle = rect.top + (rect.bottom - rect.top) / 2;
+                let secondary_color = match selected {
+                    true => text_color,
+                    false => GetSysColor(COLOR_GRAYTEXT)
+                };
+
+                draw_text(hdc, &item.text, RECT { bottom: middle, ..rect }, text_color, text_font, DT_BOTTOM);
+                draw_text(hdc, secondary_text, RECT { top: middle, ..rect }, secondary_color, font, DT_TOP);
+            },
+            None => {
+                draw_text(hdc, &item.text, rect, text_color, text_font, DT_VCENTER);
+            }
+        }
+    }
+
+    if data.itemState & ODS_FOCUS == ODS_FOCUS && data.itemState & ODS_NOFOCUSRECT == 0 {
+        DrawFocusRect(hdc, &data.rcItem);
+    }
+}
+
+unsafe fn draw_text(hdc: HDC, text: &str, mut rect: RECT, color: DWORD, font: HFONT, align: u32) {
+    use winapi::um::winuser::{DrawTextW, DT_SINGLELINE, DT_END_ELLIPSIS, DT_NOPREFIX};
+    use winapi::um::wingdi::{SetTextColor, SelectObject};
+
+    let text = to_utf16(text);
+    let old_font = SelectObject(hdc, font as _);
+    SetTextColor(hdc, color);
+    DrawTextW(hdc, text.as_ptr(), -1, &mut rect, DT_SINGLELINE | DT_END_ELLIPSIS | DT_NOPREFIX | align);
+    SelectObject(hdc, old_font);
+}
+
+fn image_size(image_list: HIMAGELIST) -> (i32, i32) {
+    use winapi::um::commctrl::ImageList_GetIconSize;
+
+    let (mut width, mut height) = (0, 0);
+    unsafe { ImageList_GetIconSize(image_list, &mut width, &mut height); }
+    (width, height)
+}
+
+/**
+    Returns the height of the items of `control`. The items have the same height: the largest text or image,
+    and a second line if any item has a secondary text.
+*/
+pub(crate) fn item_height(control: HWND, state: &OwnerDraw) -> u32 {
+    use winapi::um::winuser::{GetDC, ReleaseDC};
+    use winapi::um::wingdi::{GetTextMetricsW, SelectObject, TEXTMETRICW};
+
+    let items = state.items.borrow();
+
+    unsafe {
+        let hdc = GetDC(control);
+        let text_height = |font: HFONT| {
+            let mut metrics: TEXTMETRICW = mem::zeroed();
+            let old_font = SelectObject(hdc, font as _);
+            GetTextMetricsW(hdc, &mut metrics);
+            SelectObject(hdc, old_font);
+            metrics.tmHeight
+        };
+
+        let font = wh::get_window_font(control);
+        let line_height = text_height(font);
+        let first_line = items.iter()
+            .filter_map(|i| i.font.as_ref().map(|f| f.handle))
+            .fold(line_height, |height, font| height.max(text_height(font)));
+
+        let mut height = first_line;
+        if items.iter().any(|i| i.secondary_text.is_some()) {
+            height += line_height;
+        }
+
+        ReleaseDC(control, hdc);
+
+        let image_list = state.image_list.get();
+        if !image_list.is_null() {
+            height = height.max(image_size(image_list).1);
+        }
+
+        (height + PADDING * 2) as u32
+    }
+}
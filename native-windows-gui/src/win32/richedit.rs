@@ -0,0 +1,191 @@
+/*!
+    Rich edit definitions that are missing from winapi. See `Richedit.h`.
+*/
+use winapi::shared::minwindef::{UINT, DWORD, WORD, BYTE, LPBYTE, LPBOOL};
+use winapi::um::winnt::LPCSTR;
+use winapi::shared::basetsd::DWORD_PTR;
+use winapi::shared::windef::COLORREF;
+use winapi::shared::ntdef::{LONG, SHORT, LCID, WCHAR};
+use winapi::um::wingdi::LF_FACESIZE;
+use winapi::um::winuser::WM_USER;
+use winapi::um::oaidl::{IDispatch, IDispatchVtbl};
+use winapi::um::winnt::HRESULT;
+use winapi::ctypes::{c_void, c_float};
+use winapi::RIDL;
+
+pub const EM_GETCHARFORMAT: UINT = WM_USER + 58;
+pub const EM_GETEVENTMASK: UINT = WM_USER + 59;
+pub const EM_GETOLEINTERFACE: UINT = WM_USER + 60;
+pub const EM_GETPARAFORMAT: UINT = WM_USER + 61;
+pub const EM_SETCHARFORMAT: UINT = WM_USER + 68;
+pub const EM_SETEVENTMASK: UINT = WM_USER + 69;
+pub const EM_SETPARAFORMAT: UINT = WM_USER + 71;
+pub const EM_STREAMIN: UINT = WM_USER + 73;
+pub const EM_STREAMOUT: UINT = WM_USER + 74;
+pub const EM_GETTEXTEX: UINT = WM_USER + 94;
+pub const EM_GETTEXTLENGTHEX: UINT = WM_USER + 95;
+pub const EM_GETSCROLLPOS: UINT = WM_USER + 221;
+pub const EM_SETSCROLLPOS: UINT = WM_USER + 222;
+
+pub const ENM_CHANGE: DWORD = 0x00000001;
+
+pub const SF_RTF: UINT = 0x0002;
+pub const SFF_SELECTION: UINT = 0x8000;
+
+pub const SCF_SELECTION: UINT = 0x0001;
+
+pub const GT_DEFAULT: DWORD = 0;
+pub const GTL_PRECISE: DWORD = 2;
+pub const GTL_NUMCHARS: DWORD = 8;
+
+/// The code page of UTF-16 text in `GETTEXTEX` and `GETTEXTLENGTHEX`
+pub const CP_UNICODE: UINT = 1200;
+
+pub const CFM_BOLD: DWORD = 0x00000001;
+pub const CFM_ITALIC: DWORD = 0x00000002;
+pub const CFM_UNDERLINE: DWORD = 0x00000004;
+pub const CFM_STRIKEOUT: DWORD = 0x00000008;
+pub const CFM_BACKCOLOR: DWORD = 0x04000000;
+pub const CFM_FACE: DWORD = 0x20000000;
+pub const CFM_COLOR: DWORD = 0x40000000;
+pub const CFM_SIZE: DWORD = 0x80000000;
+
+pub const CFE_BOLD: DWORD = 0x00000001;
+pub const CFE_ITALIC: DWORD = 0x00000002;
+pub const CFE_UNDERLINE: DWORD = 0x00000004;
+pub const CFE_STRIKEOUT: DWORD = 0x00000008;
+pub const CFE_AUTOBACKCOLOR: DWORD = CFM_BACKCOLOR;
+pub const CFE_AUTOCOLOR: DWORD = CFM_COLOR;
+
+pub const PFM_STARTINDENT: DWORD = 0x00000001;
+pub const PFM_RIGHTINDENT: DWORD = 0x00000002;
+pub const PFM_OFFSET: DWORD = 0x00000004;
+pub const PFM_ALIGNMENT: DWORD = 0x00000008;
+pub const PFM_NUMBERING: DWORD = 0x00000020;
+pub const PFM_SPACEBEFORE: DWORD = 0x00000040;
+pub const PFM_SPACEAFTER: DWORD = 0x00000080;
+
+pub const PFA_LEFT: WORD = 1;
+pub const PFA_RIGHT: WORD = 2;
+pub const PFA_CENTER: WORD = 3;
+pub const PFA_JUSTIFY: WORD = 4;
+
+pub const PFN_BULLET: WORD = 1;
+pub const PFN_ARABIC: WORD = 2;
+pub const PFN_LCLETTER: WORD = 3;
+pub const PFN_UCLETTER: WORD = 4;
+pub const PFN_LCROMAN: WORD = 5;
+pub const PFN_UCROMAN: WORD = 6;
+
+pub const MAX_TAB_STOPS: usize = 32;
+
+#[repr(C)]
+#[allow(non_snake_case)]
+pub struct CHARFORMAT2W {
+    pub cbSize: UINT,
+    pub dwMask: DWORD,
+    pub dwEffects: DWORD,
+    pub yHeight: LONG,
+    pub yOffset: LONG,
+    pub crTextColor: COLORREF,
+    pub bCharSet: BYTE,
+    pub bPitchAndFamily: BYTE,
+    pub szFaceName: [WCHAR; LF_FACESIZE],
+    pub wWeight: WORD,
+    pub sSpacing: SHORT,
+    pub crBackColor: COLORREF,
+    pub lcid: LCID,
+    pub dwCookie: DWORD,
+    pub sStyle: SHORT,
+    pub wKerning: WORD,
+    pub bUnderlineType: BYTE,
+    pub bAnimation: BYTE,
+    pub bRevAuthor: BYTE,
+    pub bUnderlineColor: BYTE,
+}
+
+#[repr(C)]
+#[allow(non_snake_case)]
+pub struct PARAFORMAT2 {
+    pub cbSize: UINT,
+    pub dwMask: DWORD,
+    pub wNumbering: WORD,
+    pub wEffects: WORD,
+    pub dxStartIndent: LONG,
+    pub dxRightIndent: LONG,
+    pub dxOffset: LONG,
+    pub wAlignment: WORD,
+    pub cTabCount: SHORT,
+    pub rgxTabs: [LONG; MAX_TAB_STOPS],
+    pub dySpaceBefore: LONG,
+    pub dySpaceAfter: LONG,
+    pub dyLineSpacing: LONG,
+    pub sStyle: SHORT,
+    pub bLineSpacingRule: BYTE,
+    pub bOutlineLevel: BYTE,
+    pub wShadingWeight: WORD,
+    pub wShadingStyle: WORD,
+    pub wNumberingStart: WORD,
+    pub wNumberingStyle: WORD,
+    pub wNumberingTab: WORD,
+    pub wBorderSpace: WORD,
+    pub wBorderWidth: WORD,
+    pub wBorders: WORD,
+}
+
+/// Packed on 4 bytes like `EDITSTREAM`
+#[repr(C, packed(4))]
+#[allow(non_snake_case)]
+pub struct GETTEXTEX {
+    pub cb: DWORD,
+    pub flags: DWORD,
+    pub codepage: UINT,
+    pub lpDefaultChar: LPCSTR,
+    pub lpUsedDefChar: LPBOOL,
+}
+
+#[repr(C)]
+#[allow(non_snake_case)]
+pub struct GETTEXTLENGTHEX {
+    pub flags: DWORD,
+    pub codepage: UINT,
+}
+
+pub type EDITSTREAMCALLBACK = Option<unsafe extern "system" fn(DWORD_PTR, LPBYTE, LONG, *mut LONG) -> DWORD>;
+
+/// Like all the rich edit structures, `EDITSTREAM` is packed on 4 bytes. On 64 bits, it moves the pointers that follow a single `DWORD`.
+#[repr(C, packed(4))]
+#[allow(non_snake_case)]
+pub struct EDITSTREAM {
+    pub dwCookie: DWORD_PTR,
+    pub dwError: DWORD,
+    pub pfnCallback: EDITSTREAMCALLBACK,
+}
+
+/// Values of `ITextDocument::Undo` that suspend and resume the undo collection. See `tom.h`.
+pub const TOM_SUSPEND: LONG = -9999995;
+pub const TOM_RESUME: LONG = -9999994;
+
+// The methods that take tom interfaces, `BSTR` or `VARIANT` are declared with untyped pointers. Only `Undo` is used.
+RIDL!{#[uuid(0x8cc497c0, 0xa1df, 0x11ce, 0x80, 0x98, 0x00, 0xaa, 0x00, 0x47, 0xbe, 0x5d)]
+interface ITextDocument(ITextDocumentVtbl): IDispatch(IDispatchVtbl) {
+    fn GetName(pName: *mut *mut c_void,) -> HRESULT,
+    fn GetSelection(ppSel: *mut *mut c_void,) -> HRESULT,
+    fn GetStoryCount(pCount: *mut LONG,) -> HRESULT,
+    fn GetStoryRanges(ppStories: *mut *mut c_void,) -> HRESULT,
+    fn GetSaved(pValue: *mut LONG,) -> HRESULT,
+    fn SetSaved(Value: LONG,) -> HRESULT,
+    fn GetDefaultTabStop(pValue: *mut c_float,) -> HRESULT,
+    fn SetDefaultTabStop(Value: c_float,) -> HRESULT,
+    fn New() -> HRESULT,
+    fn Open(pVar: *mut c_void, Flags: LONG, CodePage: LONG,) -> HRESULT,
+    fn Save(pVar: *mut c_void, Flags: LONG, CodePage: LONG,) -> HRESULT,
+    fn Freeze(pCount: *mut LONG,) -> HRESULT,
+    fn Unfreeze(pCount: *mut LONG,) -> HRESULT,
+    fn BeginEditCollection() -> HRESULT,
+    fn EndEditCollection() -> HRESULT,
+    fn Undo(Count: LONG, pCount: *mut LONG,) -> HRESULT,
+    fn Redo(Count: LONG, pCount: *mut LONG,) -> HRESULT,
+    fn Range(cpActive: LONG, cpAnchor: LONG, ppRange: *mut *mut c_void,) -> HRESULT,
+    fn RangeFromPoint(x: LONG, y: LONG, ppRange: *mut *mut c_void,) -> HRESULT,
+}}
@@ -9,11 +9,18 @@ use winapi::shared::basetsd::{DWORD_PTR, UINT_PTR};
 use winapi::um::winuser::{WNDPROC, NMHDR};
 use winapi::um::commctrl::{NMTTDISPINFOW, SUBCLASSPROC};
 use super::base_helper::{CUSTOM_ID_BEGIN, to_utf16};
-use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TRAY};
+use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TRAY, NWG_CUSTOM_EVENT, NWG_VALIDATION_CHANGED};
+#[cfg(feature = "event-recorder")]
+use super::window_helper::NWG_REPLAY_EVENT;
+#[cfg(any(feature = "tree-view", feature = "list-view"))]
+use super::window_helper::NWG_ITEM_DROP;
+#[cfg(feature = "number-select")]
+use super::window_helper::NWG_NUMBER_SELECT_CHANGED;
 use super::high_dpi;
 use crate::controls::ControlHandle;
 use crate::{Event, EventData, NwgError};
 use std::{ptr, mem};
+use std::any::Any;
 use std::rc::Rc;
 
 
@@ -361,6 +368,77 @@ pub fn unbind_raw_event_handler(handler: &RawEventHandler) -> Result<(), NwgErro
     }
 }
 
+/// The payload of a custom event. Owned by the `free_custom_event` subclass of the window that receives the message.
+struct CustomEventRecord {
+    payload: Box<dyn Any>,
+}
+
+/**
+    Raise an application defined event on a window control. The event is posted to the window message queue
+    and is dispatched to every event handler bound to the control as `Event::Custom(id)`, with `EventData::Custom` as data.
+
+    The payload is freed after the event was dispatched, even if no event handler is bound to the control.
+
+    Because the payload does not need to be `Send`, events can only be raised on controls created by the current thread.
+    To communicate with another thread, use a `Notice`.
+
+    Errors:
+      - If the handle is not a window handle
+      - If the control was created by another thread
+      - If the message could not be posted (the window was destroyed or the message queue is full)
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    const DOCUMENT_SAVED: u32 = 1;
+
+    fn save(window: &nwg::Window) {
+        nwg::raise_event(&window.handle, DOCUMENT_SAVED, String::from("document.txt")).unwrap();
+    }
+
+    fn events(evt: nwg::Event, evt_data: &nwg::EventData) {
+        match evt {
+            nwg::Event::Custom(DOCUMENT_SAVED) => {
+                let path = evt_data.on_custom().payload::<String>();
+                println!("Saved {:?}", path);
+            },
+            _ => {}
+        }
+    }
+    ```
+*/
+pub fn raise_event<T: Any>(handle: &ControlHandle, id: u32, payload: T) -> Result<(), NwgError> {
+    use winapi::um::winuser::{PostMessageW, GetWindowThreadProcessId};
+    use winapi::um::processthreadsapi::GetCurrentThreadId;
+    use winapi::um::commctrl::{GetWindowSubclass, SetWindowSubclass};
+
+    let hwnd = match handle.hwnd() {
+        Some(h) => h,
+        None => { return Err(NwgError::events_binding("Custom events can only be raised on window controls")); }
+    };
+
+    unsafe {
+        if GetWindowThreadProcessId(hwnd, ptr::null_mut()) != GetCurrentThreadId() {
+            return Err(NwgError::events_binding("Custom events can only be raised on controls created by the current thread"));
+        }
+
+        // The subclass frees the payload once every event handler bound to the window received it
+        let mut tmp_value = 0;
+        if GetWindowSubclass(hwnd, Some(free_custom_event), 0, &mut tmp_value) == 0 {
+            SetWindowSubclass(hwnd, Some(free_custom_event), 0, 0);
+        }
+
+        let record = Box::new(CustomEventRecord { payload: Box::new(payload) });
+        let record_ptr = Box::into_raw(record);
+        if PostMessageW(hwnd, NWG_CUSTOM_EVENT, id as WPARAM, record_ptr as LPARAM) == 0 {
+            mem::drop(Box::from_raw(record_ptr));
+            return Err(NwgError::events_binding("Failed to post the custom event"));
+        }
+    }
+
+    Ok(())
+}
+
 /**
     High level function that handle the creation of custom window control or built in window control
 */
@@ -576,6 +654,12 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
     use winapi::um::winnt::WCHAR;
     use winapi::shared::minwindef::{HIWORD, LOWORD};
 
+    #[cfg(feature="list-view")]
+    use winapi::um::commctrl::{NMLVDISPINFOW, LVN_BEGINLABELEDITW, LVN_ENDLABELEDITW};
+
+    #[cfg(feature="tree-view")]
+    use winapi::um::commctrl::{NMTVDISPINFOW, TVN_ENDLABELEDITW};
+
     let callback_ptr = data as *mut *const Callback;
     let callback: &Callback = &**callback_ptr;
     let base_handle = ControlHandle::Hwnd(hwnd);
@@ -599,6 +683,14 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
         
             match code {
                 TTN_GETDISPINFOW => handle_tooltip_callback(mem::transmute::<_, *mut NMTTDISPINFOW>(l), callback),
+                #[cfg(feature="list-view")]
+                LVN_BEGINLABELEDITW | LVN_ENDLABELEDITW => {
+                    return handle_list_view_edit(hwnd, msg, w, l, code, mem::transmute::<_, *const NMLVDISPINFOW>(l), callback);
+                },
+                #[cfg(feature="tree-view")]
+                TVN_ENDLABELEDITW => {
+                    return handle_tree_item_rename(hwnd, msg, w, l, mem::transmute::<_, *const NMTVDISPINFOW>(l), callback);
+                },
                 _ => handle_default_notify_callback(mem::transmute::<_, *const NMHDR>(l), callback)
             }
         },
@@ -690,8 +782,13 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
         WM_ENTERSIZEMOVE => callback(Event::OnResizeBegin, NO_DATA, base_handle),
         WM_TIMER => callback(Event::OnTimerTick, NO_DATA, ControlHandle::Timer(hwnd, w as u32)),
         WM_MOVE => callback(Event::OnMove, NO_DATA, base_handle),
-        WM_HSCROLL => callback(Event::OnHorizontalScroll, NO_DATA, ControlHandle::Hwnd(l as HWND)),
-        WM_VSCROLL => callback(Event::OnVerticalScroll, NO_DATA, ControlHandle::Hwnd(l as HWND)),
+        WM_HSCROLL | WM_VSCROLL => {
+            // The scroll bar controls move their thumb in a raw event handler of the parent.
+            // The message goes through the other handlers first so that the event has the new position.
+            let result = DefSubclassProc(hwnd, msg, w, l);
+            handle_scroll_callback(hwnd, msg, w, l, callback);
+            return result;
+        },
         WM_MOUSEMOVE => callback(Event::OnMouseMove, NO_DATA, base_handle), 
         WM_LBUTTONUP => callback(Event::OnMousePress(MousePressEvent::MousePressLeftUp), NO_DATA,  base_handle), 
         WM_LBUTTONDOWN => callback(Event::OnMousePress(MousePressEvent::MousePressLeftDown), NO_DATA, base_handle), 
@@ -699,6 +796,28 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
         WM_RBUTTONDOWN => callback(Event::OnMousePress(MousePressEvent::MousePressRightDown), NO_DATA, base_handle),
         NOTICE_MESSAGE => callback(Event::OnNotice, NO_DATA, ControlHandle::Notice(hwnd, w as u32)),
         NWG_INIT => callback(Event::OnInit, NO_DATA, base_handle),
+        NWG_CUSTOM_EVENT => {
+            // The payload is freed by `free_custom_event` further down the subclass chain
+            let record = &*(l as *const CustomEventRecord);
+            let data = EventData::Custom(CustomEventData { payload: &*record.payload as *const dyn Any });
+            callback(Event::Custom(w as u32), data, base_handle);
+        },
+        #[cfg(feature = "event-recorder")]
+        NWG_REPLAY_EVENT => {
+            let replayed = &*(l as *const super::event_recorder::ReplayedEvent);
+            callback(replayed.event, replayed.event_data(), replayed.handle);
+        },
+        #[cfg(any(feature = "tree-view", feature = "list-view"))]
+        NWG_ITEM_DROP => {
+            let data = EventData::OnItemDrop(super::item_drag::dropped_item(l));
+            callback(Event::OnItemDrop, data, base_handle);
+        },
+        NWG_VALIDATION_CHANGED => callback(Event::OnValidationChanged, EventData::OnValidationChanged(w != 0), base_handle),
+        #[cfg(feature = "number-select")]
+        NWG_NUMBER_SELECT_CHANGED => {
+            let value = *(l as *const crate::NumberSelectValue);
+            callback(Event::OnNumberSelectChanged, EventData::OnNumberSelectChanged(value), base_handle);
+        },
         WM_CLOSE => {
             let mut should_exit = true;
             let data = EventData::OnWindowClose(WindowCloseData { data: &mut should_exit as *mut bool });
@@ -731,6 +850,28 @@ unsafe extern "system" fn process_raw_events(hwnd: HWND, msg: UINT, w: WPARAM, l
     }
 }
 
+/**
+    A window subclass procedure bound by `raise_event`. Frees the payload of custom events after every event handler
+    bound to the window received the message.
+*/
+unsafe extern "system" fn free_custom_event(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, _id: UINT_PTR, _data: DWORD_PTR) -> LRESULT {
+    use winapi::um::commctrl::{DefSubclassProc, RemoveWindowSubclass};
+    use winapi::um::winuser::WM_NCDESTROY;
+
+    match msg {
+        NWG_CUSTOM_EVENT => {
+            let result = DefSubclassProc(hwnd, msg, w, l);
+            mem::drop(Box::from_raw(l as *mut CustomEventRecord));
+            result
+        },
+        WM_NCDESTROY => {
+            RemoveWindowSubclass(hwnd, Some(free_custom_event), 0);
+            DefSubclassProc(hwnd, msg, w, l)
+        },
+        _ => DefSubclassProc(hwnd, msg, w, l)
+    }
+}
+
 fn button_commands(m: u16) -> Event {
     use winapi::um::winuser::{BN_CLICKED, BN_DBLCLK};
     match m {
@@ -750,11 +891,12 @@ fn edit_commands(m: u16) -> Event {
 }
 
 fn combo_commands(m: u16) -> Event {
-    use winapi::um::winuser::{CBN_CLOSEUP, CBN_DROPDOWN, CBN_SELCHANGE};
+    use winapi::um::winuser::{CBN_CLOSEUP, CBN_DROPDOWN, CBN_SELCHANGE, CBN_EDITCHANGE};
     match m {
         CBN_CLOSEUP => Event::OnComboBoxClosed,
         CBN_DROPDOWN => Event::OnComboBoxDropdown,
         CBN_SELCHANGE => Event::OnComboxBoxSelection,
+        CBN_EDITCHANGE => Event::OnComboBoxTextChanged,
         _ => Event::Unknown
     }
 }
@@ -769,6 +911,15 @@ fn datetimepick_commands(m: u32) -> Event {
     }
 }
 
+fn month_calendar_commands(m: u32) -> Event {
+    use winapi::um::commctrl::{MCN_SELECT, MCN_VIEWCHANGE};
+    match m {
+        MCN_SELECT => Event::OnCalendarSelect,
+        MCN_VIEWCHANGE => Event::OnCalendarViewChange,
+        _ => Event::Unknown
+    }
+}
+
 fn tabs_commands(m: u32) -> Event {
     use winapi::um::commctrl::{TCN_SELCHANGE, TCN_SELCHANGING};
     match m {
@@ -807,7 +958,7 @@ fn tree_commands(m: u32) -> Event {
 
 fn list_view_commands(m: u32) -> Event {
     use winapi::um::commctrl::{NM_KILLFOCUS, NM_SETFOCUS, LVN_DELETEALLITEMS,
-        LVN_DELETEITEM, LVN_INSERTITEM, LVN_ITEMACTIVATE, LVN_ITEMCHANGED};
+        LVN_DELETEITEM, LVN_INSERTITEM, LVN_ITEMACTIVATE, LVN_ITEMCHANGED, LVN_COLUMNCLICK};
 
     match m {
         LVN_DELETEALLITEMS => Event::OnListViewClear,
@@ -817,6 +968,7 @@ fn list_view_commands(m: u32) -> Event {
         LVN_ITEMCHANGED => Event::OnListViewItemChanged,
         NM_KILLFOCUS => Event::OnListViewFocusLost,
         NM_SETFOCUS => Event::OnListViewFocus,
+        LVN_COLUMNCLICK => Event::OnListViewColumnClick,
         _ => Event::Unknown
     }
 }
@@ -856,8 +1008,8 @@ fn tree_data(m: u32, notif_raw: *const NMHDR) -> EventData {
             let data = unsafe { &*(notif_raw as *const NMTVITEMCHANGE) };
             let item = TreeItem { handle: data.hItem };
             let action = TreeItemAction::State { 
-                new: TreeItemState::from_bits_truncate(data.uStateNew),
-                old: TreeItemState::from_bits_truncate(data.uStateOld)
+                new: TreeItemState::from_raw(data.uStateNew),
+                old: TreeItemState::from_raw(data.uStateOld)
             };
             EventData::OnTreeItemUpdate { item, action }
         },
@@ -874,7 +1026,7 @@ fn tree_data(_m: u32, _notif_raw: *const NMHDR) -> EventData {
 #[cfg(feature="list-view")]
 fn list_view_data(m: u32, notif_raw: *const NMHDR) -> EventData {
     use winapi::um::commctrl::{NMLISTVIEW, LVN_DELETEITEM, LVN_ITEMACTIVATE,
-        LVN_INSERTITEM, LVN_ITEMCHANGED, LVIS_SELECTED};
+        LVN_INSERTITEM, LVN_ITEMCHANGED, LVIS_SELECTED, LVN_COLUMNCLICK};
 
     match m {
         LVN_DELETEITEM => {
@@ -906,10 +1058,47 @@ fn list_view_data(m: u32, notif_raw: *const NMHDR) -> EventData {
                 selected: data.uNewState & LVIS_SELECTED == LVIS_SELECTED
             }
         },
+        LVN_COLUMNCLICK => {
+            let data: &NMLISTVIEW = unsafe { &*(notif_raw as *const NMLISTVIEW) };
+            EventData::OnListViewItemIndex { 
+                row_index: 0,
+                column_index: data.iSubItem as _
+            }
+        },
         _ => NO_DATA
     }
 }
 
+/// Raises `OnListViewItemChecked` if a `LVN_ITEMCHANGED` notification changed the checkbox of an item
+#[cfg(feature="list-view")]
+fn list_view_checked(m: u32, notif_raw: *const NMHDR, callback: &Callback) {
+    use winapi::um::commctrl::{NMLISTVIEW, LVN_ITEMCHANGED, LVIF_STATE, LVIS_STATEIMAGEMASK};
+
+    if m != LVN_ITEMCHANGED {
+        return;
+    }
+
+    let data: &NMLISTVIEW = unsafe { &*(notif_raw as *const NMLISTVIEW) };
+    let old_state = data.uOldState & LVIS_STATEIMAGEMASK;
+    let new_state = data.uNewState & LVIS_STATEIMAGEMASK;
+
+    // The state image goes from 0 to "unchecked" when an item is inserted
+    if data.uChanged & LVIF_STATE == 0 || old_state == 0 || old_state == new_state {
+        return;
+    }
+
+    let data = EventData::OnListViewItemChecked {
+        row_index: data.iItem as _,
+        checked: (new_state >> 12) == 2
+    };
+
+    callback(Event::OnListViewItemChecked, data, ControlHandle::Hwnd(unsafe { (*notif_raw).hwndFrom }));
+}
+
+#[cfg(not(feature="list-view"))]
+fn list_view_checked(_m: u32, _notif_raw: *const NMHDR, _callback: &Callback) {
+}
+
 #[cfg(not(feature="list-view"))]
 fn list_view_data(_m: u32, _notif_raw: *const NMHDR) -> EventData {
     // If list-view is not enabled, the data type won't be available so we return NO_DATA
@@ -947,6 +1136,106 @@ unsafe fn listbox_commands(m: u16) -> Event {
     }
 }
 
+#[cfg(any(feature="list-view", feature="tree-view"))]
+thread_local! {
+    /// The answer of the event handlers already called for the label edition notification being dispatched.
+    /// `Some(true)` if one of them rejected the edition. `None` outside of a dispatch.
+    static LABEL_EDIT_REJECTED: std::cell::Cell<Option<bool>> = std::cell::Cell::new(None);
+}
+
+/**
+    Runs `handler` for a label edition notification, then forwards the notification to the next event handlers bound to the window.
+    Returns `false` if `handler` or any of the next handlers rejected the edition. Only the value returned to the first handler
+    of the chain is sent back to the control.
+*/
+#[cfg(any(feature="list-view", feature="tree-view"))]
+unsafe fn chain_label_edit<F: FnOnce() -> bool>(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, handler: F) -> bool {
+    use winapi::um::commctrl::DefSubclassProc;
+
+    // The state of the current chain is put aside while the handler runs, in case it starts another edition
+    let chain = LABEL_EDIT_REJECTED.with(|r| r.replace(None));
+    let accept = handler();
+
+    LABEL_EDIT_REJECTED.with(|r| r.set(Some(!accept)));
+    DefSubclassProc(hwnd, msg, w, l);
+    let rejected = LABEL_EDIT_REJECTED.with(|r| r.replace(chain)).unwrap_or(!accept);
+
+    if let Some(chain_rejected) = chain {
+        LABEL_EDIT_REJECTED.with(|r| r.set(Some(chain_rejected || rejected)));
+    }
+
+    !rejected
+}
+
+/// Dispatch the label edition notifications of a list view. The edition is cancelled or rejected
+/// if any of the event handlers bound to the parent window rejects it.
+#[cfg(feature="list-view")]
+unsafe fn handle_list_view_edit(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, code: u32, info: *const winapi::um::commctrl::NMLVDISPINFOW, callback: &Callback) -> LRESULT {
+    use winapi::um::commctrl::LVN_BEGINLABELEDITW;
+    use super::base_helper::from_wide_ptr;
+    use crate::ListViewEditData;
+
+    let info = &*info;
+    let begin = code == LVN_BEGINLABELEDITW;
+    let text = match begin || info.item.pszText.is_null() {
+        true => None,
+        false => Some(from_wide_ptr(info.item.pszText, None))
+    };
+
+    let evt = match begin {
+        true => Event::OnListViewBeginEdit,
+        false => Event::OnListViewEndEdit
+    };
+
+    let accept = chain_label_edit(hwnd, msg, w, l, || {
+        let mut accept = true;
+        let data = EventData::OnListViewEdit(ListViewEditData {
+            row_index: info.item.iItem as usize,
+            column_index: info.item.iSubItem as usize,
+            text,
+            accept: &mut accept as *mut bool
+        });
+
+        callback(evt, data, ControlHandle::Hwnd(info.hdr.hwndFrom));
+        accept
+    });
+
+    // Begin: returning TRUE cancels the edition. End: returning TRUE accepts the new text
+    match begin {
+        true => (!accept) as LRESULT,
+        false => accept as LRESULT
+    }
+}
+
+/// Dispatch the end of a tree view label edition. The new text is rejected if any of the event handlers bound to the parent window rejects it.
+#[cfg(feature="tree-view")]
+unsafe fn handle_tree_item_rename(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, info: *const winapi::um::commctrl::NMTVDISPINFOW, callback: &Callback) -> LRESULT {
+    use winapi::um::commctrl::DefSubclassProc;
+    use super::base_helper::from_wide_ptr;
+    use crate::{TreeItem, TreeItemRenameData};
+
+    // A null text means that the edition was cancelled
+    let info = &*info;
+    if info.item.pszText.is_null() {
+        return DefSubclassProc(hwnd, msg, w, l);
+    }
+
+    let accept = chain_label_edit(hwnd, msg, w, l, || {
+        let mut accept = true;
+        let data = EventData::OnTreeItemRename(TreeItemRenameData {
+            item: TreeItem { handle: info.item.hItem },
+            text: from_wide_ptr(info.item.pszText, None),
+            accept: &mut accept as *mut bool
+        });
+
+        callback(Event::OnTreeItemRenamed, data, ControlHandle::Hwnd(info.hdr.hwndFrom));
+        accept
+    });
+
+    // Returning TRUE accepts the new text
+    accept as LRESULT
+}
+
 unsafe fn handle_tooltip_callback<'a>(notif: *mut NMTTDISPINFOW, callback: &Callback) {
     use crate::events::ToolTipTextData;
 
@@ -956,6 +1245,71 @@ unsafe fn handle_tooltip_callback<'a>(notif: *mut NMTTDISPINFOW, callback: &Call
     callback(Event::OnTooltipText, data, handle);
 }
 
+unsafe fn handle_scroll_callback(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, callback: &Callback) {
+    use winapi::um::winuser::{WM_HSCROLL, SB_HORZ, SB_VERT, SB_CTL, SIF_POS, SIF_TRACKPOS, SCROLLINFO, GetScrollInfo};
+    use winapi::um::winuser::{SB_LINEUP, SB_LINEDOWN, SB_PAGEUP, SB_PAGEDOWN, SB_THUMBTRACK, SB_THUMBPOSITION, SB_TOP, SB_BOTTOM, SB_ENDSCROLL};
+    use winapi::um::commctrl::{TRACKBAR_CLASS, TBM_GETPOS};
+    use winapi::shared::minwindef::LOWORD;
+    use super::window_helper::{send_message, get_window_class_name};
+    use crate::ScrollAction;
+
+    let action = match LOWORD(w as u32) as LPARAM {
+        SB_LINEUP => ScrollAction::LineUp,
+        SB_LINEDOWN => ScrollAction::LineDown,
+        SB_PAGEUP => ScrollAction::PageUp,
+        SB_PAGEDOWN => ScrollAction::PageDown,
+        SB_THUMBTRACK => ScrollAction::ThumbTrack,
+        SB_THUMBPOSITION => ScrollAction::ThumbPosition,
+        SB_TOP => ScrollAction::Top,
+        SB_BOTTOM => ScrollAction::Bottom,
+        SB_ENDSCROLL => ScrollAction::EndScroll,
+        _ => ScrollAction::Unknown
+    };
+
+    let event = match msg == WM_HSCROLL {
+        true => Event::OnHorizontalScroll,
+        false => Event::OnVerticalScroll
+    };
+
+    // `l` is the scroll bar or trackbar control, or null for the scroll bars of the window itself
+    let control = l as HWND;
+    let trackbar = !control.is_null() && get_window_class_name(control) == TRACKBAR_CLASS;
+
+    let position = if trackbar {
+        send_message(control, TBM_GETPOS, 0, 0) as i32
+    } else {
+        let (target, bar) = match (control.is_null(), event) {
+            (false, _) => (control, SB_CTL),
+            (true, Event::OnHorizontalScroll) => (hwnd, SB_HORZ),
+            (true, _) => (hwnd, SB_VERT),
+        };
+
+        let mut si: SCROLLINFO = mem::zeroed();
+        si.cbSize = mem::size_of::<SCROLLINFO>() as u32;
+        si.fMask = SIF_POS | SIF_TRACKPOS;
+        GetScrollInfo(target, bar as i32, &mut si);
+
+        match action {
+            ScrollAction::ThumbTrack => si.nTrackPos,
+            _ => si.nPos
+        }
+    };
+
+    // As before the scroll data was added, the handle is null for the scroll bars of the window
+    let handle = ControlHandle::Hwnd(control);
+    callback(event, EventData::OnScroll { position, action }, handle);
+
+    // `EndScroll` and `ThumbPosition` are sent after the value was changed by another action
+    let changed = match action {
+        ScrollAction::EndScroll | ScrollAction::ThumbPosition => false,
+        _ => true
+    };
+
+    if trackbar && changed {
+        callback(Event::TrackBarChanged, EventData::OnScroll { position, action }, handle);
+    }
+}
+
 unsafe fn handle_default_notify_callback<'a>(notif_raw: *const NMHDR, callback: &Callback){
     use std::os::windows::ffi::OsStringExt;
     use std::ffi::OsString;
@@ -973,10 +1327,14 @@ unsafe fn handle_default_notify_callback<'a>(notif_raw: *const NMHDR, callback:
 
     match &class_name as &str {
         "SysDateTimePick32" => callback(datetimepick_commands(code), NO_DATA, handle),
+        winapi::um::commctrl::MONTHCAL_CLASS => callback(month_calendar_commands(code), NO_DATA, handle),
         "SysTabControl32" => callback(tabs_commands(code), NO_DATA, handle),
         "msctls_trackbar32" => callback(track_commands(code), NO_DATA, handle),
         winapi::um::commctrl::WC_TREEVIEW => callback(tree_commands(code), tree_data(code, notif_raw), handle),
-        winapi::um::commctrl::WC_LISTVIEW => callback(list_view_commands(code), list_view_data(code, notif_raw), handle),
+        winapi::um::commctrl::WC_LISTVIEW => {
+            callback(list_view_commands(code), list_view_data(code, notif_raw), handle);
+            list_view_checked(code, notif_raw, callback);
+        },
         _ => {}
     }
 }
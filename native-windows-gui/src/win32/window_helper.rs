@@ -10,6 +10,16 @@ use std::{ptr, mem};
 pub const NOTICE_MESSAGE: UINT = WM_USER+100;
 pub const NWG_INIT: UINT = WM_USER + 101;
 pub const NWG_TRAY: UINT = WM_USER + 102;
+pub const NWG_CUSTOM_EVENT: UINT = WM_USER + 103;
+#[cfg(feature = "event-recorder")]
+pub const NWG_REPLAY_EVENT: UINT = WM_USER + 104;
+#[cfg(feature = "list-view")]
+pub const NWG_LIST_VIEW_END_EDIT: UINT = WM_USER + 105;
+#[cfg(any(feature = "tree-view", feature = "list-view"))]
+pub const NWG_ITEM_DROP: UINT = WM_USER + 106;
+pub const NWG_VALIDATION_CHANGED: UINT = WM_USER + 107;
+#[cfg(feature = "number-select")]
+pub const NWG_NUMBER_SELECT_CHANGED: UINT = WM_USER + 108;
 
 
 /// Haha you maybe though that destroying windows would be easy right? WRONG.
@@ -120,7 +130,6 @@ pub fn get_style(handle: HWND) -> UINT {
     get_window_long(handle, GWL_STYLE) as UINT
 }
 
-#[cfg(feature="list-view")]
 pub fn set_style(handle: HWND, style: u32) {
     use ::winapi::um::winuser::GWL_STYLE;
     set_window_long(handle, GWL_STYLE, style as usize);
@@ -274,7 +283,6 @@ pub unsafe fn set_window_enabled(handle: HWND, enabled: bool) {
     UpdateWindow(handle);
 }
 
-#[cfg(feature = "tabs")]
 pub unsafe fn get_window_class_name(handle: HWND) -> String {
     use std::ffi::OsString;
     use std::os::windows::ffi::OsStringExt;